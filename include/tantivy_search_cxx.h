@@ -718,6 +718,7 @@ namespace TANTIVY {
   struct FFIError;
   struct FFIBoolResult;
   struct FFIU64Result;
+  struct FFIStringResult;
   struct FFIVecU8Result;
   struct FFIVecRowIdWithScoreResult;
   struct FFIVecDocWithFreqResult;
@@ -804,6 +805,16 @@ struct FFIU64Result final {
 };
 #endif // CXXBRIDGE1_STRUCT_TANTIVY$FFIU64Result
 
+#ifndef CXXBRIDGE1_STRUCT_TANTIVY$FFIStringResult
+#define CXXBRIDGE1_STRUCT_TANTIVY$FFIStringResult
+struct FFIStringResult final {
+  ::rust::String result;
+  ::TANTIVY::FFIError error;
+
+  using IsRelocatable = ::std::true_type;
+};
+#endif // CXXBRIDGE1_STRUCT_TANTIVY$FFIStringResult
+
 #ifndef CXXBRIDGE1_STRUCT_TANTIVY$FFIVecU8Result
 #define CXXBRIDGE1_STRUCT_TANTIVY$FFIVecU8Result
 struct FFIVecU8Result final {
@@ -850,9 +861,27 @@ struct FFIFieldTokenNumsResult final {
 // arguments:
 // - `index_path`: index directory.
 // - `column_names`: which columns will be used to build index.
-// - `index_json_parameter`: config index with json.
+// - `index_json_parameter`: config index with json, non-text columns are declared
+//                           with `type`, such as `{"price": {"type": "f64"}}`,
+//                           json column is declared as `{"attrs": {"type": "json"}}`,
+//                           json column is stored only with `store_doc` in its tokenizer, docs with a
+//                           not stored json column can't be rebuilt by remap, merge parts or alter column,
+//                           text and json column can set `record` (`basic`, `freqs` or
+//                           `positions`) and `fieldnorms` to shrink index size, docstore
+//                           compressor and block size are set under the `__docstore__` key,
+//                           reader `reload_policy` (`on_commit` or `manual`) is set under the `__reader__` key.
 ::TANTIVY::FFIBoolResult ffi_create_index_with_parameter(::std::string const &index_path, ::std::vector<::std::string> const &column_names, ::std::string const &index_json_parameter) noexcept;
 
+// Create tantivy index with writer settings given for current call.
+// arguments:
+// - `index_path`: index directory.
+// - `column_names`: which columns will be used to build index.
+// - `index_json_parameter`: config index with json, writer settings can be
+//                           persisted under the `__writer__` key.
+// - `writer_json_parameter`: override persisted writer settings, such as
+//                            `heap_size`, `num_threads` and `merge_policy`.
+::TANTIVY::FFIBoolResult ffi_create_index_with_writer_parameter(::std::string const &index_path, ::std::vector<::std::string> const &column_names, ::std::string const &index_json_parameter, ::std::string const &writer_json_parameter) noexcept;
+
 // Create tantivy index by default.
 // arguments:
 // - `index_path`: index directory.
@@ -864,25 +893,154 @@ struct FFIFieldTokenNumsResult final {
 // - `index_path`: index directory.
 // - `row_id`: row_id given by ClickHouse.
 // - `column_names`: align with column_docs.
-// - `column_docs`: align with column_names.
+// - `column_docs`: align with column_names, values of typed columns are given as string,
+//                  empty means null, date is unix timestamp in seconds.
 ::TANTIVY::FFIBoolResult ffi_index_multi_column_docs(::std::string const &index_path, ::std::uint64_t row_id, ::std::vector<::std::string> const &column_names, ::std::vector<::std::string> const &column_docs) noexcept;
 
+// Index a contiguous block of rows with given rowIds.
+// arguments:
+// - `index_path`: index directory.
+// - `row_ids`: row_ids given by ClickHouse, one for each row.
+// - `column_names`: which columns will be indexed.
+// - `column_docs`: columnar docs, size is `column_names.size() * row_ids.size()`,
+//                  all rows of the first column come first.
+::TANTIVY::FFIBoolResult ffi_index_multi_column_docs_batch(::std::string const &index_path, ::std::vector<::std::uint64_t> const &row_ids, ::std::vector<::std::string> const &column_names, ::std::vector<::std::string> const &column_docs) noexcept;
+
+// Replace the doc with given rowId, or index it if not exists, delete and add are applied atomically.
+// Same as index, changes are visible after commit.
+// arguments:
+// - `index_path`: index directory.
+// - `row_id`: row_id given by ClickHouse.
+// - `column_names`: align with column_docs.
+// - `column_docs`: align with column_names.
+::TANTIVY::FFIBoolResult ffi_upsert_multi_column_docs(::std::string const &index_path, ::std::uint64_t row_id, ::std::vector<::std::string> const &column_names, ::std::vector<::std::string> const &column_docs) noexcept;
+
+// Index one row whose columns may have multiple values, such as `Array(String)`.
+// Phrase query won't match across values of the same column.
+// arguments:
+// - `index_path`: index directory.
+// - `row_id`: row_id given by ClickHouse.
+// - `column_names`: which columns will be indexed.
+// - `column_docs`: flattened values, `value_counts[0]` values of the first column come first.
+// - `value_counts`: align with column_names, number of values of each column.
+::TANTIVY::FFIBoolResult ffi_index_multi_valued_docs(::std::string const &index_path, ::std::uint64_t row_id, ::std::vector<::std::string> const &column_names, ::std::vector<::std::string> const &column_docs, ::std::vector<::std::uint32_t> const &value_counts) noexcept;
+
 // Delete a group of rowIds.
 // arguments:
 // - `index_path`: index directory.
 // - `row_ids`: a group of rowIds need be deleted.
 ::TANTIVY::FFIBoolResult ffi_delete_row_ids(::std::string const &index_path, ::std::vector<::std::uint32_t> const &row_ids) noexcept;
 
-// Commit index writer
+// Delete a group of u64 rowIds.
+// arguments:
+// - `index_path`: index directory.
+// - `row_ids`: a group of u64 rowIds need be deleted.
+::TANTIVY::FFIBoolResult ffi_delete_row_ids_u64(::std::string const &index_path, ::std::vector<::std::uint64_t> const &row_ids) noexcept;
+
+// Delete rowIds marked in u8 bitmap with one row_id set query, such as lightweight delete mask.
+// arguments:
+// - `index_path`: index directory.
+// - `u8_bitmap`: bit `i` set means rowId `i` need be deleted.
+::TANTIVY::FFIBoolResult ffi_delete_row_ids_by_u8_bitmap(::std::string const &index_path, ::std::vector<::std::uint8_t> const &u8_bitmap) noexcept;
+
+// Delete u64 rowIds in serialized RoaringTreemap with one row_id set query.
+// arguments:
+// - `index_path`: index directory.
+// - `treemap_bytes`: rowIds need be deleted, serialized in RoaringTreemap portable format.
+::TANTIVY::FFIBoolResult ffi_delete_row_ids_by_treemap(::std::string const &index_path, ::std::vector<::std::uint8_t> const &treemap_bytes) noexcept;
+
+// Delete all rows matching the query, no need to know rowIds.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute query, json column uses path such as `attrs.color`.
+// - `query`: term, sentence or `LIKE` pattern, same as bitmap queries.
+// - `query_type`: one of `term`, `sentence` and `regex`.
+::TANTIVY::FFIBoolResult ffi_delete_by_query(::std::string const &index_path, ::std::string const &column_name, ::std::string const &query, ::std::string const &query_type) noexcept;
+
+// Commit index writer, the payload of the last commit is not kept,
+// use `ffi_index_writer_commit_with_payload` to keep one.
 // arguments:
 // - `index_path`: index directory.
 ::TANTIVY::FFIBoolResult ffi_index_writer_commit(::std::string const &index_path) noexcept;
 
+// Rollback index writer, discard all uncommitted adds and deletes since last commit.
+// arguments:
+// - `index_path`: index directory.
+::TANTIVY::FFIBoolResult ffi_index_writer_rollback(::std::string const &index_path) noexcept;
+
+// Commit index writer with a user payload, return the commit opstamp.
+// arguments:
+// - `index_path`: index directory.
+// - `payload`: stored in commit meta, such as part name or mutation version.
+//              empty string means no payload, the payload of the last commit is cleared.
+::TANTIVY::FFIU64Result ffi_index_writer_commit_with_payload(::std::string const &index_path, ::std::string const &payload) noexcept;
+
 // Free index writer
 // arguments:
 // - `index_path`: index directory.
 ::TANTIVY::FFIBoolResult ffi_free_index_writer(::std::string const &index_path) noexcept;
 
+// Open index writer for an existing index, docs can be appended to it.
+// arguments:
+// - `index_path`: index directory.
+::TANTIVY::FFIBoolResult ffi_open_index_writer(::std::string const &index_path) noexcept;
+
+// Merge committed segments, wait for completion and remove old files.
+// If columns have been dropped, docs are rebuilt without them first, which fails if there are uncommitted docs.
+// arguments:
+// - `index_path`: index directory.
+// - `num_segments`: max segments number after merge, 0 is treated as 1.
+// - `purge_deleted`: rewrite segments which contain deleted docs.
+::TANTIVY::FFIBoolResult ffi_merge_index(::std::string const &index_path, ::std::uint32_t num_segments, bool purge_deleted) noexcept;
+
+// Merge several part indexes into a new index, row_ids are remapped.
+// Columns dropped in any part stay dropped in the new index, their values are not copied.
+// Postings are not copied, docs of each part are rebuilt the same way as `ffi_remap_row_ids`
+// and indexed again. The target directory is kept empty if merging fails.
+// arguments:
+// - `index_path`: new index directory.
+// - `source_index_paths`: index directories of the parts to merge.
+// - `row_id_mappings`: concatenated row_id mappings of all parts, for each part
+//                      `mapping[old_row_id]` is the new row_id, UINT64_MAX means dropped.
+// - `mapping_sizes`: row_id mapping size of each part.
+::TANTIVY::FFIBoolResult ffi_merge_parts(::std::string const &index_path, ::std::vector<::std::string> const &source_index_paths, ::std::vector<::std::uint64_t> const &row_id_mappings, ::std::vector<::std::uint64_t> const &mapping_sizes) noexcept;
+
+// Remap rowIds of an existing index, fails if there are uncommitted docs.
+// Text columns are rebuilt from their postings without tokenizing again, stored values keep
+// their text, positions and field norms are kept. Stored json columns are read from the
+// doc store and tokenized again, not stored json columns can't be remapped.
+// arguments:
+// - `index_path`: index directory.
+// - `row_id_mapping`: `row_id_mapping[old_row_id]` is the new row_id, UINT64_MAX means deleted.
+::TANTIVY::FFIBoolResult ffi_remap_row_ids(::std::string const &index_path, ::std::vector<::std::uint64_t> const &row_id_mapping) noexcept;
+
+// Add an offset to all rowIds of an existing index, fails if there are uncommitted docs.
+// Docs are rebuilt the same way as `ffi_remap_row_ids`.
+// arguments:
+// - `index_path`: index directory.
+// - `offset`: will be added to each rowId, can be negative.
+::TANTIVY::FFIBoolResult ffi_offset_row_ids(::std::string const &index_path, ::std::int64_t offset) noexcept;
+
+// Add a column to an existing index, existing docs have no value for it.
+// Tantivy schema is fixed once an index is created, so all docs are rebuilt into a new index
+// which then replaces the original directory. Fails if there are uncommitted docs, adding or
+// deleting docs is blocked until the index is replaced.
+// Dropped columns are removed from the new schema, a dropped column name can be added again.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: new column name.
+// - `column_json_parameter`: config of new column, such as `{"tokenizer": {"type": "default"}}`.
+::TANTIVY::FFIBoolResult ffi_add_column(::std::string const &index_path, ::std::string const &column_name, ::std::string const &column_json_parameter) noexcept;
+
+// Drop a column of an existing index without rebuilding docs, it can't be indexed or queried any more.
+// The column stays in tantivy schema until docs are rebuilt by `ffi_merge_index`, `ffi_add_column`,
+// `ffi_merge_parts`, `ffi_remap_row_ids` or `ffi_offset_row_ids`, which reclaim its space.
+// Segments merged in background keep the column.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: column need be dropped.
+::TANTIVY::FFIBoolResult ffi_drop_column(::std::string const &index_path, ::std::string const &column_name) noexcept;
+
 // Load index reader
 // arguments:
 // - `index_path`: index directory.
@@ -898,10 +1056,15 @@ struct FFIFieldTokenNumsResult final {
 // - `index_path`: index directory.
 ::TANTIVY::FFIU64Result ffi_get_indexed_doc_counts(::std::string const &index_path) noexcept;
 
+// Get payload stored by the last commit, empty string means no payload.
+// arguments:
+// - `index_path`: index directory.
+::TANTIVY::FFIStringResult ffi_get_commit_payload(::std::string const &index_path) noexcept;
+
 // Execute a term query and return rowIds u8 bitmap.
 // arguments:
 // - `index_path`: index directory.
-// - `column_name`: which column will execute search.
+// - `column_name`: which column will execute search, json column uses path such as `attrs.color`.
 // - `term`: term needs to be searched.
 ::TANTIVY::FFIVecU8Result ffi_query_term_bitmap(::std::string const &index_path, ::std::string const &column_name, ::std::string const &term) noexcept;
 
@@ -926,6 +1089,34 @@ struct FFIFieldTokenNumsResult final {
 // - `pattern`: pattern should be given by ClickHouse.
 ::TANTIVY::FFIVecU8Result ffi_regex_term_bitmap(::std::string const &index_path, ::std::string const &column_name, ::std::string const &pattern) noexcept;
 
+// Execute a term query and return u64 rowIds as serialized RoaringTreemap.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `term`: term needs to be searched.
+::TANTIVY::FFIVecU8Result ffi_query_term_bitmap_u64(::std::string const &index_path, ::std::string const &column_name, ::std::string const &term) noexcept;
+
+// Execute a group of terms query and return u64 rowIds as serialized RoaringTreemap.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `terms`: terms need to be searched.
+::TANTIVY::FFIVecU8Result ffi_query_terms_bitmap_u64(::std::string const &index_path, ::std::string const &column_name, ::std::vector<::std::string> const &terms) noexcept;
+
+// Execute a sentence query and return u64 rowIds as serialized RoaringTreemap.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `sentence`: sentence needs to be searched.
+::TANTIVY::FFIVecU8Result ffi_query_sentence_bitmap_u64(::std::string const &index_path, ::std::string const &column_name, ::std::string const &sentence) noexcept;
+
+// Execute a regex query and return u64 rowIds as serialized RoaringTreemap.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `pattern`: pattern should be given by ClickHouse.
+::TANTIVY::FFIVecU8Result ffi_regex_term_bitmap_u64(::std::string const &index_path, ::std::string const &column_name, ::std::string const &pattern) noexcept;
+
 // Execute a bm25 query.
 // arguments:
 // - `index_path`: index directory.
@@ -936,6 +1127,59 @@ struct FFIFieldTokenNumsResult final {
 // - `statistics`: for multi parts bm25 statistics info.
 ::TANTIVY::FFIVecRowIdWithScoreResult ffi_bm25_search(::std::string const &index_path, ::std::string const &sentence, ::std::vector<::std::string> const &column_names, ::std::uint32_t top_k, ::std::vector<::std::uint8_t> const &u8_alive_bitmap, bool query_with_filter, bool enable_nlq, bool operator_or, ::TANTIVY::Statistics const &statistics) noexcept;
 
+// Execute a bm25 query, alive rowIds are given by serialized RoaringTreemap.
+// arguments:
+// - `index_path`: index directory.
+// - `sentence`: from ClickHouse TextSearch function.
+// - `top_k`: only return top k related results.
+// - `u64_alive_bitmap`: alive u64 row ids given by serialized RoaringTreemap.
+// - `query_with_filter`:use alive_bitmap or not.
+// - `statistics`: for multi parts bm25 statistics info.
+::TANTIVY::FFIVecRowIdWithScoreResult ffi_bm25_search_u64(::std::string const &index_path, ::std::string const &sentence, ::std::vector<::std::string> const &column_names, ::std::uint32_t top_k, ::std::vector<::std::uint8_t> const &u64_alive_bitmap, bool query_with_filter, bool enable_nlq, bool operator_or, ::TANTIVY::Statistics const &statistics) noexcept;
+
+// Execute a term query with filter on typed columns.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `term`: term needs to be searched.
+// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+::TANTIVY::FFIVecU8Result ffi_query_term_bitmap_with_filter(::std::string const &index_path, ::std::string const &column_name, ::std::string const &term, ::std::string const &filter_query) noexcept;
+
+// Execute a group of terms query with filter on typed columns.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `terms`: terms need to be searched.
+// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+::TANTIVY::FFIVecU8Result ffi_query_terms_bitmap_with_filter(::std::string const &index_path, ::std::string const &column_name, ::std::vector<::std::string> const &terms, ::std::string const &filter_query) noexcept;
+
+// Execute a sentence query with filter on typed columns.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `sentence`: sentence needs to be searched.
+// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+::TANTIVY::FFIVecU8Result ffi_query_sentence_bitmap_with_filter(::std::string const &index_path, ::std::string const &column_name, ::std::string const &sentence, ::std::string const &filter_query) noexcept;
+
+// Execute a regex query with filter on typed columns.
+// arguments:
+// - `index_path`: index directory.
+// - `column_name`: which column will execute search.
+// - `pattern`: pattern should be given by ClickHouse.
+// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+::TANTIVY::FFIVecU8Result ffi_regex_term_bitmap_with_filter(::std::string const &index_path, ::std::string const &column_name, ::std::string const &pattern, ::std::string const &filter_query) noexcept;
+
+// Execute a bm25 query with filter on typed columns.
+// arguments:
+// - `index_path`: index directory.
+// - `sentence`: from ClickHouse TextSearch function.
+// - `top_k`: only return top k related results.
+// - `u8_alive_bitmap`: alive row ids given by u8 bitmap.
+// - `query_with_filter`:use alive_bitmap or not.
+// - `statistics`: for multi parts bm25 statistics info.
+// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+::TANTIVY::FFIVecRowIdWithScoreResult ffi_bm25_search_with_filter(::std::string const &index_path, ::std::string const &sentence, ::std::vector<::std::string> const &column_names, ::std::uint32_t top_k, ::std::vector<::std::uint8_t> const &u8_alive_bitmap, bool query_with_filter, bool enable_nlq, bool operator_or, ::TANTIVY::Statistics const &statistics, ::std::string const &filter_query) noexcept;
+
 // Get doc freq for current part.
 // arguments:
 // - `index_path`: index directory.
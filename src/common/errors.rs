@@ -27,6 +27,11 @@ pub enum IndexUtilsError {
     #[error("Failed to config tokenizer. '{0}'")]
     ConfigTokenizerError(String),
 
+    #[error("Unsupported index setting format. '{0}'")]
+    UnsupportedFormatError(String),
+    #[error("Index setting doesn't match index schema. '{0}'")]
+    ColumnNamesMismatchError(String),
+
     #[error(transparent)]
     TokenizerError(#[from] TokenizerError),
 }
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 use std::iter::zip;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex, RwLock};
 use once_cell::sync::Lazy;
 use tantivy::{collector::Count, Document, Index, merge_policy::LogMergePolicy, Opstamp, query::QueryParser, ReloadPolicy, schema::{FAST, INDEXED, Schema, TEXT}};
 use tantivy::schema::{Field, STORED};
@@ -9,6 +10,7 @@ use crate::{FFI_INDEX_WRITER_CACHE, index::bridge::IndexWriterBridge};
 use crate::common::constants::FFI_INDEX_SEARCHER_CACHE;
 use crate::ffi::{DocWithFreq, FieldTokenNums, RowIdWithScore, Statistics};
 use crate::search::bridge::index_reader_bridge::IndexReaderBridge;
+use crate::index::implements::{commit_index, create_index_with_parameter, index_multi_column_docs_batch};
 use crate::search::implements::{bm25_natural_language_search, bm25_standard_search, get_doc_freq, get_total_num_docs, get_total_num_tokens, load_index_reader};
 
 pub struct SinglePartTest;
@@ -107,7 +109,10 @@ impl SinglePartTest {
         let bridge = Arc::new(IndexWriterBridge {
             path: index_directory.to_string(),
             index,
-            writer: Mutex::new(Some(writer)),
+            merge_policy: writer.get_merge_policy(),
+            writer: RwLock::new(Some(writer)),
+            pending_operations: AtomicBool::new(false),
+            dropped_columns: RwLock::new(Vec::new()),
         });
         if !skip_insert_cache {
             FFI_INDEX_WRITER_CACHE.set_index_writer_bridge(index_directory.to_string(), bridge.clone()).expect("");
@@ -115,6 +120,75 @@ impl SinglePartTest {
         return bridge;
     }
 
+    /// Create index with `title` text column and typed columns, index 5 rows and load reader.
+    /// | row_id | title  | price | stock | on_sale | created    |
+    /// | 0      | COL1-0 | 10.5  | 3     | true    | 1700000000 |
+    /// | 1      | COL1-1 | 99    | -1    | false   | 1700086400 |
+    /// | 2      | COL1-2 | 150   | 0     | 1       | 1700172800 |
+    /// | 3      | COL1-3 | null  | 7     | 0       | 1700259200 |
+    /// | 4      | COL1-4 | 45.25 | 12    | true    | 1700345600 |
+    #[allow(dead_code)]
+    pub fn index_typed_columns_docs(index_directory: &str) {
+        let index_json_parameter = r#"
+            {
+                "title": { "tokenizer": { "type": "default" } },
+                "price": { "type": "f64" },
+                "stock": { "type": "i64" },
+                "on_sale": { "type": "bool" },
+                "created": { "type": "date" }
+            }
+        "#;
+        let column_names: Vec<String> =
+            ["title", "price", "stock", "on_sale", "created"].iter().map(|name| name.to_string()).collect();
+        create_index_with_parameter(index_directory, &column_names, index_json_parameter).unwrap();
+
+        let row_ids: Vec<u64> = (0..5).collect();
+        let mut column_docs: Vec<String> = Self::get_mocked_docs()[0].iter().map(|doc| doc.to_string()).collect();
+        column_docs.extend(["10.5", "99", "150", "", "45.25"].iter().map(|value| value.to_string()));
+        column_docs.extend(["3", "-1", "0", "7", "12"].iter().map(|value| value.to_string()));
+        column_docs.extend(["true", "false", "1", "0", "true"].iter().map(|value| value.to_string()));
+        column_docs.extend(
+            ["1700000000", "1700086400", "1700172800", "1700259200", "1700345600"].iter().map(|value| value.to_string())
+        );
+        index_multi_column_docs_batch(index_directory, &row_ids, &column_names, column_docs).unwrap();
+        commit_index(index_directory).unwrap();
+        assert!(load_index_reader(index_directory).unwrap());
+    }
+
+    /// Create index with `title` text column and `attrs` json column, index 5 rows and load reader.
+    /// | row_id | attrs                                                     |
+    /// | 0      | {"color": "Red", "size": "large", "tags": ["summer sale"]} |
+    /// | 1      | {"color": "blue", "material": {"name": "cotton red"}}     |
+    /// | 2      | {"color": "red blue"}                                     |
+    /// | 3      | null                                                      |
+    /// | 4      | {"size": "red"}                                           |
+    #[allow(dead_code)]
+    pub fn index_json_column_docs(index_directory: &str) {
+        let index_json_parameter = r#"
+            {
+                "title": { "tokenizer": { "type": "default" } },
+                "attrs": { "type": "json", "tokenizer": { "type": "default", "store_doc": true } }
+            }
+        "#;
+        let column_names: Vec<String> = vec!["title".to_string(), "attrs".to_string()];
+        create_index_with_parameter(index_directory, &column_names, index_json_parameter).unwrap();
+
+        let row_ids: Vec<u64> = (0..5).collect();
+        let mut column_docs: Vec<String> = Self::get_mocked_docs()[0].iter().map(|doc| doc.to_string()).collect();
+        column_docs.extend(
+            [
+                r#"{"color": "Red", "size": "large", "tags": ["summer sale"]}"#,
+                r#"{"color": "blue", "material": {"name": "cotton red"}}"#,
+                r#"{"color": "red blue"}"#,
+                "",
+                r#"{"size": "red"}"#,
+            ].iter().map(|value| value.to_string())
+        );
+        index_multi_column_docs_batch(index_directory, &row_ids, &column_names, column_docs).unwrap();
+        commit_index(index_directory).unwrap();
+        assert!(load_index_reader(index_directory).unwrap());
+    }
+
     #[allow(dead_code)]
     pub fn create_index_and_get_reader_bridge(index_directory: &str, skip_insert_cache: bool) -> Arc<IndexReaderBridge> {
         let writer_bridge = Self::create_index_and_get_writer_bridge(index_directory, false);
@@ -128,6 +202,7 @@ impl SinglePartTest {
                 path: writer_bridge.path.to_string(),
                 index: writer_bridge.index.clone(),
                 reader: reader.clone(),
+                dropped_columns: Vec::new(),
             }
         );
         if !skip_insert_cache {
@@ -175,6 +250,7 @@ impl SinglePartTest {
                 path: writer_bridge.path.to_string(),
                 index: writer_bridge.index.clone(),
                 reader: reader.clone(),
+                dropped_columns: Vec::new(),
             }
         )
     }
@@ -198,6 +274,7 @@ impl SinglePartTest {
                     path: writer_bridge.path.to_string(),
                     index: writer_bridge.index.clone(),
                     reader: reader.clone(),
+                    dropped_columns: Vec::new(),
                 }
             )
         )
@@ -407,7 +484,10 @@ impl MultiPartsTest {
         let bridge = Arc::new(IndexWriterBridge {
             path: index_directory.to_string(),
             index,
-            writer: Mutex::new(Some(writer)),
+            merge_policy: writer.get_merge_policy(),
+            writer: RwLock::new(Some(writer)),
+            pending_operations: AtomicBool::new(false),
+            dropped_columns: RwLock::new(Vec::new()),
         });
         FFI_INDEX_WRITER_CACHE.set_index_writer_bridge(index_directory.to_string(), bridge.clone()).expect("");
         return bridge;
@@ -1,7 +1,7 @@
 use crate::index::implements::*;
 use crate::{cxx_vector_converter, CXX_STRING_CONVERTER, CXX_VECTOR_STRING_CONVERTER};
 use cxx::{CxxString, CxxVector};
-use crate::ffi::{FFIBoolResult, FFIError};
+use crate::ffi::{FFIBoolResult, FFIError, FFIU64Result};
 use crate::utils::api_utils::ApiUtils;
 
 pub fn ffi_create_index_with_parameter(
@@ -48,6 +48,56 @@ pub fn ffi_create_index_with_parameter(
 
 }
 
+pub fn ffi_create_index_with_writer_parameter(
+    index_path: &CxxString,
+    column_names: &CxxVector<CxxString>,
+    index_json_parameter: &CxxString,
+    writer_json_parameter: &CxxString,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_create_index_with_writer_parameter";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let column_names: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_names) {
+        Ok(names) => names,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_names'", e.to_string());
+        }
+    };
+
+    let index_json_parameter: String = match CXX_STRING_CONVERTER.convert(index_json_parameter) {
+        Ok(json) => json,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_json_parameter'", e.to_string());
+        }
+    };
+
+    let writer_json_parameter: String = match CXX_STRING_CONVERTER.convert(writer_json_parameter) {
+        Ok(json) => json,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'writer_json_parameter'", e.to_string());
+        }
+    };
+
+    match create_index_with_writer_parameter(&index_path, &column_names, &index_json_parameter, &writer_json_parameter) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error creating index", e.to_string())
+        }
+    }
+}
+
 pub fn ffi_create_index(index_path: &CxxString, column_names: &CxxVector<CxxString>) -> FFIBoolResult {
     static FUNC_NAME: &str = "ffi_create_index";
 
@@ -131,6 +181,170 @@ pub fn ffi_index_multi_column_docs(
     }
 }
 
+pub fn ffi_index_multi_column_docs_batch(
+    index_path: &CxxString,
+    row_ids: &CxxVector<u64>,
+    column_names: &CxxVector<CxxString>,
+    column_docs: &CxxVector<CxxString>,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_index_multi_column_docs_batch";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let row_ids: Vec<u64> = match cxx_vector_converter::<u64>().convert(row_ids) {
+        Ok(ids) => ids,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'row_ids'", e.to_string());
+        }
+    };
+
+    let column_names: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_names) {
+        Ok(names) => names,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_names'", e.to_string());
+        }
+    };
+
+    let column_docs: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_docs) {
+        Ok(docs) => docs,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_docs'", e.to_string());
+        }
+    };
+
+    if column_names.len() == 0 || row_ids.len() == 0 {
+        return ApiUtils::handle_error(FUNC_NAME, "column_names and row_ids can't be empty", "".to_string());
+    }
+
+    if column_names.len() * row_ids.len() != column_docs.len() {
+        return ApiUtils::handle_error(FUNC_NAME, "column_docs size doesn't match column_names size * row_ids size", "".to_string());
+    }
+
+    match index_multi_column_docs_batch(&index_path, &row_ids, &column_names, column_docs) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error indexing multi-column docs batch", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_upsert_multi_column_docs(
+    index_path: &CxxString,
+    row_id: u64,
+    column_names: &CxxVector<CxxString>,
+    column_docs: &CxxVector<CxxString>,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_upsert_multi_column_docs";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let column_names: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_names) {
+        Ok(names) => names,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_names'", e.to_string());
+        }
+    };
+
+    let column_docs: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_docs) {
+        Ok(docs) => docs,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_docs'", e.to_string());
+        }
+    };
+
+    if column_names.len() != column_docs.len() {
+        return ApiUtils::handle_error(FUNC_NAME, "column_names size doesn't match column_docs size", "".to_string());
+    }
+
+    if column_names.len() == 0 {
+        return ApiUtils::handle_error(FUNC_NAME, "column_names and column_docs can't be empty", "".to_string());
+    }
+
+    match upsert_multi_column_docs(&index_path, row_id, &column_names, &column_docs) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error upserting multi-column docs", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_index_multi_valued_docs(
+    index_path: &CxxString,
+    row_id: u64,
+    column_names: &CxxVector<CxxString>,
+    column_docs: &CxxVector<CxxString>,
+    value_counts: &CxxVector<u32>,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_index_multi_valued_docs";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let column_names: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_names) {
+        Ok(names) => names,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_names'", e.to_string());
+        }
+    };
+
+    let column_docs: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_docs) {
+        Ok(docs) => docs,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_docs'", e.to_string());
+        }
+    };
+
+    let value_counts: Vec<u32> = match cxx_vector_converter::<u32>().convert(value_counts) {
+        Ok(counts) => counts,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'value_counts'", e.to_string());
+        }
+    };
+
+    if column_names.len() != value_counts.len() {
+        return ApiUtils::handle_error(FUNC_NAME, "column_names size doesn't match value_counts size", "".to_string());
+    }
+
+    match index_multi_valued_docs(&index_path, row_id, &column_names, column_docs, &value_counts) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error indexing multi-valued docs", e.to_string())
+        }
+    }
+}
+
 pub fn ffi_delete_row_ids(index_path: &CxxString, row_ids: &CxxVector<u32>) -> FFIBoolResult {
     static FUNC_NAME: &str = "ffi_delete_row_ids";
 
@@ -162,6 +376,149 @@ pub fn ffi_delete_row_ids(index_path: &CxxString, row_ids: &CxxVector<u32>) -> F
     }
 }
 
+pub fn ffi_delete_row_ids_u64(index_path: &CxxString, row_ids: &CxxVector<u64>) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_delete_row_ids_u64";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let row_ids: Vec<u64> = match cxx_vector_converter::<u64>().convert(row_ids) {
+        Ok(ids) => ids,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'row_ids'", e.to_string());
+        }
+    };
+
+    match delete_row_ids_u64(&index_path, &row_ids) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error deleting u64 row ids", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_delete_row_ids_by_u8_bitmap(index_path: &CxxString, u8_bitmap: &CxxVector<u8>) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_delete_row_ids_by_u8_bitmap";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let u8_bitmap: Vec<u8> = match cxx_vector_converter::<u8>().convert(u8_bitmap) {
+        Ok(bitmap) => bitmap,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'u8_bitmap'", e.to_string());
+        }
+    };
+
+    match delete_row_ids_by_u8_bitmap(&index_path, &u8_bitmap) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error deleting row ids by u8 bitmap", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_delete_row_ids_by_treemap(index_path: &CxxString, treemap_bytes: &CxxVector<u8>) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_delete_row_ids_by_treemap";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let treemap_bytes: Vec<u8> = match cxx_vector_converter::<u8>().convert(treemap_bytes) {
+        Ok(bitmap) => bitmap,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'treemap_bytes'", e.to_string());
+        }
+    };
+
+    match delete_row_ids_by_treemap(&index_path, &treemap_bytes) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error deleting row ids by treemap", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_delete_by_query(
+    index_path: &CxxString,
+    column_name: &CxxString,
+    query: &CxxString,
+    query_type: &CxxString,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_delete_by_query";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let column_name: String = match CXX_STRING_CONVERTER.convert(column_name) {
+        Ok(name) => name,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_name'", e.to_string());
+        }
+    };
+
+    let query: String = match CXX_STRING_CONVERTER.convert(query) {
+        Ok(q) => q,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'query'", e.to_string());
+        }
+    };
+
+    let query_type: String = match CXX_STRING_CONVERTER.convert(query_type) {
+        Ok(t) => t,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'query_type'", e.to_string());
+        }
+    };
+
+    match delete_by_query(&index_path, &column_name, &query, &query_type) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error deleting by query", e.to_string())
+        }
+    }
+}
+
 pub fn ffi_index_writer_commit(index_path: &CxxString) -> FFIBoolResult {
     static FUNC_NAME: &str = "ffi_index_writer_commit";
 
@@ -186,6 +543,60 @@ pub fn ffi_index_writer_commit(index_path: &CxxString) -> FFIBoolResult {
     }
 }
 
+pub fn ffi_index_writer_commit_with_payload(index_path: &CxxString, payload: &CxxString) -> FFIU64Result {
+    static FUNC_NAME: &str = "ffi_index_writer_commit_with_payload";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+    let payload: String = match CXX_STRING_CONVERTER.convert(payload) {
+        Ok(payload) => payload,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'payload'", e.to_string());
+        }
+    };
+
+    match commit_index_with_payload(&index_path, &payload) {
+        Ok(result) => FFIU64Result {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error commit index with payload", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_index_writer_rollback(index_path: &CxxString) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_index_writer_rollback";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    match rollback_index(&index_path) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error rollback index", e.to_string())
+        }
+    }
+}
+
 pub fn ffi_free_index_writer(index_path: &CxxString) -> FFIBoolResult {
     static FUNC_NAME: &str = "ffi_free_index_writer";
 
@@ -209,3 +620,229 @@ pub fn ffi_free_index_writer(index_path: &CxxString) -> FFIBoolResult {
         }
     }
 }
+
+pub fn ffi_open_index_writer(index_path: &CxxString) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_open_index_writer";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    match open_index_writer(&index_path) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error opening index writer", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_merge_index(index_path: &CxxString, num_segments: u32, purge_deleted: bool) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_merge_index";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    match merge_index(&index_path, num_segments as usize, purge_deleted) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error merging index", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_merge_parts(
+    index_path: &CxxString,
+    source_index_paths: &CxxVector<CxxString>,
+    row_id_mappings: &CxxVector<u64>,
+    mapping_sizes: &CxxVector<u64>,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_merge_parts";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let source_index_paths: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(source_index_paths) {
+        Ok(paths) => paths,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'source_index_paths'", e.to_string());
+        }
+    };
+
+    let row_id_mappings: Vec<u64> = match cxx_vector_converter::<u64>().convert(row_id_mappings) {
+        Ok(mappings) => mappings,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'row_id_mappings'", e.to_string());
+        }
+    };
+
+    let mapping_sizes: Vec<u64> = match cxx_vector_converter::<u64>().convert(mapping_sizes) {
+        Ok(sizes) => sizes,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'mapping_sizes'", e.to_string());
+        }
+    };
+
+    match merge_parts(&index_path, &source_index_paths, &row_id_mappings, &mapping_sizes) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error merging parts", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_remap_row_ids(index_path: &CxxString, row_id_mapping: &CxxVector<u64>) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_remap_row_ids";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let row_id_mapping: Vec<u64> = match cxx_vector_converter::<u64>().convert(row_id_mapping) {
+        Ok(mapping) => mapping,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'row_id_mapping'", e.to_string());
+        }
+    };
+
+    match remap_row_ids(&index_path, &row_id_mapping) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error remapping row ids", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_offset_row_ids(index_path: &CxxString, offset: i64) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_offset_row_ids";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    match offset_row_ids(&index_path, offset) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error offsetting row ids", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_add_column(
+    index_path: &CxxString,
+    column_name: &CxxString,
+    column_json_parameter: &CxxString,
+) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_add_column";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let column_name: String = match CXX_STRING_CONVERTER.convert(column_name) {
+        Ok(name) => name,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_name'", e.to_string());
+        }
+    };
+
+    let column_json_parameter: String = match CXX_STRING_CONVERTER.convert(column_json_parameter) {
+        Ok(json) => json,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_json_parameter'", e.to_string());
+        }
+    };
+
+    match add_column(&index_path, &column_name, &column_json_parameter) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error adding column", e.to_string())
+        }
+    }
+}
+
+pub fn ffi_drop_column(index_path: &CxxString, column_name: &CxxString) -> FFIBoolResult {
+    static FUNC_NAME: &str = "ffi_drop_column";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let column_name: String = match CXX_STRING_CONVERTER.convert(column_name) {
+        Ok(name) => name,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_name'", e.to_string());
+        }
+    };
+
+    match drop_column(&index_path, &column_name) {
+        Ok(result) => FFIBoolResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error dropping column", e.to_string())
+        }
+    }
+}
@@ -1,6 +1,9 @@
 mod api_index;
 
 pub use api_index::{
-    ffi_create_index_with_parameter, ffi_create_index, ffi_index_multi_column_docs,
-    ffi_delete_row_ids, ffi_index_writer_commit, ffi_free_index_writer
+    ffi_create_index_with_parameter, ffi_create_index_with_writer_parameter, ffi_create_index, ffi_index_multi_column_docs,
+    ffi_index_multi_column_docs_batch, ffi_index_multi_valued_docs, ffi_upsert_multi_column_docs,
+    ffi_delete_row_ids, ffi_delete_row_ids_u64, ffi_delete_row_ids_by_u8_bitmap, ffi_delete_row_ids_by_treemap, ffi_delete_by_query, ffi_index_writer_commit, ffi_index_writer_commit_with_payload, ffi_index_writer_rollback, ffi_free_index_writer,
+    ffi_open_index_writer, ffi_merge_index, ffi_merge_parts,
+    ffi_remap_row_ids, ffi_offset_row_ids, ffi_add_column, ffi_drop_column
 };
\ No newline at end of file
@@ -1,22 +1,51 @@
-use std::sync::Mutex;
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
-use tantivy::{Document, Index, IndexWriter, Opstamp, Term};
+use tantivy::query::{AllQuery, Query};
+use tantivy::indexer::UserOperation;
+use tantivy::merge_policy::{MergeCandidate, MergePolicy, NoMergePolicy};
+use tantivy::{Document, Index, IndexWriter, Opstamp, SegmentId, SegmentMeta, Term};
 
-use crate::{common::constants::LOG_CALLBACK, INFO};
+use crate::{common::constants::LOG_CALLBACK, INFO, WARNING};
 use crate::logger::logger_bridge::TantivySearchLogger;
 
+/// `IndexWriter::add_document` only needs `&self`, so adding documents takes
+/// the read lock and can run concurrently from many threads.
+/// Commit, delete and free take the write lock and are serialized.
 pub struct IndexWriterBridge {
     pub path: String,
     pub index: Index,
-    pub writer: Mutex<Option<IndexWriter>>,
+    pub writer: RwLock<Option<IndexWriter>>,
+    /// Whether documents have been added or deleted since the last commit or rollback.
+    pub pending_operations: AtomicBool,
+    /// Columns dropped by `drop_column`, they stay in schema until documents are rebuilt.
+    pub dropped_columns: RwLock<Vec<String>>,
+    /// Merge policy configured for the writer, it is set back after being replaced by a merge.
+    pub merge_policy: Arc<dyn MergePolicy>,
 }
 
 impl IndexWriterBridge {
+    // Whether the inner `IndexWriter` is still alive.
+    pub fn is_available(&self) -> bool {
+        match self.writer.read() {
+            Ok(writer) => writer.is_some(),
+            Err(_) => false,
+        }
+    }
+
+    pub fn dropped_columns(&self) -> Vec<String> {
+        self.dropped_columns.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
     pub fn commit(&self) -> Result<Opstamp, String> {
-        match self.writer.lock() {
+        match self.writer.write() {
             Ok(mut writer) => {
                 if let Some(writer) = writer.as_mut() {
-                    writer.commit().map_err(|e| e.to_string())
+                    let opstamp = writer.commit().map_err(|e| e.to_string())?;
+                    self.pending_operations.store(false, Ordering::SeqCst);
+                    Ok(opstamp)
                 } else {
                     Err("IndexWriterBridge is not available for commit".to_string())
                 }
@@ -25,10 +54,50 @@ impl IndexWriterBridge {
         }
     }
 
-    pub fn add_document(&self, document: Document) -> Result<Opstamp, String> {
-        match self.writer.lock() {
+    // Commit with a payload stored in the index meta, `None` keeps payload empty.
+    pub fn commit_with_payload(&self, payload: Option<&str>) -> Result<Opstamp, String> {
+        match self.writer.write() {
             Ok(mut writer) => {
                 if let Some(writer) = writer.as_mut() {
+                    let mut prepared_commit = writer.prepare_commit().map_err(|e| e.to_string())?;
+                    if let Some(payload) = payload {
+                        prepared_commit.set_payload(payload);
+                    }
+                    let opstamp = prepared_commit.commit().map_err(|e| e.to_string())?;
+                    self.pending_operations.store(false, Ordering::SeqCst);
+                    Ok(opstamp)
+                } else {
+                    Err("IndexWriterBridge is not available for commit_with_payload".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
+    // Discard all operations since the last commit, writer is still usable after rollback.
+    // Tantivy creates a new writer with the default merge policy, the configured one is set back.
+    pub fn rollback(&self) -> Result<Opstamp, String> {
+        match self.writer.write() {
+            Ok(mut writer) => {
+                if let Some(writer) = writer.as_mut() {
+                    let result = writer.rollback().map_err(|e| e.to_string());
+                    self.restore_merge_policy(writer);
+                    let opstamp = result?;
+                    self.pending_operations.store(false, Ordering::SeqCst);
+                    Ok(opstamp)
+                } else {
+                    Err("IndexWriterBridge is not available for rollback".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
+    pub fn add_document(&self, document: Document) -> Result<Opstamp, String> {
+        match self.writer.read() {
+            Ok(writer) => {
+                if let Some(writer) = writer.as_ref() {
+                    self.pending_operations.store(true, Ordering::SeqCst);
                     writer.add_document(document).map_err(|e| e.to_string())
                 } else {
                     Err("IndexWriterBridge is not available for add_document".to_string())
@@ -38,11 +107,31 @@ impl IndexWriterBridge {
         }
     }
 
+    // Add a group of documents while holding the writer lock only once.
+    pub fn add_documents(&self, documents: Vec<Document>) -> Result<Opstamp, String> {
+        match self.writer.read() {
+            Ok(writer) => {
+                if let Some(writer) = writer.as_ref() {
+                    self.pending_operations.store(true, Ordering::SeqCst);
+                    let mut opstamp: Opstamp = 0;
+                    for document in documents {
+                        opstamp = writer.add_document(document).map_err(|e| e.to_string())?;
+                    }
+                    Ok(opstamp)
+                } else {
+                    Err("IndexWriterBridge is not available for add_documents".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn delete_term(&self, term: Term) -> Result<Opstamp, String> {
-        match self.writer.lock() {
+        match self.writer.write() {
             Ok(mut writer) => {
                 if let Some(writer) = writer.as_mut() {
+                    self.pending_operations.store(true, Ordering::SeqCst);
                     Ok(writer.delete_term(term))
                 } else {
                     Err("IndexWriterBridge is not available for delete_term".to_string())
@@ -52,11 +141,30 @@ impl IndexWriterBridge {
         }
     }
 
+    // Delete documents with the term and add the new document in one operation batch,
+    // they get consecutive opstamps and no commit can happen between them.
+    pub fn upsert_document(&self, term: Term, document: Document) -> Result<Opstamp, String> {
+        match self.writer.write() {
+            Ok(mut writer) => {
+                if let Some(writer) = writer.as_mut() {
+                    self.pending_operations.store(true, Ordering::SeqCst);
+                    writer
+                        .run(vec![UserOperation::Delete(term), UserOperation::Add(document)])
+                        .map_err(|e| e.to_string())
+                } else {
+                    Err("IndexWriterBridge is not available for upsert_document".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
     // Delete a group of terms.
     pub fn delete_terms(&self, terms: Vec<Term>) -> Result<Opstamp, String> {
-        match self.writer.lock() {
+        match self.writer.write() {
             Ok(mut writer) => {
                 if let Some(writer) = writer.as_mut() {
+                    self.pending_operations.store(true, Ordering::SeqCst);
                     let mut opstamp: Opstamp = 0;
                     for term in terms {
                         opstamp = writer.delete_term(term)
@@ -70,8 +178,139 @@ impl IndexWriterBridge {
         }
     }
 
+    // Delete all documents matching the query.
+    pub fn delete_query(&self, query: Box<dyn Query>) -> Result<Opstamp, String> {
+        match self.writer.write() {
+            Ok(mut writer) => {
+                if let Some(writer) = writer.as_mut() {
+                    self.pending_operations.store(true, Ordering::SeqCst);
+                    writer.delete_query(query).map_err(|e| e.to_string())
+                } else {
+                    Err("IndexWriterBridge is not available for delete_query".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
+    // Merge the segment groups planned by `plan`, then remove the files no longer used.
+    // Background merges are paused meanwhile. A merge started before the pause may still
+    // merge the planned segments, then our merge fails and groups are planned again on
+    // the new segments. Returns the number of merged groups.
+    pub fn merge_segments<F>(&self, plan: F) -> Result<usize, String>
+    where
+        F: Fn(&[SegmentMeta]) -> Vec<Vec<SegmentId>>,
+    {
+        match self.writer.write() {
+            Ok(mut writer) => {
+                if let Some(writer) = writer.as_mut() {
+                    writer.set_merge_policy(Box::new(NoMergePolicy));
+                    let result = self.merge_planned_segments(writer, plan);
+                    self.restore_merge_policy(writer);
+                    let merged_groups = result?;
+                    writer.garbage_collect_files().wait().map_err(|e| e.to_string())?;
+                    Ok(merged_groups)
+                } else {
+                    Err("IndexWriterBridge is not available for merge_segments".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
+    // Set the configured merge policy, wrapped once so that wrappers never nest.
+    fn restore_merge_policy(&self, writer: &IndexWriter) {
+        writer.set_merge_policy(Box::new(SharedMergePolicy(self.merge_policy.clone())));
+    }
+
+    fn merge_planned_segments<F>(&self, writer: &mut IndexWriter, plan: F) -> Result<usize, String>
+    where
+        F: Fn(&[SegmentMeta]) -> Vec<Vec<SegmentId>>,
+    {
+        let segment_ids = |segment_metas: &[SegmentMeta]| -> HashSet<SegmentId> {
+            segment_metas.iter().map(|meta| meta.id()).collect()
+        };
+        let mut merged_groups: usize = 0;
+        loop {
+            let segment_metas = writer.index().searchable_segment_metas().map_err(|e| e.to_string())?;
+            let segment_groups = plan(&segment_metas);
+            if segment_groups.is_empty() {
+                return Ok(merged_groups);
+            }
+            let merge_futures: Vec<_> = segment_groups
+                .iter()
+                .map(|segment_ids| writer.merge(segment_ids))
+                .collect();
+            // Wait for all merges, even if one of them fails.
+            let mut first_error: Option<String> = None;
+            for merge_future in merge_futures {
+                match merge_future.wait() {
+                    Ok(_) => merged_groups += 1,
+                    Err(e) => {
+                        first_error.get_or_insert(e.to_string());
+                    }
+                }
+            }
+            let Some(error) = first_error else {
+                return Ok(merged_groups);
+            };
+            // Plan again only if segments were changed by another merge, otherwise give up.
+            let current_metas = writer.index().searchable_segment_metas().map_err(|e| e.to_string())?;
+            if segment_ids(&current_metas) == segment_ids(&segment_metas) {
+                return Err(error);
+            }
+            WARNING!("Segments changed by another merge, plan again. index_path:[{}], error: {}", self.path, error);
+        }
+    }
+
+    // Replace all documents by the rebuilt ones within one commit, `rebuild` reads the last commit.
+    // Refuse to run while there are uncommitted operations, they would be committed with the
+    // rebuilt docs or dropped. Changes are rolled back if `rebuild` fails.
+    pub fn rewrite_documents<F>(&self, rebuild: F) -> Result<Opstamp, String>
+    where
+        F: FnOnce(&IndexWriter) -> Result<(), String>,
+    {
+        match self.writer.write() {
+            Ok(mut writer) => {
+                if let Some(writer) = writer.as_mut() {
+                    if self.pending_operations.load(Ordering::SeqCst) {
+                        return Err("There are uncommitted operations, commit or rollback them first".to_string());
+                    }
+                    writer.delete_query(Box::new(AllQuery)).map_err(|e| e.to_string())?;
+                    if let Err(e) = rebuild(writer) {
+                        let _ = writer.rollback();
+                        self.restore_merge_policy(writer);
+                        return Err(e);
+                    }
+                    writer.commit().map_err(|e| e.to_string())
+                } else {
+                    Err("IndexWriterBridge is not available for rewrite_documents".to_string())
+                }
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
+    // Lock the writer while index files are rebuilt and replaced elsewhere, no document can be
+    // added or deleted until the guard is dropped. Refuse to lock while there are uncommitted
+    // operations, they would be lost once the writer is closed to replace the files.
+    pub fn lock_for_replace(&self) -> Result<RwLockWriteGuard<'_, Option<IndexWriter>>, String> {
+        match self.writer.write() {
+            Ok(writer) => {
+                if writer.is_none() {
+                    return Err("IndexWriterBridge is not available for lock_for_replace".to_string());
+                }
+                if self.pending_operations.load(Ordering::SeqCst) {
+                    return Err("There are uncommitted operations, commit or rollback them first".to_string());
+                }
+                Ok(writer)
+            }
+            Err(e) => Err(format!("Lock error: {}", e)),
+        }
+    }
+
     pub fn wait_merging_threads(&self) -> Result<(), String> {
-        match self.writer.lock() {
+        match self.writer.write() {
             Ok(mut writer) => {
                 if let Some(writer) = writer.take() {
                     let _ = writer.wait_merging_threads();
@@ -83,6 +322,22 @@ impl IndexWriterBridge {
     }
 }
 
+// `IndexWriter::set_merge_policy` takes a `Box`, wrap the configured `Arc` to set it back.
+// Debug output is the one of the configured policy.
+struct SharedMergePolicy(Arc<dyn MergePolicy>);
+
+impl fmt::Debug for SharedMergePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl MergePolicy for SharedMergePolicy {
+    fn compute_merge_candidates(&self, segments: &[SegmentMeta]) -> Vec<MergeCandidate> {
+        self.0.compute_merge_candidates(segments)
+    }
+}
+
 impl Drop for IndexWriterBridge {
     fn drop(&mut self) {
         INFO!("IndexW has been dropped. index_path:[{}]", self.path);
@@ -91,11 +346,12 @@ impl Drop for IndexWriterBridge {
 
 #[cfg(test)]
 mod tests {
-    use tantivy::{query::QueryParser, Term};
+    use std::thread;
+    use tantivy::{query::QueryParser, Document, Term};
     use tantivy::schema::Field;
     use tempfile::TempDir;
 
-    use crate::common::SinglePartTest;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
     use crate::search::collector::row_id_bitmap_collector::RowIdRoaringCollector;
 
     #[test]
@@ -124,6 +380,32 @@ mod tests {
         assert!(bridge.commit().is_ok());
     }
 
+    #[test]
+    pub fn normal_test_bridge_rollback() {
+        let tmp_dir = TempDir::new().expect("");
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, false, false);
+        let query_parser = QueryParser::for_index(&bridge.index, vec![Field::from_field_id(1)]);
+        let text_query = query_parser.parse_query("Ancient").expect("");
+        let collector = RowIdRoaringCollector::with_field("row_id".to_string());
+
+        // Uncommitted docs are discarded.
+        assert!(bridge.rollback().is_ok());
+        assert!(bridge.commit().is_ok());
+        let searcher_1 = bridge.index.reader().expect("").searcher();
+        assert_eq!(searcher_1.search(&text_query, &collector).expect("").len(), 0);
+
+        // Writer is still usable after rollback.
+        let mut doc = Document::default();
+        doc.add_u64(Field::from_field_id(0), 7);
+        doc.add_text(Field::from_field_id(1), "Ancient empires");
+        assert!(bridge.add_document(doc).is_ok());
+        assert!(bridge.commit().is_ok());
+        let searcher_2 = bridge.index.reader().expect("").searcher();
+        assert_eq!(searcher_2.search(&text_query, &collector).expect("").len(), 1);
+    }
+
     #[test]
     pub fn normal_test_bridge_wait_merge() {
         let tmp_dir = TempDir::new().expect("");
@@ -132,14 +414,16 @@ mod tests {
         let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
         // Now all merging threads are living.
         {
-            let lock = bridge.writer.lock().unwrap();
+            let lock = bridge.writer.read().unwrap();
             let index_writer_some = &*lock;
             assert!(index_writer_some.is_some());
         }
+        assert!(bridge.is_available());
         assert!(bridge.wait_merging_threads().is_ok());
+        assert!(!bridge.is_available());
         // After wait all merging threads were killed.
         {
-            let lock = bridge.writer.lock().unwrap();
+            let lock = bridge.writer.read().unwrap();
             let index_writer_some = &*lock;
             assert!(index_writer_some.is_none());
         }
@@ -185,4 +469,37 @@ mod tests {
         let res_3 = searcher_3.search(&text_query, &collector).expect("");
         assert_eq!(res_3.len(), 0);
     }
+
+    #[test]
+    pub fn test_concurrent_add_documents() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().expect("");
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::create_index_and_get_writer_bridge(tmp_dir, true);
+        let handles: Vec<_> = (0..4u64)
+            .map(|thread_id| {
+                let bridge = bridge.clone();
+                thread::spawn(move || {
+                    for i in 0..100u64 {
+                        let mut doc = Document::default();
+                        doc.add_u64(Field::from_field_id(0), thread_id * 100 + i);
+                        doc.add_text(Field::from_field_id(1), "concurrent ingestion");
+                        assert!(bridge.add_document(doc).is_ok());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert!(bridge.commit().is_ok());
+
+        let query_parser = QueryParser::for_index(&bridge.index, vec![Field::from_field_id(1)]);
+        let text_query = query_parser.parse_query("concurrent").expect("");
+        let collector = RowIdRoaringCollector::with_field("row_id".to_string());
+        let searcher = bridge.index.reader().expect("").searcher();
+        let res = searcher.search(&text_query, &collector).expect("");
+        assert_eq!(res.len(), 400);
+    }
 }
@@ -0,0 +1,469 @@
+use std::fs;
+use std::path::Path;
+use tantivy::schema::{Field, Schema, SchemaBuilder};
+use tantivy::{Document, IndexReader, ReloadPolicy};
+use crate::common::constants::FFI_INDEX_SEARCHER_CACHE;
+use crate::common::errors::{IndexUtilsError, TantivySearchError};
+use crate::{common::constants::LOG_CALLBACK, ERROR, INFO, WARNING};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::bridge::IndexWriterBridge;
+use crate::index::implements::api_open_index_writer::open_index_writer;
+use crate::index::implements::index_manager::IndexManager;
+use crate::index::implements::index_rebuilder::IndexRebuilder;
+use crate::search::implements::{free_index_reader, load_index_reader};
+use crate::utils::index_utils::IndexUtils;
+
+/// New index is built in this sibling directory, then replaces the original one.
+const ALTER_COLUMN_DIRECTORY_SUFFIX: &str = ".alter_column";
+/// Original index is moved to this sibling directory until the new one is in place.
+const BACKUP_DIRECTORY_SUFFIX: &str = ".alter_column_backup";
+
+/// Add a new column to an existing index, existing docs have no value for it.
+/// `column_json_parameter` configs the new column like one entry of index json parameter,
+/// such as `{"tokenizer": {"type": "default"}}`, empty means default `TEXT` column.
+/// Tantivy schema is fixed once an index is created, so all docs are rebuilt into a new index,
+/// dropped columns are removed from its schema and a dropped column name can be added again.
+pub fn add_column(index_path: &str, column_name: &str, column_json_parameter: &str) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "add_column";
+
+    let (schema, mut parameter) = load_schema_and_parameter(FUNC_NAME, index_path)?;
+    let dropped_columns = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?.dropped_columns();
+    let is_dropped = |column_name: &str| dropped_columns.iter().any(|dropped| dropped == column_name);
+    if column_name.starts_with("__") || (schema.get_field(column_name).is_ok() && !is_dropped(column_name)) {
+        let error_info = format!("Column {} already exists or is reserved", column_name);
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+    parameter.retain(|name, _| !is_dropped(name.as_str()));
+    if !column_json_parameter.trim().is_empty() {
+        let column: serde_json::Value = serde_json::from_str(column_json_parameter).map_err(|e| {
+            let error_info = format!("Invalid column json parameter: {}", e);
+            ERROR!(function: FUNC_NAME, "{}", error_info);
+            TantivySearchError::InvalidArgument(error_info)
+        })?;
+        parameter.insert(column_name.to_string(), column);
+    }
+    let index_json_parameter = serde_json::Value::Object(parameter).to_string();
+
+    // Existing fields keep their options, the new column is built like `create_index_with_parameter`.
+    let (column_schema, _) =
+        IndexManager::build_schema(FUNC_NAME, &vec![column_name.to_string()], &index_json_parameter)?;
+    let mut schema_builder = schema_builder_without(&schema, &dropped_columns);
+    for (_, field_entry) in column_schema.fields().filter(|(_, field_entry)| field_entry.name() == column_name) {
+        schema_builder.add_field(field_entry.clone());
+    }
+
+    rebuild_with_schema(FUNC_NAME, index_path, schema_builder.build(), &index_json_parameter, &dropped_columns)
+}
+
+/// Rebuild all docs without the dropped columns, so they are removed from schema and their
+/// space is reclaimed. Return false if no column has been dropped.
+pub(crate) fn purge_dropped_columns(index_path: &str) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "purge_dropped_columns";
+
+    let dropped_columns = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?.dropped_columns();
+    if dropped_columns.is_empty() {
+        return Ok(false);
+    }
+    let (schema, mut parameter) = load_schema_and_parameter(FUNC_NAME, index_path)?;
+    parameter.retain(|name, _| !dropped_columns.contains(name));
+    let index_json_parameter = serde_json::Value::Object(parameter).to_string();
+    let schema = schema_builder_without(&schema, &dropped_columns).build();
+
+    rebuild_with_schema(FUNC_NAME, index_path, schema, &index_json_parameter, &dropped_columns)
+}
+
+/// Drop a column from an existing index without rebuilding docs.
+/// The column can't be indexed or queried any more, but it stays in tantivy schema until
+/// docs are rebuilt by `merge_index`, `add_column`, `merge_parts` or row_id remapping,
+/// which reclaim its space. Segments merged in background by tantivy still keep it.
+pub fn drop_column(index_path: &str, column_name: &str) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "drop_column";
+
+    let index_path = index_path.trim_end_matches('/');
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+    // Hold both locks until the dropped column is persisted, so it can't race with other
+    // drops, or with `add_column` which replaces the index and its writer.
+    let lock_error = |e: String| {
+        let error_info = format!("Lock error: {}", e);
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        TantivySearchError::InternalError(error_info)
+    };
+    let writer = bridge.writer.read().map_err(|e| lock_error(e.to_string()))?;
+    if writer.is_none() {
+        let error_info = "IndexWriterBridge is not available for drop_column".to_string();
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InternalError(error_info));
+    }
+    let mut dropped_columns = bridge.dropped_columns.write().map_err(|e| lock_error(e.to_string()))?;
+    if column_name == "row_id"
+        || bridge.index.schema().get_field(column_name).is_err()
+        || dropped_columns.iter().any(|dropped| dropped == column_name)
+    {
+        let error_info = format!("Column {} not exists or can't be dropped", column_name);
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+
+    IndexUtils::update_index_parameter(Path::new(index_path), |parameter| {
+        parameter.column_names.retain(|name| name != column_name);
+        parameter.dropped_columns.push(column_name.to_string());
+    })
+    .map_err(|e| {
+        ERROR!(function: FUNC_NAME, "Failed to persist dropped column {}: {}", column_name, e);
+        e
+    })?;
+    dropped_columns.push(column_name.to_string());
+    drop(dropped_columns);
+    drop(writer);
+
+    // Index reader keeps the dropped columns it has been loaded with.
+    if FFI_INDEX_SEARCHER_CACHE.get_index_reader_bridge(index_path.to_string()).is_ok() {
+        load_index_reader(index_path)?;
+    }
+    Ok(true)
+}
+
+// Current schema and the persisted index json parameter.
+fn load_schema_and_parameter(
+    func_name: &str,
+    index_path: &str,
+) -> Result<(Schema, serde_json::Map<String, serde_json::Value>), TantivySearchError> {
+    let schema = IndexManager::get_index_writer_bridge(func_name, index_path)?.index.schema();
+    let parameter = IndexUtils::load_index_json_parameter(Path::new(index_path))?;
+    let parameter: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&parameter).map_err(|e| {
+        let error_info = format!("Invalid persisted index json parameter: {}", e);
+        ERROR!(function: func_name, "{}", error_info);
+        TantivySearchError::IndexUtilsError(IndexUtilsError::JsonDeserializeError(error_info))
+    })?;
+    Ok((schema, parameter))
+}
+
+// Schema builder with the fields of `schema` except `dropped_columns`, fields keep their options.
+fn schema_builder_without(schema: &Schema, dropped_columns: &[String]) -> SchemaBuilder {
+    let mut schema_builder = Schema::builder();
+    for (_, field_entry) in schema.fields() {
+        if !dropped_columns.iter().any(|dropped| dropped == field_entry.name()) {
+            schema_builder.add_field(field_entry.clone());
+        }
+    }
+    schema_builder
+}
+
+// Rebuild all docs into a new index with given schema, then replace the original index.
+// The writer stays locked during the rebuild and replacement, so no document can be added or
+// deleted meanwhile, uncommitted operations are refused. Index reader is reloaded if it has been loaded.
+// `dropped_columns` are the ones excluded from `schema`, their values are not rebuilt.
+fn rebuild_with_schema(
+    func_name: &str,
+    index_path: &str,
+    schema: Schema,
+    index_json_parameter: &str,
+    dropped_columns: &[String],
+) -> Result<bool, TantivySearchError> {
+    let index_path = index_path.trim_end_matches('/');
+    let new_index_path = format!("{}{}", index_path, ALTER_COLUMN_DIRECTORY_SUFFIX);
+    let backup_index_path = format!("{}{}", index_path, BACKUP_DIRECTORY_SUFFIX);
+
+    let bridge = IndexManager::get_index_writer_bridge(func_name, index_path)?;
+    let mut writer = bridge.lock_for_replace().map_err(|e| {
+        ERROR!(function: func_name, "{}", e);
+        TantivySearchError::InternalError(e)
+    })?;
+    // `schema` is stale if a column has been dropped before the writer is locked.
+    if bridge.dropped_columns() != dropped_columns {
+        let error_info = "Columns have been dropped meanwhile, try again".to_string();
+        ERROR!(function: func_name, "{}", error_info);
+        return Err(TantivySearchError::InternalError(error_info));
+    }
+
+    let num_docs = match build_index_with_schema(
+        func_name,
+        &bridge,
+        index_path,
+        &new_index_path,
+        schema,
+        index_json_parameter,
+        dropped_columns,
+    ) {
+        Ok(num_docs) => num_docs,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&new_index_path);
+            return Err(e);
+        }
+    };
+    INFO!(function: func_name, "{} docs rebuilt in {}", num_docs, new_index_path);
+
+    // Close the writer to release the index lock, the bridge stays locked and unavailable
+    // until a writer is opened again on the index in place.
+    if let Some(writer) = writer.take() {
+        let _ = writer.wait_merging_threads();
+    }
+    let reader_loaded = free_index_reader(index_path);
+    let replaced = replace_directory(func_name, index_path, &new_index_path, &backup_index_path);
+    drop(writer);
+
+    let result = replaced.and_then(|_| match open_index_writer(index_path) {
+        Ok(_) => {
+            if let Err(e) = fs::remove_dir_all(&backup_index_path) {
+                WARNING!(function: func_name, "Failed to remove backup index {}: {}", backup_index_path, e);
+            }
+            Ok(true)
+        }
+        Err(e) => {
+            restore_directory(func_name, index_path, &new_index_path, &backup_index_path);
+            Err(e)
+        }
+    });
+    // Writer and reader are opened again on every path, on the original index if the new one is not in place.
+    let reopened = if result.is_err() { open_index_writer(index_path) } else { Ok(true) };
+    let reloaded = if reader_loaded { load_index_reader(index_path) } else { Ok(true) };
+    result.and(reopened).and(reloaded)
+}
+
+// Move the original index to a backup directory, then move the new index in place.
+// The original index is restored if the new one can't be moved in place,
+// the backup is kept until the new index is opened.
+fn replace_directory(
+    func_name: &str,
+    index_path: &str,
+    new_index_path: &str,
+    backup_index_path: &str,
+) -> Result<(), TantivySearchError> {
+    let replace_error = |e: std::io::Error| {
+        let error_info = format!("Failed to replace {} with {}: {}", index_path, new_index_path, e);
+        ERROR!(function: func_name, "{}", error_info);
+        TantivySearchError::IndexUtilsError(IndexUtilsError::DirectoryIOError(error_info))
+    };
+    if Path::new(backup_index_path).exists() {
+        fs::remove_dir_all(backup_index_path).map_err(replace_error)?;
+    }
+    fs::rename(index_path, backup_index_path).map_err(replace_error)?;
+    if let Err(e) = fs::rename(new_index_path, index_path) {
+        if let Err(restore_error) = fs::rename(backup_index_path, index_path) {
+            ERROR!(function: func_name, "Failed to restore {} from {}: {}", index_path, backup_index_path, restore_error);
+        }
+        let _ = fs::remove_dir_all(new_index_path);
+        return Err(replace_error(e));
+    }
+    Ok(())
+}
+
+// Move the original index back in place when the new one can't be opened, the new index is removed.
+fn restore_directory(func_name: &str, index_path: &str, new_index_path: &str, backup_index_path: &str) {
+    let restored = fs::rename(index_path, new_index_path).and_then(|_| fs::rename(backup_index_path, index_path));
+    match restored {
+        Ok(_) => {
+            let _ = fs::remove_dir_all(new_index_path);
+        }
+        Err(e) => ERROR!(function: func_name, "Failed to restore {} from {}: {}", index_path, backup_index_path, e),
+    }
+}
+
+// Build the new index in `new_index_path` from the last commit of `bridge`, return the number of rebuilt docs.
+fn build_index_with_schema(
+    func_name: &str,
+    bridge: &IndexWriterBridge,
+    index_path: &str,
+    new_index_path: &str,
+    schema: Schema,
+    index_json_parameter: &str,
+    dropped_columns: &[String],
+) -> Result<u64, TantivySearchError> {
+    // Keep writer settings of the original index, including writer json parameter overrides.
+    let writer_settings = IndexUtils::load_writer_settings(Path::new(index_path))?;
+    let column_names = IndexManager::get_column_names(&schema);
+
+    IndexManager::persist_index_params(new_index_path, index_json_parameter, &column_names, &writer_settings)?;
+    let new_index = IndexManager::create_index_in_dir(func_name, new_index_path, schema.clone(), index_json_parameter)?;
+    IndexUtils::register_tokenizers_from_disk(Path::new(new_index_path), &new_index)?;
+    let new_bridge = IndexManager::create_writer(func_name, &new_index, new_index_path, &writer_settings, Vec::new())?;
+
+    let reader: IndexReader = bridge
+        .index
+        .reader_builder()
+        .reload_policy(ReloadPolicy::Manual)
+        .try_into()
+        .map_err(|e: tantivy::TantivyError| {
+            ERROR!(function: func_name, "Failed to open index reader: {}", e.to_string());
+            TantivySearchError::TantivyError(e)
+        })?;
+    let searcher = reader.searcher();
+
+    // Field of old schema to the field with same name in new schema, `None` if it's removed.
+    let field_mapping: Vec<Option<Field>> = searcher
+        .schema()
+        .fields()
+        .map(|(_, field_entry)| schema.get_field(field_entry.name()).ok())
+        .collect();
+    // Dropped columns are skipped, a dropped column name added again gets no old value.
+    let num_docs = IndexRebuilder::rebuild_documents(
+        func_name,
+        &searcher,
+        dropped_columns,
+        |row_id| Some(row_id),
+        |docs| {
+            let docs: Vec<Document> = docs
+                .iter()
+                .map(|doc| remap_fields(doc, &field_mapping))
+                .collect();
+            new_bridge.add_documents(docs).map(|_| ())
+        },
+    )?;
+
+    new_bridge.commit().map_err(|e| {
+        let error_info = format!("Failed to commit rebuilt index: {}", e);
+        ERROR!(function: func_name, "{}", error_info);
+        TantivySearchError::InternalError(error_info)
+    })?;
+    new_bridge.wait_merging_threads().map_err(|e| {
+        ERROR!(function: func_name, "{}", e);
+        TantivySearchError::InternalError(e)
+    })?;
+    Ok(num_docs)
+}
+
+// Move field values of a rebuilt doc to fields of the new schema.
+fn remap_fields(doc: &Document, field_mapping: &[Option<Field>]) -> Document {
+    let mut new_doc = Document::default();
+    for field_value in doc.field_values() {
+        if let Some(Some(new_field)) = field_mapping.get(field_value.field().field_id() as usize) {
+            new_doc.add_field_value(*new_field, field_value.value().clone());
+        }
+    }
+    new_doc
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use tempfile::TempDir;
+    use crate::common::constants::FFI_INDEX_WRITER_CACHE;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::index::implements::api_alter_column::{ALTER_COLUMN_DIRECTORY_SUFFIX, BACKUP_DIRECTORY_SUFFIX};
+    use crate::index::implements::{add_column, commit_index, drop_column, index_multi_column_docs};
+    use crate::search::implements::{load_index_reader, query_term_bitmap, query_term_bitmap_u64};
+    use crate::search::utils::ConvertUtils;
+    use crate::utils::index_utils::IndexUtils;
+
+    #[test]
+    pub fn normal_test_add_column() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        assert!(add_column(tmp_dir, "col4", r#"{ "tokenizer": { "type": "raw" } }"#).is_ok());
+        // Existing docs are kept and have no value for new column.
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+        assert!(query_term_bitmap(tmp_dir, "col4", "Ancient").unwrap().is_empty());
+
+        let column_names = vec!["col1".to_string(), "col4".to_string()];
+        let column_docs = vec!["Ancient".to_string(), "New Column".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 100, &column_names, &column_docs).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        let res = query_term_bitmap_u64(tmp_dir, "col4", "New Column").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![100]);
+
+        // Column already exists, reserved or invalid parameter.
+        assert!(add_column(tmp_dir, "col1", "").is_err());
+        assert!(add_column(tmp_dir, "row_id", "").is_err());
+        assert!(add_column(tmp_dir, "__writer__", "").is_err());
+        assert!(add_column(tmp_dir, "col5", r#"{ "tokenizer": { "type": "unknown" } }"#).is_err());
+        assert!(add_column(tmp_dir, "col5", "").is_ok());
+    }
+
+    #[test]
+    pub fn boundary_test_add_column_with_uncommitted_docs() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+        let column_names = vec!["col1".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 100, &column_names, &vec!["Ancient".to_string()]).is_ok());
+
+        // Uncommitted doc would be lost with the replaced writer.
+        assert!(add_column(tmp_dir, "col4", "").is_err());
+        assert!(commit_index(tmp_dir).is_ok());
+
+        assert!(add_column(tmp_dir, "col4", "").is_ok());
+        let res = query_term_bitmap_u64(tmp_dir, "col1", "Ancient").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![0, 4, 100]);
+        assert!(!Path::new(&format!("{}{}", tmp_dir, ALTER_COLUMN_DIRECTORY_SUFFIX)).exists());
+        assert!(!Path::new(&format!("{}{}", tmp_dir, BACKUP_DIRECTORY_SUFFIX)).exists());
+    }
+
+    #[test]
+    pub fn boundary_test_add_column_replace_failure() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        // A file in place of the backup directory can't be removed, the index can't be replaced.
+        let backup_index_path = format!("{}{}", tmp_dir, BACKUP_DIRECTORY_SUFFIX);
+        std::fs::write(&backup_index_path, "").unwrap();
+        assert!(add_column(tmp_dir, "col4", "").is_err());
+        assert!(!Path::new(&format!("{}{}", tmp_dir, ALTER_COLUMN_DIRECTORY_SUFFIX)).exists());
+
+        // Original index is kept, writer and reader are opened again.
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        assert!(bridge.is_available());
+        assert!(bridge.index.schema().get_field("col4").is_err());
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+        let column_names = vec!["col1".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 100, &column_names, &vec!["Ancient".to_string()]).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        let res = query_term_bitmap_u64(tmp_dir, "col1", "Ancient").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![0, 4, 100]);
+
+        std::fs::remove_file(&backup_index_path).unwrap();
+        assert!(add_column(tmp_dir, "col4", "").is_ok());
+    }
+
+    #[test]
+    pub fn normal_test_drop_column() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+        assert_eq!(query_term_bitmap(tmp_dir, "col2", "Economic").unwrap(), vec![4]);
+
+        assert!(drop_column(tmp_dir, "col2").is_ok());
+        assert!(query_term_bitmap(tmp_dir, "col2", "Economic").is_err());
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+        let column_names = vec!["col1".to_string(), "col2".to_string()];
+        let column_docs = vec!["Ancient".to_string(), "Economic".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 100, &column_names, &column_docs).is_err());
+
+        // Dropped lazily, the field stays in schema until docs are rebuilt.
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        assert!(bridge.index.schema().get_field("col2").is_ok());
+        let parameter = IndexUtils::load_index_parameter(Path::new(tmp_dir)).unwrap();
+        assert_eq!(parameter.dropped_columns, vec!["col2".to_string()]);
+        assert!(!parameter.column_names.contains(&"col2".to_string()));
+
+        assert!(drop_column(tmp_dir, "col2").is_err());
+        assert!(drop_column(tmp_dir, "row_id").is_err());
+
+        // Dropped column is removed by the rebuild, its name can be added again without old values.
+        assert!(add_column(tmp_dir, "col2", "").is_ok());
+        assert!(query_term_bitmap(tmp_dir, "col2", "Economic").unwrap().is_empty());
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        assert!(bridge.dropped_columns().is_empty());
+        assert_eq!(bridge.index.schema().fields().filter(|(_, entry)| entry.name() == "col2").count(), 1);
+    }
+}
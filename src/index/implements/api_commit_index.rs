@@ -3,6 +3,7 @@ use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::index::implements::index_manager::IndexManager;
 
+/// Commit index writer, the commit meta has no payload, so a previous payload is cleared.
 pub fn commit_index(index_path: &str) -> Result<bool, TantivySearchError> {
     static FUNC_NAME: &str = "commit_index";
 
@@ -18,11 +19,30 @@ pub fn commit_index(index_path: &str) -> Result<bool, TantivySearchError> {
     Ok(true)
 }
 
+/// Commit index writer with a user payload, return the commit opstamp.
+/// Empty `payload` means no payload is stored in commit meta, each commit replaces
+/// the payload of the previous one, so an empty payload clears it.
+pub fn commit_index_with_payload(index_path: &str, payload: &str) -> Result<u64, TantivySearchError> {
+    static FUNC_NAME: &str = "commit_index_with_payload";
+
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+    let payload = if payload.is_empty() { None } else { Some(payload) };
+    let opstamp = bridge.commit_with_payload(payload).map_err(|e| {
+        let error_info = format!("Failed to commit index writer with payload: {}", e.to_string());
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        TantivySearchError::InternalError(e)
+    })?;
+    // Reload, not need handle error.
+    let _ = IndexManager::reload_index_reader(index_path);
+
+    Ok(opstamp)
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
-    use crate::index::implements::commit_index;
+    use crate::index::implements::{commit_index, commit_index_with_payload};
 
     #[test]
     pub fn boundary_test_with_empty_directory() {
@@ -46,4 +66,32 @@ mod tests {
 
         SinglePartTest::search_with_index_writer_bridge(index_writer_bridge)
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn normal_test_commit_index_with_payload() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+        assert!(commit_index_with_payload(tmp_dir, "all_1_1_0").is_err());
+
+        let index_writer_bridge =
+            SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, false, false);
+
+        let opstamp_1 = commit_index_with_payload(tmp_dir, "all_1_1_0").unwrap();
+        let metas = index_writer_bridge.index.load_metas().unwrap();
+        assert_eq!(metas.opstamp, opstamp_1);
+        assert_eq!(metas.payload, Some("all_1_1_0".to_string()));
+
+        // Empty payload clears the previous one.
+        let opstamp_2 = commit_index_with_payload(tmp_dir, "").unwrap();
+        assert!(opstamp_2 >= opstamp_1);
+        assert_eq!(index_writer_bridge.index.load_metas().unwrap().payload, None);
+
+        // Commit without payload clears it too.
+        assert!(commit_index_with_payload(tmp_dir, "all_1_1_0").is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        assert_eq!(index_writer_bridge.index.load_metas().unwrap().payload, None);
+
+        SinglePartTest::search_with_index_writer_bridge(index_writer_bridge)
+    }
+}
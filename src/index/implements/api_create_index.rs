@@ -1,6 +1,4 @@
-use std::path::Path;
 use std::sync::Arc;
-use tantivy::Index;
 use crate::common::constants::FFI_INDEX_WRITER_CACHE;
 use crate::common::errors::TantivySearchError;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
@@ -8,34 +6,30 @@ use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::index::implements::index_manager::IndexManager;
 use crate::utils::index_utils::IndexUtils;
 
-pub fn create_index_with_parameter(
+pub fn create_index_with_writer_parameter(
     index_path: &str,
     column_names: &Vec<String>,
     index_json_parameter: &str,
+    writer_json_parameter: &str,
 ) -> Result<bool, TantivySearchError>
 {
-    static FUNC_NAME: &str = "create_index_with_parameter";
+    static FUNC_NAME: &str = "create_index_with_writer_parameter";
+
+    let writer_settings =
+        IndexManager::parse_writer_settings(FUNC_NAME, index_json_parameter, writer_json_parameter)?;
 
     IndexManager::prepare_directory(FUNC_NAME, index_path)?;
-    IndexManager::persist_index_params(index_path, index_json_parameter)?;
+    IndexManager::persist_index_params(index_path, index_json_parameter, column_names, &writer_settings)?;
 
     let (schema, config_map) =
         IndexManager::build_schema(FUNC_NAME, column_names, index_json_parameter)?;
 
     // Create the index in the specified directory.
-    let index = Index::create_in_dir(Path::new(index_path), schema).map_err(|e| {
-        let error_info = format!(
-            "Failed to create index in directory:{}, message:{}",
-            index_path,
-            e.to_string()
-        );
-        ERROR!(function: FUNC_NAME, "{}", error_info);
-        TantivySearchError::TantivyError(e)
-    })?;
+    let index = IndexManager::create_index_in_dir(FUNC_NAME, index_path, schema, index_json_parameter)?;
 
     IndexUtils::register_tokenizers_from_config_map(&index, &config_map);
 
-    let bridge = IndexManager::create_writer(FUNC_NAME, &index, index_path)?;
+    let bridge = IndexManager::create_writer(FUNC_NAME, &index, index_path, &writer_settings, Vec::new())?;
 
     FFI_INDEX_WRITER_CACHE
         .set_index_writer_bridge(index_path.to_string(), Arc::new(bridge))
@@ -47,6 +41,15 @@ pub fn create_index_with_parameter(
     Ok(true)
 }
 
+pub fn create_index_with_parameter(
+    index_path: &str,
+    column_names: &Vec<String>,
+    index_json_parameter: &str,
+) -> Result<bool, TantivySearchError>
+{
+    create_index_with_writer_parameter(index_path, column_names, index_json_parameter, "")
+}
+
 pub fn create_index(
     index_path: &str,
     column_names: &Vec<String>,
@@ -58,9 +61,13 @@ pub fn create_index(
 
 #[cfg(test)]
 mod tests {
+    use tantivy::schema::{Field, FieldType, IndexRecordOption};
+    use tantivy::{DocAddress, Document};
+    use tantivy::store::{Compressor, ZstdCompressor};
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
-    use crate::index::implements::{create_index, create_index_with_parameter};
+    use crate::common::constants::FFI_INDEX_WRITER_CACHE;
+    use crate::index::implements::{create_index, create_index_with_parameter, create_index_with_writer_parameter};
 
 
     // TODO 在创建完索引之后可以检查索引目录中是否存在自定义的配置文件
@@ -118,4 +125,127 @@ mod tests {
         ).is_ok());
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn normal_test_create_index_with_writer_settings() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_json_parameter = r#"
+        {
+            "__writer__": { "heap_size": 134217728, "num_threads": 1, "merge_policy": { "type": "no_merge" } }
+        }
+        "#;
+        assert!(create_index_with_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), index_json_parameter
+        ).is_ok());
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        {
+            let writer = bridge.writer.read().unwrap();
+            let merge_policy = writer.as_ref().unwrap().get_merge_policy();
+            assert!(format!("{:?}", merge_policy).contains("NoMergePolicy"));
+        }
+        let index_writer_bridge =
+            SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, false, true, true);
+        SinglePartTest::search_with_index_writer_bridge(index_writer_bridge)
+    }
+
+    #[test]
+    pub fn normal_test_create_index_with_writer_override() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        // Override the persisted merge policy for current call.
+        let index_json_parameter = r#"{ "__writer__": { "merge_policy": { "type": "no_merge" } } }"#;
+        let writer_json_parameter = r#"{ "merge_policy": { "type": "log", "min_num_segments": 3 } }"#;
+        assert!(create_index_with_writer_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), index_json_parameter, writer_json_parameter
+        ).is_ok());
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        {
+            let writer = bridge.writer.read().unwrap();
+            let merge_policy = writer.as_ref().unwrap().get_merge_policy();
+            assert!(format!("{:?}", merge_policy).contains("LogMergePolicy"));
+        }
+
+        // Invalid writer json parameter.
+        assert!(create_index_with_writer_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), "{}", r#"{ "num_threads": 0 }"#
+        ).is_err());
+        assert!(create_index_with_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), r#"{ "__writer__": { "merge_policy": { "type": "tiered" } } }"#
+        ).is_err());
+    }
+
+    #[test]
+    pub fn normal_test_create_index_with_record_options() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_json_parameter = r#"
+        {
+            "col1": { "tokenizer": { "type": "default" }, "record": "basic", "fieldnorms": false },
+            "col2": { "tokenizer": { "type": "default" }, "record": "freqs" }
+        }
+        "#;
+        assert!(create_index_with_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), index_json_parameter
+        ).is_ok());
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        let schema = bridge.index.schema();
+        let indexing_options = |col_name: &str| {
+            match schema.get_field_entry(schema.get_field(col_name).unwrap()).field_type() {
+                FieldType::Str(options) => {
+                    let indexing = options.get_indexing_options().unwrap();
+                    (indexing.index_option(), indexing.fieldnorms())
+                }
+                _ => panic!("{} should be a text column", col_name),
+            }
+        };
+        assert_eq!(indexing_options("col1"), (IndexRecordOption::Basic, false));
+        assert_eq!(indexing_options("col2"), (IndexRecordOption::WithFreqs, true));
+        // Column not configured keeps positions.
+        assert_eq!(indexing_options("col3"), (IndexRecordOption::WithFreqsAndPositions, true));
+
+        // Typed column can't have record option.
+        assert!(create_index_with_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), r#"{ "col1": { "type": "u64", "record": "basic" } }"#
+        ).is_err());
+    }
+
+    #[test]
+    pub fn normal_test_create_index_with_docstore_settings() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_json_parameter = r#"
+        {
+            "__docstore__": { "compressor": { "type": "zstd", "level": 9 }, "block_size": 65536 },
+            "col1": { "tokenizer": { "type": "default", "store_doc": true } }
+        }
+        "#;
+        assert!(create_index_with_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), index_json_parameter
+        ).is_ok());
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        let settings = bridge.index.settings();
+        assert_eq!(settings.docstore_compression, Compressor::Zstd(ZstdCompressor { compression_level: Some(9) }));
+        assert_eq!(settings.docstore_blocksize, 65536);
+
+        // Stored docs can be read back with zstd docstore.
+        let index_writer_bridge =
+            SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, false, true, true);
+        let searcher = index_writer_bridge.index.reader().unwrap().searcher();
+        let doc: Document = searcher.doc(DocAddress::new(0, 0)).unwrap();
+        assert!(doc.get_first(Field::from_field_id(1)).and_then(|value| value.as_text()).is_some());
+        SinglePartTest::search_with_index_writer_bridge(index_writer_bridge);
+
+        assert!(create_index_with_parameter(
+            tmp_dir, SinglePartTest::get_column_names(), r#"{ "__docstore__": { "compressor": { "type": "zstd", "level": 0 } } }"#
+        ).is_err());
+    }
+}
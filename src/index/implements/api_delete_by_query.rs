@@ -0,0 +1,99 @@
+use tantivy::query::Query;
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::implements::index_manager::IndexManager;
+use crate::search::implements::strategy::{RegexQueryStrategy, SentenceQueryStrategy, SingleTermQueryStrategy};
+
+/// Delete all documents matching the query, then commit.
+/// `query_type` decides how `query` is parsed, same as bitmap queries:
+/// - `term`: like `query_term_bitmap`.
+/// - `sentence`: like `query_sentence_bitmap`.
+/// - `regex`: like `regex_term_bitmap`, `query` is a `LIKE` pattern.
+pub fn delete_by_query(
+    index_path: &str,
+    column_name: &str,
+    query: &str,
+    query_type: &str,
+) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "delete_by_query";
+
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+    let dropped_columns = bridge.dropped_columns();
+
+    let delete_query: Box<dyn Query> = match query_type {
+        "term" => SingleTermQueryStrategy { column_name, term: query, filter_query: "" }.build_query(&bridge.index, &dropped_columns)?,
+        "sentence" => SentenceQueryStrategy { column_name, sentence: query, filter_query: "" }.build_query(&bridge.index, &dropped_columns)?,
+        "regex" => RegexQueryStrategy { column_name, pattern: query, filter_query: "" }.build_query(&bridge.index, &dropped_columns)?,
+        _ => {
+            let error_info = format!("Unknown query_type '{}', should be term, sentence or regex", query_type);
+            ERROR!(function: FUNC_NAME, "{}", error_info);
+            return Err(TantivySearchError::InvalidArgument(error_info));
+        }
+    };
+
+    bridge.delete_query(delete_query).map_err(|e| {
+        ERROR!(function: FUNC_NAME, "{}", e);
+        TantivySearchError::InternalError(e)
+    })?;
+
+    // After delete_query, commit index writer.
+    bridge.commit().map_err(|e| {
+        let error_info = format!("Failed to commit index writer: {}", e.to_string());
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        TantivySearchError::InternalError(error_info)
+    })?;
+
+    // Try reload index reader from CACHE
+    let reload_status = IndexManager::reload_index_reader(index_path)?;
+
+    Ok(reload_status)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::index::implements::delete_by_query;
+    use crate::search::implements::{load_index_reader, query_sentence_bitmap, query_term_bitmap, regex_term_bitmap};
+
+    #[test]
+    pub fn normal_test_delete_by_query() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+
+        // Delete row 0 and row 4.
+        assert!(delete_by_query(tmp_dir, "col1", "Ancient", "term").is_ok());
+        assert!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap().is_empty());
+        assert_eq!(query_sentence_bitmap(tmp_dir, "col2", "nature moral").unwrap(), vec![2]);
+
+        // Delete row 1.
+        assert!(delete_by_query(tmp_dir, "col2", "nature moral", "sentence").is_ok());
+        assert!(query_sentence_bitmap(tmp_dir, "col2", "nature moral").unwrap().is_empty());
+
+        // Delete row 2.
+        assert_eq!(regex_term_bitmap(tmp_dir, "col2", "E%no__c").unwrap(), vec![4]);
+        assert!(delete_by_query(tmp_dir, "col2", "E%no__c", "regex").is_ok());
+        assert!(regex_term_bitmap(tmp_dir, "col2", "E%no__c").unwrap().is_empty());
+    }
+
+    #[test]
+    pub fn boundary_test_delete_by_query() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        assert!(delete_by_query(tmp_dir, "col1", "Ancient", "phrase").is_err());
+        assert!(delete_by_query(tmp_dir, "col_not_exist", "Ancient", "term").is_err());
+        assert!(delete_by_query(tmp_dir, "col1", "Nothing%matched", "regex").is_ok());
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+    }
+}
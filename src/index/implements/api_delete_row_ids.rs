@@ -1,37 +1,96 @@
+use std::sync::Arc;
+use roaring::RoaringTreemap;
 use tantivy::Term;
 use crate::common::errors::TantivySearchError;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::index::implements::index_manager::IndexManager;
+use crate::search::utils::{ConvertUtils, RowIdTreemapQuery};
 
 // FixMe: If all writer threads were killed, this func won't delete any rows.
 pub fn delete_row_ids(index_path: &str, row_ids: &Vec<u32>) -> Result<bool, TantivySearchError> {
     static FUNC_NAME: &str = "delete_row_ids";
+    let row_ids: Vec<u64> = row_ids.iter().map(|&row_id| row_id as u64).collect();
+    delete_row_ids_inner(FUNC_NAME, index_path, &row_ids)
+}
+
+pub fn delete_row_ids_u64(index_path: &str, row_ids: &Vec<u64>) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "delete_row_ids_u64";
+    delete_row_ids_inner(FUNC_NAME, index_path, row_ids)
+}
 
-    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+/// Delete rows marked in u8 bitmap, bit `i` stands for row_id `i`.
+pub fn delete_row_ids_by_u8_bitmap(index_path: &str, u8_bitmap: &Vec<u8>) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "delete_row_ids_by_u8_bitmap";
+    let treemap: RoaringTreemap = ConvertUtils::u8_bitmap_to_roaring(u8_bitmap).iter().map(u64::from).collect();
+    delete_row_ids_by_treemap_query(FUNC_NAME, index_path, treemap)
+}
+
+/// Delete rows in serialized RoaringTreemap.
+pub fn delete_row_ids_by_treemap(index_path: &str, treemap_bytes: &Vec<u8>) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "delete_row_ids_by_treemap";
+    let treemap = ConvertUtils::bytes_to_treemap(treemap_bytes).map_err(|e| {
+        let error_info = format!("Can't deserialize row_ids treemap: {}", e);
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        TantivySearchError::InvalidArgument(error_info)
+    })?;
+    delete_row_ids_by_treemap_query(FUNC_NAME, index_path, treemap)
+}
+
+// Delete all row_ids with one query testing `row_id` fast field against the treemap,
+// instead of one delete operation or term for each row_id.
+fn delete_row_ids_by_treemap_query(
+    func_name: &str,
+    index_path: &str,
+    treemap: RoaringTreemap,
+) -> Result<bool, TantivySearchError> {
+    let bridge = IndexManager::get_index_writer_bridge(func_name, index_path)?;
+
+    if !treemap.is_empty() {
+        bridge.delete_query(Box::new(RowIdTreemapQuery::new(Arc::new(treemap)))).map_err(|e| {
+            ERROR!(function: func_name, "{}", e);
+            TantivySearchError::InternalError(e)
+        })?;
+    }
+
+    // After delete_query, commit index writer.
+    bridge.commit().map_err(|e| {
+        let error_info = format!("Failed to commit index writer: {}", e.to_string());
+        ERROR!(function: func_name, "{}", error_info);
+        TantivySearchError::InternalError(error_info)
+    })?;
+
+    // Try reload index reader from CACHE
+    let reload_status = IndexManager::reload_index_reader(index_path)?;
+
+    Ok(reload_status)
+}
+
+fn delete_row_ids_inner(func_name: &str, index_path: &str, row_ids: &Vec<u64>) -> Result<bool, TantivySearchError> {
+    let bridge = IndexManager::get_index_writer_bridge(func_name, index_path)?;
 
     // Alias: Field::from_field_id
     let schema = bridge.index.schema();
     let row_id_field = schema.get_field("row_id").map_err(|e| {
-        ERROR!(function: FUNC_NAME, "Failed to get row_id field: {}", e.to_string());
+        ERROR!(function: func_name, "Failed to get row_id field: {}", e.to_string());
         TantivySearchError::TantivyError(e)
     })?;
 
     let terms = row_ids
         .iter()
-        .map(|&row_id| Term::from_field_u64(row_id_field, row_id as u64))
+        .map(|&row_id| Term::from_field_u64(row_id_field, row_id))
         .collect();
 
     // Delete row_id terms.
     bridge.delete_terms(terms).map_err(|e| {
-        ERROR!(function: FUNC_NAME, "{}", e);
+        ERROR!(function: func_name, "{}", e);
         TantivySearchError::InternalError(e)
     })?;
 
     // After delete_term, commit index writer.
     bridge.commit().map_err(|e| {
         let error_info = format!("Failed to commit index writer: {}", e.to_string());
-        ERROR!(function: FUNC_NAME, "{}", error_info);
+        ERROR!(function: func_name, "{}", error_info);
         TantivySearchError::InternalError(error_info)
     })?;
 
@@ -43,12 +102,16 @@ pub fn delete_row_ids(index_path: &str, row_ids: &Vec<u32>) -> Result<bool, Tant
 
 #[cfg(test)]
 mod tests {
+    use roaring::RoaringTreemap;
     use tantivy::collector::Count;
     use tantivy::query::QueryParser;
+    use tantivy::Document;
     use tantivy::schema::Field;
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
-    use crate::index::implements::delete_row_ids;
+    use crate::index::implements::{delete_row_ids, delete_row_ids_by_treemap, delete_row_ids_by_u8_bitmap, delete_row_ids_u64};
+    use crate::search::implements::{load_index_reader, query_term_bitmap};
+    use crate::search::utils::ConvertUtils;
 
     #[test]
     pub fn test_delete_row_ids() {
@@ -76,4 +139,57 @@ mod tests {
         let count_col1 = reader.searcher().search(&text_query, &Count).unwrap();
         assert_eq!(count_col1, 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn test_delete_row_ids_u64() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_writer_bridge = SinglePartTest::create_index_and_get_writer_bridge(tmp_dir, false);
+        let large_row_id = u32::MAX as u64 + 1;
+        for row_id in [large_row_id, large_row_id + 1] {
+            let mut doc = Document::default();
+            doc.add_u64(Field::from_field_id(0), row_id);
+            doc.add_text(Field::from_field_id(1), "Ancient empires");
+            assert!(index_writer_bridge.add_document(doc).is_ok());
+        }
+        assert!(index_writer_bridge.commit().is_ok());
+
+        let col1 = Field::from_field_id(1);
+        let query_parser = QueryParser::for_index(&index_writer_bridge.index, vec![col1]);
+        let text_query = query_parser.parse_query("Ancient").unwrap();
+        let reader = index_writer_bridge.index.reader().unwrap();
+        assert_eq!(reader.searcher().search(&text_query, &Count).unwrap(), 2);
+
+        assert!(delete_row_ids_u64(tmp_dir, &vec![large_row_id]).is_ok());
+        assert!(reader.reload().is_ok());
+        assert_eq!(reader.searcher().search(&text_query, &Count).unwrap(), 1);
+    }
+
+    #[test]
+    pub fn test_delete_row_ids_by_bitmap() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+        // Ancient appears in row 0 and row 4.
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+
+        // Delete row 0 and row 1 with u8 bitmap.
+        assert!(delete_row_ids_by_u8_bitmap(tmp_dir, &vec![3]).is_ok());
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![16]);
+
+        // Delete row 4 with serialized treemap.
+        let treemap: RoaringTreemap = [4u64, 100].into_iter().collect();
+        let treemap_bytes = ConvertUtils::treemap_to_bytes(&treemap).unwrap();
+        assert!(delete_row_ids_by_treemap(tmp_dir, &treemap_bytes).is_ok());
+        assert!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap().is_empty());
+
+        // Empty bitmap deletes nothing, invalid treemap is rejected.
+        assert!(delete_row_ids_by_u8_bitmap(tmp_dir, &vec![]).is_ok());
+        assert!(delete_row_ids_by_treemap(tmp_dir, &vec![1, 2, 3]).is_err());
+    }
+}
@@ -59,9 +59,9 @@ mod tests {
         // Test whether index_writer is present after `tantivy_writer_free`.
         assert!(
             bridge.writer
-                .try_lock()
+                .try_read()
                 .unwrap()
-                .as_mut()
+                .as_ref()
                 .is_some()
         );
 
@@ -69,9 +69,9 @@ mod tests {
 
         assert!(
             bridge.writer
-                .try_lock()
+                .try_read()
                 .unwrap()
-                .as_mut()
+                .as_ref()
                 .is_none()
         );
 
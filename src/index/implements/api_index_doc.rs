@@ -1,9 +1,97 @@
-use tantivy::Document;
+use tantivy::schema::{Field, FieldType, Schema};
+use tantivy::{DateTime, Document, Term};
 use crate::common::errors::TantivySearchError;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::index::implements::index_manager::IndexManager;
 
+// Resolve `row_id` field and all column fields from schema, dropped columns can't be indexed.
+fn get_row_id_and_column_fields(
+    func_name: &str,
+    schema: &Schema,
+    column_names: &Vec<String>,
+    dropped_columns: &[String],
+) -> Result<(Field, Vec<Field>), TantivySearchError> {
+    // Alias: Field::from_field_id
+    let row_id_field = schema.get_field("row_id").map_err(|e| {
+        ERROR!(function: func_name, "Failed to get row_id field: {}", e.to_string());
+        TantivySearchError::TantivyError(e)
+    })?;
+
+    let mut column_fields: Vec<Field> = Vec::with_capacity(column_names.len());
+    for col_name in column_names {
+        if dropped_columns.contains(col_name) {
+            let error_info = format!("Column {} has been dropped", col_name);
+            ERROR!(function: func_name, "{}", error_info);
+            return Err(TantivySearchError::InvalidArgument(error_info));
+        }
+        let column_field = schema.get_field(col_name).map_err(|e| {
+            ERROR!(function: func_name, "Failed to get {} field in schema: {}", col_name, e.to_string());
+            TantivySearchError::TantivyError(e)
+        })?;
+        column_fields.push(column_field);
+    }
+
+    Ok((row_id_field, column_fields))
+}
+
+// Column values are given as string, convert them by field type.
+// Empty value of non-text column means null and is skipped.
+// Date value is unix timestamp in seconds, bool value is `true`/`false` or `1`/`0`,
+// json value should be a json object.
+fn add_column_value(
+    func_name: &str,
+    schema: &Schema,
+    doc: &mut Document,
+    field: Field,
+    value: String,
+) -> Result<(), TantivySearchError> {
+    let field_type = schema.get_field_entry(field).field_type();
+    if let FieldType::Str(_) = field_type {
+        doc.add_text(field, value);
+        return Ok(());
+    }
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Ok(());
+    }
+    let parse_error = |e: String| {
+        let error_info = format!(
+            "Can't convert value '{}' of column {} to {:?}: {}",
+            value,
+            schema.get_field_name(field),
+            field_type.value_type(),
+            e
+        );
+        ERROR!(function: func_name, "{}", error_info);
+        TantivySearchError::InvalidArgument(error_info)
+    };
+    match field_type {
+        FieldType::U64(_) => doc.add_u64(field, trimmed.parse::<u64>().map_err(|e| parse_error(e.to_string()))?),
+        FieldType::I64(_) => doc.add_i64(field, trimmed.parse::<i64>().map_err(|e| parse_error(e.to_string()))?),
+        FieldType::F64(_) => doc.add_f64(field, trimmed.parse::<f64>().map_err(|e| parse_error(e.to_string()))?),
+        FieldType::Bool(_) => {
+            let bool_value = match trimmed {
+                "1" => true,
+                "0" => false,
+                _ => trimmed.parse::<bool>().map_err(|e| parse_error(e.to_string()))?,
+            };
+            doc.add_bool(field, bool_value)
+        }
+        FieldType::Date(_) => {
+            let timestamp = trimmed.parse::<i64>().map_err(|e| parse_error(e.to_string()))?;
+            doc.add_date(field, DateTime::from_timestamp_secs(timestamp))
+        }
+        FieldType::JsonObject(_) => {
+            let json_object: serde_json::Map<String, serde_json::Value> =
+                serde_json::from_str(trimmed).map_err(|e| parse_error(e.to_string()))?;
+            doc.add_json_object(field, json_object)
+        }
+        _ => return Err(parse_error("unsupported column type".to_string())),
+    }
+    Ok(())
+}
+
 pub fn index_multi_column_docs(
     index_path: &str,
     row_id: u64,
@@ -14,22 +102,14 @@ pub fn index_multi_column_docs(
 
     let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
 
-    // Alias: Field::from_field_id
     let schema = bridge.index.schema();
-    let row_id_field = schema.get_field("row_id").map_err(|e| {
-        ERROR!(function: FUNC_NAME, "Failed to get row_id field: {}", e.to_string());
-        TantivySearchError::TantivyError(e)
-    })?;
+    let (row_id_field, column_fields) = get_row_id_and_column_fields(FUNC_NAME, &schema, column_names, &bridge.dropped_columns())?;
 
     let mut doc = Document::default();
     doc.add_u64(row_id_field, row_id);
 
-    for (col_name, column_doc) in column_names.iter().zip(column_docs.iter()) {
-        let column_field = schema.get_field(col_name).map_err(|e| {
-            ERROR!(function: FUNC_NAME, "Failed to get {} field in schema: {}", col_name, e.to_string());
-            TantivySearchError::TantivyError(e)
-        })?;
-        doc.add_text(column_field, column_doc.clone());
+    for (column_field, column_doc) in column_fields.iter().zip(column_docs.iter()) {
+        add_column_value(FUNC_NAME, &schema, &mut doc, *column_field, column_doc.clone())?;
     }
 
     match bridge.add_document(doc) {
@@ -42,12 +122,150 @@ pub fn index_multi_column_docs(
     }
 }
 
+/// Replace the document with given `row_id`, or add it if not exists.
+/// Delete and add are applied together, there is no window between them for commit.
+/// Like `index_multi_column_docs`, changes are visible after commit.
+pub fn upsert_multi_column_docs(
+    index_path: &str,
+    row_id: u64,
+    column_names: &Vec<String>,
+    column_docs: &Vec<String>,
+) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "upsert_multi_column_docs";
+
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+
+    let schema = bridge.index.schema();
+    let (row_id_field, column_fields) = get_row_id_and_column_fields(FUNC_NAME, &schema, column_names, &bridge.dropped_columns())?;
+
+    let mut doc = Document::default();
+    doc.add_u64(row_id_field, row_id);
+
+    for (column_field, column_doc) in column_fields.iter().zip(column_docs.iter()) {
+        add_column_value(FUNC_NAME, &schema, &mut doc, *column_field, column_doc.clone())?;
+    }
+
+    match bridge.upsert_document(Term::from_field_u64(row_id_field, row_id), doc) {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            let error_info = format!("Failed to upsert doc:{}", e);
+            ERROR!(function: FUNC_NAME, "{}", error_info);
+            Err(TantivySearchError::InternalError(e))
+        }
+    }
+}
+
+/// Index one row whose columns may have multiple values, such as ClickHouse `Array(String)`.
+/// `column_docs` is flattened: `value_counts[0]` values of `column_names[0]` come first,
+/// followed by `value_counts[1]` values of `column_names[1]`, and so on.
+/// Values of the same column are indexed as separate field values, tantivy leaves a
+/// position gap between them, so phrase query won't match across values.
+pub fn index_multi_valued_docs(
+    index_path: &str,
+    row_id: u64,
+    column_names: &Vec<String>,
+    column_docs: Vec<String>,
+    value_counts: &Vec<u32>,
+) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "index_multi_valued_docs";
+
+    let total_values: usize = value_counts.iter().map(|count| *count as usize).sum();
+    if value_counts.len() != column_names.len() || total_values != column_docs.len() {
+        let error_info = format!(
+            "value_counts size {} should match column_names size {}, and its sum {} should match column_docs size {}",
+            value_counts.len(),
+            column_names.len(),
+            total_values,
+            column_docs.len()
+        );
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+
+    let schema = bridge.index.schema();
+    let (row_id_field, column_fields) = get_row_id_and_column_fields(FUNC_NAME, &schema, column_names, &bridge.dropped_columns())?;
+
+    let mut doc = Document::default();
+    doc.add_u64(row_id_field, row_id);
+
+    let mut column_docs = column_docs.into_iter();
+    for (column_field, value_count) in column_fields.iter().zip(value_counts.iter()) {
+        for column_doc in column_docs.by_ref().take(*value_count as usize) {
+            add_column_value(FUNC_NAME, &schema, &mut doc, *column_field, column_doc)?;
+        }
+    }
+
+    match bridge.add_document(doc) {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            let error_info = format!("Failed to index multi-valued doc:{}", e);
+            ERROR!(function: FUNC_NAME, "{}", error_info);
+            Err(TantivySearchError::InternalError(e))
+        }
+    }
+}
+
+/// Index a contiguous block of rows.
+/// `column_docs` is columnar: all rows of `column_names[0]` come first,
+/// followed by all rows of `column_names[1]`, and so on.
+pub fn index_multi_column_docs_batch(
+    index_path: &str,
+    row_ids: &Vec<u64>,
+    column_names: &Vec<String>,
+    column_docs: Vec<String>,
+) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "index_multi_column_docs_batch";
+
+    if column_docs.len() != column_names.len() * row_ids.len() {
+        let error_info = format!(
+            "column_docs size {} doesn't match column_names size {} * row_ids size {}",
+            column_docs.len(),
+            column_names.len(),
+            row_ids.len()
+        );
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+
+    let schema = bridge.index.schema();
+    let (row_id_field, column_fields) = get_row_id_and_column_fields(FUNC_NAME, &schema, column_names, &bridge.dropped_columns())?;
+
+    let num_rows = row_ids.len();
+    let mut docs: Vec<Document> = row_ids
+        .iter()
+        .map(|row_id| {
+            let mut doc = Document::default();
+            doc.add_u64(row_id_field, *row_id);
+            doc
+        })
+        .collect();
+
+    for (idx, column_doc) in column_docs.into_iter().enumerate() {
+        add_column_value(FUNC_NAME, &schema, &mut docs[idx % num_rows], column_fields[idx / num_rows], column_doc)?;
+    }
+
+    match bridge.add_documents(docs) {
+        Ok(_) => Ok(true),
+        Err(e) => {
+            let error_info = format!("Failed to index docs batch:{}", e);
+            ERROR!(function: FUNC_NAME, "{}", error_info);
+            Err(TantivySearchError::InternalError(e))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
     use crate::common::constants::FFI_INDEX_WRITER_CACHE;
     use crate::common::{SinglePartTest, TEST_MUTEX};
-    use crate::index::implements::index_multi_column_docs;
+    use crate::index::implements::{commit_index, create_index_with_parameter, index_multi_column_docs, index_multi_column_docs_batch, index_multi_valued_docs, upsert_multi_column_docs};
+    use crate::ffi::Statistics;
+    use crate::search::implements::{bm25_natural_language_search, load_index_reader, query_term_bitmap};
 
     #[test]
     pub fn normal_test_index_multi_column_docs() {
@@ -94,4 +312,156 @@ mod tests {
         }
     }
 
-}
\ No newline at end of file
+    #[test]
+    pub fn normal_test_index_multi_column_docs_batch() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::create_index_and_get_writer_bridge(tmp_dir, false);
+        // Flatten mocked docs in columnar order.
+        let mocked_docs:Vec<Vec<&str>> = SinglePartTest::get_mocked_docs();
+        let min_rows = mocked_docs.iter().map(|item| item.len()).min().unwrap();
+        let row_ids: Vec<u64> = (0..min_rows as u64).collect();
+        let column_docs: Vec<String> = mocked_docs.iter()
+            .flat_map(|item| item[..min_rows].iter().map(|doc| doc.to_string()))
+            .collect();
+        assert!(index_multi_column_docs_batch(tmp_dir, &row_ids, SinglePartTest::get_column_names(), column_docs).is_ok());
+        assert!(bridge.commit().is_ok());
+
+        // get index writer from CACHE
+        let bridge = FFI_INDEX_WRITER_CACHE
+            .get_index_writer_bridge(tmp_dir.to_string())
+            .unwrap();
+
+        SinglePartTest::search_with_index_writer_bridge(bridge)
+    }
+
+    #[test]
+    pub fn boundary_test_index_multi_column_docs_batch_size_mismatch() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _bridge = SinglePartTest::create_index_and_get_writer_bridge(tmp_dir, false);
+        let row_ids: Vec<u64> = vec![0, 1];
+        let column_docs: Vec<String> = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert!(index_multi_column_docs_batch(tmp_dir, &row_ids, SinglePartTest::get_column_names(), column_docs).is_err());
+    }
+
+    #[test]
+    pub fn boundary_test_typed_column_invalid_value() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let column_names = vec!["title".to_string(), "price".to_string()];
+        assert!(create_index_with_parameter(tmp_dir, &column_names, r#"{ "price": { "type": "u64" } }"#).is_ok());
+        let index_doc = |price: &str| {
+            index_multi_column_docs(tmp_dir, 0, &column_names, &vec!["title".to_string(), price.to_string()])
+        };
+        assert!(index_doc("12").is_ok());
+        // Empty value means null.
+        assert!(index_doc("").is_ok());
+        assert!(index_doc("-1").is_err());
+        assert!(index_doc("abc").is_err());
+    }
+
+    #[test]
+    pub fn boundary_test_json_column_invalid_value() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let column_names = vec!["attrs".to_string()];
+        assert!(create_index_with_parameter(tmp_dir, &column_names, r#"{ "attrs": { "type": "json" } }"#).is_ok());
+        let index_doc = |attrs: &str| index_multi_column_docs(tmp_dir, 0, &column_names, &vec![attrs.to_string()]);
+        assert!(index_doc(r#"{"color": "red", "size": 3}"#).is_ok());
+        assert!(index_doc("").is_ok());
+        assert!(index_doc("[1, 2]").is_err());
+        assert!(index_doc("color=red").is_err());
+    }
+
+    #[test]
+    pub fn normal_test_index_multi_valued_docs() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let column_names = vec!["title".to_string(), "tags".to_string()];
+        let parameter = r#"{ "title": { "tokenizer": { "type": "default" } }, "tags": { "tokenizer": { "type": "default" } } }"#;
+        assert!(create_index_with_parameter(tmp_dir, &column_names, parameter).is_ok());
+
+        let rows: Vec<(Vec<&str>, Vec<&str>)> = vec![
+            (vec!["harry potter"], vec!["summer", "sale"]),
+            (vec!["first title", "second title"], vec!["summer sale"]),
+            (vec![], vec!["winter"]),
+        ];
+        for (row_id, (titles, tags)) in rows.iter().enumerate() {
+            let column_docs: Vec<String> = titles.iter().chain(tags.iter()).map(|value| value.to_string()).collect();
+            let value_counts = vec![titles.len() as u32, tags.len() as u32];
+            assert!(index_multi_valued_docs(tmp_dir, row_id as u64, &column_names, column_docs, &value_counts).is_ok());
+        }
+        assert!(commit_index(tmp_dir).is_ok());
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        // Every value is indexed.
+        assert_eq!(query_term_bitmap(tmp_dir, "tags", "summer").unwrap(), vec![3]);
+        assert_eq!(query_term_bitmap(tmp_dir, "title", "second").unwrap(), vec![2]);
+        assert_eq!(query_term_bitmap(tmp_dir, "tags", "winter").unwrap(), vec![4]);
+        // Phrase query doesn't match across values.
+        let phrase_row_ids = |sentence: &str| -> Vec<u64> {
+            bm25_natural_language_search(tmp_dir, sentence, &vec![], 10, &vec![], false, true, &Statistics::default(), false)
+                .unwrap()
+                .iter()
+                .map(|item| item.row_id)
+                .collect()
+        };
+        assert_eq!(phrase_row_ids(r#"tags:"summer sale""#), vec![1]);
+        assert!(phrase_row_ids(r#"title:"title second""#).is_empty());
+        assert_eq!(phrase_row_ids(r#"title:"second title""#), vec![1]);
+    }
+
+    #[test]
+    pub fn boundary_test_index_multi_valued_docs() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let column_names = vec!["tags".to_string()];
+        assert!(create_index_with_parameter(tmp_dir, &column_names, "{}").is_ok());
+        let column_docs = vec!["a".to_string(), "b".to_string()];
+        // value_counts size doesn't match column_names.
+        assert!(index_multi_valued_docs(tmp_dir, 0, &column_names, column_docs.clone(), &vec![1, 1]).is_err());
+        // value_counts sum doesn't match column_docs.
+        assert!(index_multi_valued_docs(tmp_dir, 0, &column_names, column_docs.clone(), &vec![3]).is_err());
+        assert!(index_multi_valued_docs(tmp_dir, 0, &column_names, column_docs, &vec![2]).is_ok());
+    }
+
+    #[test]
+    pub fn normal_test_upsert_multi_column_docs() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let column_names = vec!["title".to_string()];
+        assert!(create_index_with_parameter(tmp_dir, &column_names, "{}").is_ok());
+        assert!(index_multi_column_docs(tmp_dir, 0, &column_names, &vec!["old title".to_string()]).is_ok());
+        assert!(index_multi_column_docs(tmp_dir, 1, &column_names, &vec!["old title".to_string()]).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        assert!(load_index_reader(tmp_dir).unwrap());
+        assert_eq!(query_term_bitmap(tmp_dir, "title", "old").unwrap(), vec![3]);
+
+        // Replace row 1 and add row 2.
+        assert!(upsert_multi_column_docs(tmp_dir, 1, &column_names, &vec!["new title".to_string()]).is_ok());
+        assert!(upsert_multi_column_docs(tmp_dir, 2, &column_names, &vec!["new title".to_string()]).is_ok());
+        // Upsert twice before commit, the last one wins.
+        assert!(upsert_multi_column_docs(tmp_dir, 2, &column_names, &vec!["newest title".to_string()]).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+
+        assert_eq!(query_term_bitmap(tmp_dir, "title", "old").unwrap(), vec![1]);
+        assert_eq!(query_term_bitmap(tmp_dir, "title", "new").unwrap(), vec![2]);
+        assert_eq!(query_term_bitmap(tmp_dir, "title", "newest").unwrap(), vec![4]);
+        assert_eq!(query_term_bitmap(tmp_dir, "title", "title").unwrap(), vec![7]);
+    }
+}
@@ -0,0 +1,169 @@
+use tantivy::{SegmentId, SegmentMeta};
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR, INFO};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::implements::api_alter_column::purge_dropped_columns;
+use crate::index::implements::index_manager::IndexManager;
+
+// Split segments into at most `num_segments` groups with balanced doc counts.
+// Groups holding a single segment are skipped unless its deleted docs need to be purged.
+fn plan_segment_groups(
+    segment_metas: &[SegmentMeta],
+    num_segments: usize,
+    purge_deleted: bool,
+) -> Vec<Vec<SegmentId>> {
+    let need_purge = purge_deleted && segment_metas.iter().any(|meta| meta.has_deletes());
+    if segment_metas.len() <= num_segments && !need_purge {
+        return vec![];
+    }
+
+    let mut sorted_metas: Vec<&SegmentMeta> = segment_metas.iter().collect();
+    sorted_metas.sort_by_key(|meta| std::cmp::Reverse(meta.num_docs()));
+
+    let mut groups: Vec<(u64, Vec<&SegmentMeta>)> = vec![(0, vec![]); num_segments.min(sorted_metas.len())];
+    for meta in sorted_metas {
+        if let Some((num_docs, group)) = groups.iter_mut().min_by_key(|(num_docs, _)| *num_docs) {
+            *num_docs += meta.num_docs() as u64;
+            group.push(meta);
+        }
+    }
+
+    groups
+        .into_iter()
+        .filter(|(_, group)| group.len() > 1 || (purge_deleted && group.iter().any(|meta| meta.has_deletes())))
+        .map(|(_, group)| group.iter().map(|meta| meta.id()).collect())
+        .collect()
+}
+
+/// Merge all committed segments into `num_segments` segments (0 is treated as 1).
+/// Segments with deleted docs are always rewritten when `purge_deleted` is true.
+/// If columns have been dropped, docs are rebuilt without them first, which fails
+/// if there are uncommitted docs.
+pub fn merge_index(
+    index_path: &str,
+    num_segments: usize,
+    purge_deleted: bool,
+) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "merge_index";
+
+    // Tantivy merges keep all fields of schema, dropped columns are removed by rebuilding docs.
+    if purge_dropped_columns(index_path)? {
+        INFO!(function: FUNC_NAME, "index_path:{}, docs rebuilt without dropped columns", index_path);
+    }
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+    let merged_groups = bridge
+        .merge_segments(|segment_metas| plan_segment_groups(segment_metas, num_segments.max(1), purge_deleted))
+        .map_err(|e| {
+            let error_info = format!("Failed to merge segments: {}", e);
+            ERROR!(function: FUNC_NAME, "{}", error_info);
+            TantivySearchError::InternalError(error_info)
+        })?;
+    INFO!(function: FUNC_NAME, "index_path:{}, merged groups:{}", index_path, merged_groups);
+    // Reload, not need handle error.
+    let _ = IndexManager::reload_index_reader(index_path);
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use tantivy::Term;
+    use tantivy::schema::Field;
+    use tempfile::TempDir;
+    use crate::common::constants::FFI_INDEX_WRITER_CACHE;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::index::implements::{drop_column, merge_index};
+    use crate::search::implements::{load_index_reader, query_term_bitmap};
+    use crate::utils::index_utils::IndexUtils;
+
+    #[test]
+    pub fn normal_test_merge_index() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        // Each commit produces new segments.
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        for _ in 0..2 {
+            SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, false, true, false);
+        }
+        assert!(bridge.index.searchable_segment_metas().unwrap().len() >= 3);
+        let merge_policy_of = || {
+            let writer = bridge.writer.read().unwrap();
+            format!("{:?}", writer.as_ref().unwrap().get_merge_policy())
+        };
+        let merge_policy = merge_policy_of();
+
+        assert!(merge_index(tmp_dir, 2, false).is_ok());
+        assert_eq!(bridge.index.searchable_segment_metas().unwrap().len(), 2);
+
+        // Merge into one segment by default.
+        assert!(merge_index(tmp_dir, 0, false).is_ok());
+        let segment_metas = bridge.index.searchable_segment_metas().unwrap();
+        assert_eq!(segment_metas.len(), 1);
+        assert_eq!(segment_metas[0].num_docs(), 15);
+
+        // Background merge policy is paused during merge and restored after it,
+        // merging again doesn't wrap the policy once more.
+        assert!(merge_policy.contains("LogMergePolicy"));
+        assert_eq!(merge_policy_of(), merge_policy);
+    }
+
+    #[test]
+    pub fn normal_test_merge_index_purge_deleted() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        assert!(bridge.delete_term(Term::from_field_u64(Field::from_field_id(0), 0)).is_ok());
+        assert!(bridge.commit().is_ok());
+        let segment_metas = bridge.index.searchable_segment_metas().unwrap();
+        let num_segments = segment_metas.len();
+        assert!(segment_metas.iter().any(|meta| meta.has_deletes()));
+
+        // Segments are kept when purge is disabled.
+        assert!(merge_index(tmp_dir, num_segments, false).is_ok());
+        let segment_metas = bridge.index.searchable_segment_metas().unwrap();
+        assert_eq!(segment_metas.len(), num_segments);
+        assert!(segment_metas.iter().any(|meta| meta.has_deletes()));
+
+        // Segments with deleted docs are rewritten.
+        assert!(merge_index(tmp_dir, num_segments, true).is_ok());
+        let segment_metas = bridge.index.searchable_segment_metas().unwrap();
+        assert!(segment_metas.len() <= num_segments);
+        assert!(segment_metas.iter().all(|meta| !meta.has_deletes()));
+        assert_eq!(segment_metas.iter().map(|meta| meta.num_docs()).sum::<u32>(), 4);
+    }
+
+    #[test]
+    pub fn normal_test_merge_index_drops_columns() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+        assert!(drop_column(tmp_dir, "col2").is_ok());
+
+        // Dropped column is removed from schema by the merge.
+        assert!(merge_index(tmp_dir, 1, false).is_ok());
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        assert!(bridge.index.schema().get_field("col2").is_err());
+        assert!(bridge.dropped_columns().is_empty());
+        assert!(IndexUtils::load_index_parameter(Path::new(tmp_dir)).unwrap().dropped_columns.is_empty());
+        assert_eq!(bridge.index.searchable_segment_metas().unwrap().len(), 1);
+        assert_eq!(query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap(), vec![17]);
+        assert!(query_term_bitmap(tmp_dir, "col2", "Economic").is_err());
+    }
+
+    #[test]
+    pub fn boundary_test_merge_without_writer() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        assert!(merge_index(tmp_dir, 1, true).is_err());
+    }
+}
@@ -0,0 +1,302 @@
+use std::fs;
+use std::path::Path;
+use tantivy::{Index, Searcher};
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR, INFO, WARNING};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::implements::api_free_index_writer::free_index_writer;
+use crate::index::implements::index_manager::IndexManager;
+use crate::index::implements::index_rebuilder::{IndexRebuilder, DROPPED_ROW_ID};
+use crate::tokenizer::ingredient::WriterSettings;
+use crate::utils::index_utils::IndexUtils;
+
+fn open_source_searcher(func_name: &str, source_index_path: &str) -> Result<Searcher, TantivySearchError> {
+    let index = Index::open_in_dir(Path::new(source_index_path)).map_err(|e| {
+        ERROR!(function: func_name, "Failed to open source index {}: {}", source_index_path, e.to_string());
+        TantivySearchError::TantivyError(e)
+    })?;
+    let reader = index.reader().map_err(|e| {
+        ERROR!(function: func_name, "Failed to create reader for {}: {}", source_index_path, e.to_string());
+        TantivySearchError::TantivyError(e)
+    })?;
+    Ok(reader.searcher())
+}
+
+/// Merge several part indexes into a new index in `index_path`.
+/// `row_id_mappings` is the concatenation of each source mapping, `mapping_sizes[i]` is
+/// the mapping size of `source_index_paths[i]`. In a source mapping, `mapping[old_row_id]`
+/// is the new row_id, `u64::MAX` means the row has been dropped.
+/// Postings are not copied, docs are rebuilt by `IndexRebuilder` and indexed again.
+/// If merging fails, files written to `index_path` are removed but the directory is kept.
+pub fn merge_parts(
+    index_path: &str,
+    source_index_paths: &Vec<String>,
+    row_id_mappings: &Vec<u64>,
+    mapping_sizes: &Vec<u64>,
+) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "merge_parts";
+
+    if source_index_paths.is_empty() || source_index_paths.len() != mapping_sizes.len() {
+        let error_info = format!(
+            "source_index_paths size {} should be positive and match mapping_sizes size {}",
+            source_index_paths.len(),
+            mapping_sizes.len()
+        );
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+    // The target directory is recreated, it can't be one of the sources.
+    if let Some(source_index_path) = source_index_paths.iter().find(|path| is_same_path(path, index_path)) {
+        let error_info = format!("Target index_path {} is also a source index path {}", index_path, source_index_path);
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+    if mapping_sizes.iter().sum::<u64>() != row_id_mappings.len() as u64 {
+        let error_info = format!(
+            "row_id_mappings size {} doesn't match the sum of mapping_sizes",
+            row_id_mappings.len()
+        );
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        return Err(TantivySearchError::InvalidArgument(error_info));
+    }
+
+    // All sources should share the same schema.
+    let mut searchers: Vec<Searcher> = Vec::with_capacity(source_index_paths.len());
+    for source_index_path in source_index_paths {
+        let searcher = open_source_searcher(FUNC_NAME, source_index_path)?;
+        if let Some(first) = searchers.first() {
+            if first.schema() != searcher.schema() {
+                let error_info = format!("Schema of {} doesn't match other parts", source_index_path);
+                ERROR!(function: FUNC_NAME, "{}", error_info);
+                return Err(TantivySearchError::InvalidArgument(error_info));
+            }
+        }
+        searchers.push(searcher);
+    }
+
+    // Create the target index with the index parameter of first source.
+    // Columns dropped in any source are skipped and stay dropped in the target.
+    let index_parameter = IndexUtils::load_index_parameter(Path::new(&source_index_paths[0]))?;
+    let mut dropped_columns: Vec<String> = index_parameter.dropped_columns;
+    for source_index_path in &source_index_paths[1..] {
+        for column_name in IndexUtils::load_index_parameter(Path::new(source_index_path))?.dropped_columns {
+            if !dropped_columns.contains(&column_name) {
+                dropped_columns.push(column_name);
+            }
+        }
+    }
+    IndexManager::prepare_directory(FUNC_NAME, index_path)?;
+
+    // A half-built target is useless, free its writer and remove its files.
+    let result = build_merged_index(
+        FUNC_NAME,
+        index_path,
+        &index_parameter.tokenizers_json_parameter,
+        &index_parameter.writer_settings,
+        &dropped_columns,
+        source_index_paths,
+        &searchers,
+        row_id_mappings,
+        mapping_sizes,
+    );
+    if result.is_err() {
+        let _ = free_index_writer(index_path);
+        if let Err(e) = clear_directory(index_path) {
+            WARNING!(function: FUNC_NAME, "Failed to remove half-built index files in {}: {}", index_path, e);
+        }
+    }
+    result
+}
+
+// Remove all entries of `index_path`, it has been recreated empty while persisting index params.
+fn clear_directory(index_path: &str) -> std::io::Result<()> {
+    for entry in fs::read_dir(index_path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn is_same_path(left: &str, right: &str) -> bool {
+    if left.trim_end_matches('/') == right.trim_end_matches('/') {
+        return true;
+    }
+    matches!((fs::canonicalize(left), fs::canonicalize(right)), (Ok(left), Ok(right)) if left == right)
+}
+
+// Create the target index, then add rebuilt docs of all sources.
+#[allow(clippy::too_many_arguments)]
+fn build_merged_index(
+    func_name: &str,
+    index_path: &str,
+    index_json_parameter: &str,
+    writer_settings: &WriterSettings,
+    dropped_columns: &[String],
+    source_index_paths: &Vec<String>,
+    searchers: &[Searcher],
+    row_id_mappings: &Vec<u64>,
+    mapping_sizes: &Vec<u64>,
+) -> Result<bool, TantivySearchError> {
+    let mut column_names = IndexManager::get_column_names(searchers[0].schema());
+    column_names.retain(|column_name| !dropped_columns.contains(column_name));
+    IndexManager::persist_index_params(index_path, index_json_parameter, &column_names, writer_settings)?;
+    if !dropped_columns.is_empty() {
+        IndexUtils::update_index_parameter(Path::new(index_path), |parameter| {
+            parameter.dropped_columns = dropped_columns.to_vec();
+        })?;
+    }
+    let index = IndexManager::create_index_in_dir(func_name, index_path, searchers[0].schema().clone(), index_json_parameter)?;
+    IndexUtils::register_tokenizers_from_disk(Path::new(index_path), &index)?;
+    let bridge = IndexManager::create_writer(func_name, &index, index_path, writer_settings, dropped_columns.to_vec())?;
+
+    let mut mapping_offset: usize = 0;
+    for ((searcher, source_index_path), mapping_size) in searchers.iter().zip(source_index_paths).zip(mapping_sizes) {
+        let mapping = &row_id_mappings[mapping_offset..mapping_offset + *mapping_size as usize];
+        mapping_offset += *mapping_size as usize;
+
+        let mut out_of_mapping: u64 = 0;
+        let num_docs = IndexRebuilder::rebuild_documents(
+            func_name,
+            searcher,
+            dropped_columns,
+            |row_id| match mapping.get(row_id as usize) {
+                Some(new_row_id) if *new_row_id != DROPPED_ROW_ID => Some(*new_row_id),
+                Some(_) => None,
+                None => {
+                    out_of_mapping += 1;
+                    None
+                }
+            },
+            |docs| bridge.add_documents(docs).map(|_| ()),
+        )?;
+        if out_of_mapping != 0 {
+            WARNING!(function: func_name, "{} rows of {} are out of row_id mapping, dropped", out_of_mapping, source_index_path);
+        }
+        INFO!(function: func_name, "{} docs merged from {}", num_docs, source_index_path);
+    }
+
+    bridge.commit().map_err(|e| {
+        ERROR!(function: func_name, "Failed to commit merged index: {}", e);
+        TantivySearchError::InternalError(e)
+    })?;
+    bridge.wait_merging_threads().map_err(|e| {
+        ERROR!(function: func_name, "Can't wait merging threads: {}", e);
+        TantivySearchError::InternalError(e)
+    })?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use tantivy::collector::Count;
+    use tantivy::query::QueryParser;
+    use tantivy::schema::Field;
+    use tantivy::Index;
+    use tempfile::TempDir;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::common::constants::FFI_INDEX_WRITER_CACHE;
+    use crate::index::implements::{commit_index, create_index_with_parameter, free_index_writer, index_multi_column_docs, merge_parts};
+    use crate::utils::index_utils::IndexUtils;
+
+    fn create_part(index_path: &str, rows: &[(u64, &str)]) {
+        let index_json_parameter = r#"{ "col1": { "tokenizer": { "type": "whitespace" } } }"#;
+        assert!(create_index_with_parameter(index_path, SinglePartTest::get_column_names(), index_json_parameter).is_ok());
+        for (row_id, text) in rows {
+            let column_docs: Vec<String> = vec![text.to_string(), text.to_string(), text.to_string()];
+            assert!(index_multi_column_docs(index_path, *row_id, SinglePartTest::get_column_names(), &column_docs).is_ok());
+        }
+        assert!(commit_index(index_path).is_ok());
+        assert!(free_index_writer(index_path).is_ok());
+    }
+
+    #[test]
+    pub fn normal_test_merge_parts() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let part_1 = TempDir::new().unwrap();
+        let part_1 = part_1.path().to_str().unwrap();
+        let part_2 = TempDir::new().unwrap();
+        let part_2 = part_2.path().to_str().unwrap();
+        let merged = TempDir::new().unwrap();
+        let merged = merged.path().to_str().unwrap();
+
+        create_part(part_1, &[(0, "Tidal Locking"), (1, "Ocean Tides")]);
+        create_part(part_2, &[(0, "Moon Phases"), (1, "Tidal Forces"), (2, "Dropped Row")]);
+
+        // part_1: 0 -> 3, 1 -> 0; part_2: 0 -> 1, 1 -> 2, 2 dropped.
+        let source_index_paths = vec![part_1.to_string(), part_2.to_string()];
+        let row_id_mappings: Vec<u64> = vec![3, 0, 1, 2, u64::MAX];
+        let mapping_sizes: Vec<u64> = vec![2, 3];
+        assert!(merge_parts(merged, &source_index_paths, &row_id_mappings, &mapping_sizes).is_ok());
+
+        let index = Index::open_in_dir(Path::new(merged)).unwrap();
+        IndexUtils::register_tokenizers_from_disk(Path::new(merged), &index).unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        assert_eq!(searcher.num_docs(), 4);
+
+        // Rows are kept with new row ids, `col1` is searched with its whitespace tokenizer.
+        let parser = QueryParser::for_index(&index, vec![Field::from_field_id(0)]);
+        let row_id_1 = parser.parse_query("1").unwrap();
+        let col1_parser = QueryParser::for_index(&index, vec![Field::from_field_id(1)]);
+        let moon = col1_parser.parse_query("Moon").unwrap();
+        assert_eq!(searcher.search(&moon, &Count).unwrap(), 1);
+        let tidal = col1_parser.parse_query("Tidal").unwrap();
+        assert_eq!(searcher.search(&tidal, &Count).unwrap(), 2);
+        let dropped = col1_parser.parse_query("Dropped").unwrap();
+        assert_eq!(searcher.search(&dropped, &Count).unwrap(), 0);
+        assert_eq!(searcher.search(&row_id_1, &Count).unwrap(), 1);
+    }
+
+    #[test]
+    pub fn boundary_test_merge_parts_invalid_mapping() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let part_1 = TempDir::new().unwrap();
+        let part_1 = part_1.path().to_str().unwrap();
+        let merged = TempDir::new().unwrap();
+        let merged = merged.path().to_str().unwrap();
+        create_part(part_1, &[(0, "Tidal Locking")]);
+
+        let source_index_paths = vec![part_1.to_string()];
+        // mapping_sizes doesn't match source_index_paths.
+        assert!(merge_parts(merged, &source_index_paths, &vec![0], &vec![1, 1]).is_err());
+        // row_id_mappings doesn't match mapping_sizes.
+        assert!(merge_parts(merged, &source_index_paths, &vec![0, 1], &vec![1]).is_err());
+        // source index not exists.
+        let not_exists = vec![format!("{}/not_exists", merged)];
+        assert!(merge_parts(merged, &not_exists, &vec![0], &vec![1]).is_err());
+
+        // Target is one of the sources, source data is kept.
+        let source_with_slash = vec![format!("{}/", part_1)];
+        assert!(merge_parts(part_1, &source_with_slash, &vec![0], &vec![1]).is_err());
+        assert_eq!(Index::open_in_dir(Path::new(part_1)).unwrap().reader().unwrap().searcher().num_docs(), 1);
+    }
+
+    #[test]
+    pub fn boundary_test_merge_parts_build_failure() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let part_1 = TempDir::new().unwrap();
+        let part_1 = part_1.path().to_str().unwrap();
+        let merged = TempDir::new().unwrap();
+        let merged = merged.path().to_str().unwrap();
+
+        // Not stored json column can't be rebuilt.
+        let column_names = vec!["attrs".to_string()];
+        assert!(create_index_with_parameter(part_1, &column_names, r#"{ "attrs": { "type": "json" } }"#).is_ok());
+        let column_docs = vec![r#"{"color": "red"}"#.to_string()];
+        assert!(index_multi_column_docs(part_1, 0, &column_names, &column_docs).is_ok());
+        assert!(commit_index(part_1).is_ok());
+        assert!(free_index_writer(part_1).is_ok());
+
+        let source_index_paths = vec![part_1.to_string()];
+        assert!(merge_parts(merged, &source_index_paths, &vec![0], &vec![1]).is_err());
+        // Target directory is kept without half-built files.
+        assert!(Path::new(merged).is_dir());
+        assert_eq!(std::fs::read_dir(merged).unwrap().count(), 0);
+        assert!(FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(merged.to_string()).is_err());
+    }
+}
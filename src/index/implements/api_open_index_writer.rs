@@ -0,0 +1,159 @@
+use std::path::Path;
+use std::sync::Arc;
+use tantivy::Index;
+use crate::common::constants::FFI_INDEX_WRITER_CACHE;
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, DEBUG, ERROR, INFO};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::implements::index_manager::IndexManager;
+use crate::utils::index_utils::IndexUtils;
+
+/// Open an `IndexWriterBridge` for an existing index without recreating it.
+pub fn open_index_writer(index_path: &str) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "open_index_writer";
+
+    // Boundary.
+    let index_files_directory = Path::new(index_path);
+    if !index_files_directory.exists() || !index_files_directory.is_dir() {
+        let error_info: String = format!("index_path not exists: {:?}", index_path);
+        let error: TantivySearchError = TantivySearchError::IndexNotExists(error_info);
+        ERROR!(function: FUNC_NAME, "{}", error.to_string());
+        return Err(error);
+    }
+    let index_path = index_path.trim_end_matches('/');
+
+    // Reuse the cached writer bridge if its writer is still alive.
+    if let Ok(bridge) = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(index_path.to_string()) {
+        if bridge.is_available() {
+            DEBUG!(function: FUNC_NAME, "Reuse cached index writer:[{}]", index_path);
+            return Ok(true);
+        }
+    }
+
+    // Load tantivy index and register tokenizer with given directory.
+    let index: Index = Index::open_in_dir(index_files_directory).map_err(|e| {
+        ERROR!(function: FUNC_NAME, "{}", e.to_string());
+        TantivySearchError::TantivyError(e)
+    })?;
+    IndexUtils::register_tokenizers_from_disk(index_files_directory, &index)?;
+
+    let index_parameter = IndexUtils::load_index_parameter(index_files_directory)?;
+    IndexUtils::check_column_names(&index_parameter, &index.schema()).map_err(|e| {
+        ERROR!(function: FUNC_NAME, "{}", e.to_string());
+        e
+    })?;
+    let bridge = IndexManager::create_writer(
+        FUNC_NAME,
+        &index,
+        index_path,
+        &index_parameter.writer_settings,
+        index_parameter.dropped_columns,
+    )?;
+
+    // Settings of an older format are written back once the index writer holds the directory lock.
+    // The writer is dropped without being cached if they can't be written.
+    let upgraded = IndexUtils::upgrade_index_parameter(index_files_directory, &index.schema()).map_err(|e| {
+        ERROR!(function: FUNC_NAME, "Failed to upgrade index settings of [{}]: {}", index_path, e);
+        e
+    })?;
+    if upgraded {
+        INFO!(function: FUNC_NAME, "Index settings of [{}] are upgraded to current format", index_path);
+    }
+
+    FFI_INDEX_WRITER_CACHE
+        .set_index_writer_bridge(index_path.to_string(), Arc::new(bridge))
+        .map_err(|e| {
+            ERROR!(function: FUNC_NAME, "{}", e);
+            TantivySearchError::InternalError(e)
+        })?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+    use tempfile::TempDir;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::common::constants::{FFI_INDEX_WRITER_CACHE, INDEX_INFO_FILE_NAME};
+    use crate::index::implements::{
+        commit_index, create_index_with_parameter, free_index_writer, index_multi_column_docs, open_index_writer,
+    };
+    use crate::utils::index_utils::{IndexUtils, INDEX_SETTING_FORMAT_VERSION};
+
+    #[test]
+    pub fn normal_test_open_index_writer() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_json_parameter = r#"{ "col1": { "tokenizer": { "type": "whitespace" } } }"#;
+        assert!(create_index_with_parameter(tmp_dir, SinglePartTest::get_column_names(), index_json_parameter).is_ok());
+        let column_docs: Vec<String> = vec!["first row".to_string(), "a".to_string(), "b".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 0, SinglePartTest::get_column_names(), &column_docs).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        assert!(free_index_writer(tmp_dir).unwrap());
+        assert!(FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).is_err());
+
+        // Reopen the index and append docs, old docs should be kept.
+        assert!(open_index_writer(tmp_dir).unwrap());
+        let column_docs: Vec<String> = vec!["second row".to_string(), "c".to_string(), "d".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 1, SinglePartTest::get_column_names(), &column_docs).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+
+        let bridge = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        let searcher = bridge.index.reader().unwrap().searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        // Tokenizer registered from disk is still available.
+        assert!(bridge.index.tokenizers().get("col1_whitespace").is_some());
+
+        // Open again will reuse the cached writer.
+        assert!(open_index_writer(tmp_dir).unwrap());
+        let cached = FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&bridge, &cached));
+        assert!(free_index_writer(tmp_dir).unwrap());
+    }
+
+    #[test]
+    pub fn boundary_test_open_not_exists_index() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let not_exists = tmp_dir.path().join("not_exists");
+        assert!(open_index_writer(not_exists.to_str().unwrap()).is_err());
+        // Directory exists but no index in it.
+        assert!(open_index_writer(tmp_dir.path().to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    pub fn boundary_test_settings_format() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+        let settings_path = Path::new(tmp_dir).join(INDEX_INFO_FILE_NAME);
+
+        assert!(create_index_with_parameter(tmp_dir, SinglePartTest::get_column_names(), "{}").is_ok());
+        assert!(free_index_writer(tmp_dir).unwrap());
+
+        // Settings written before the file was versioned are upgraded with column names of schema.
+        std::fs::write(&settings_path, r#"{"tokenizers_json_parameter":"{}"}"#).unwrap();
+        // Upgraded settings can't be written, the writer is not opened.
+        let temp_settings_path = Path::new(tmp_dir).join(format!("{}.tmp", INDEX_INFO_FILE_NAME));
+        std::fs::create_dir(&temp_settings_path).unwrap();
+        assert!(open_index_writer(tmp_dir).is_err());
+        assert!(FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).is_err());
+        std::fs::remove_dir(&temp_settings_path).unwrap();
+        assert!(open_index_writer(tmp_dir).unwrap());
+        let parameter = IndexUtils::load_index_parameter(Path::new(tmp_dir)).unwrap();
+        assert_eq!(parameter.format_version, INDEX_SETTING_FORMAT_VERSION);
+        assert_eq!(&parameter.column_names, SinglePartTest::get_column_names());
+        assert!(std::fs::read_to_string(&settings_path).unwrap().contains("format_version"));
+        assert!(free_index_writer(tmp_dir).unwrap());
+
+        // Column names don't match the schema.
+        IndexUtils::update_index_parameter(Path::new(tmp_dir), |parameter| {
+            parameter.column_names = vec!["col1".to_string()];
+        }).unwrap();
+        assert!(open_index_writer(tmp_dir).is_err());
+        assert!(FFI_INDEX_WRITER_CACHE.get_index_writer_bridge(tmp_dir.to_string()).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use tantivy::{IndexReader, ReloadPolicy};
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR, INFO};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::implements::index_manager::IndexManager;
+use crate::index::implements::index_rebuilder::{IndexRebuilder, DROPPED_ROW_ID};
+
+// Rewrite `row_id` of all docs in one commit, index keeps unchanged if any row_id can't be remapped.
+fn rewrite_row_ids<R>(func_name: &str, index_path: &str, mut remap_row_id: R) -> Result<bool, TantivySearchError>
+where
+    R: FnMut(u64) -> Result<Option<u64>, String>,
+{
+    let bridge = IndexManager::get_index_writer_bridge(func_name, index_path)?;
+    // Values of dropped columns aren't rebuilt, their space is reclaimed.
+    let dropped_columns = bridge.dropped_columns();
+
+    let mut num_docs: u64 = 0;
+    bridge
+        .rewrite_documents(|writer| {
+            let reader: IndexReader = bridge
+                .index
+                .reader_builder()
+                .reload_policy(ReloadPolicy::Manual)
+                .try_into()
+                .map_err(|e: tantivy::TantivyError| e.to_string())?;
+            let searcher = reader.searcher();
+
+            let mut remap_error: Option<String> = None;
+            num_docs = IndexRebuilder::rebuild_documents(
+                func_name,
+                &searcher,
+                &dropped_columns,
+                |row_id| match remap_row_id(row_id) {
+                    Ok(new_row_id) => new_row_id,
+                    Err(e) => {
+                        remap_error.get_or_insert(e);
+                        None
+                    }
+                },
+                |docs| {
+                    for doc in docs {
+                        writer.add_document(doc).map_err(|e| e.to_string())?;
+                    }
+                    Ok(())
+                },
+            )
+            .map_err(|e| e.to_string())?;
+
+            match remap_error {
+                Some(e) => Err(e),
+                None => Ok(()),
+            }
+        })
+        .map_err(|e| {
+            let error_info = format!("Failed to rewrite row_ids: {}", e);
+            ERROR!(function: func_name, "{}", error_info);
+            TantivySearchError::InternalError(error_info)
+        })?;
+    INFO!(function: func_name, "{} docs rewritten in {}", num_docs, index_path);
+
+    // Reload, not need handle error.
+    let _ = IndexManager::reload_index_reader(index_path);
+    Ok(true)
+}
+
+/// Remap `row_id` with a permutation, `row_id_mapping[old_row_id]` is the new row_id
+/// and `u64::MAX` means the row has been deleted.
+pub fn remap_row_ids(index_path: &str, row_id_mapping: &Vec<u64>) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "remap_row_ids";
+
+    rewrite_row_ids(FUNC_NAME, index_path, |row_id| match row_id_mapping.get(row_id as usize) {
+        Some(new_row_id) if *new_row_id != DROPPED_ROW_ID => Ok(Some(*new_row_id)),
+        Some(_) => Ok(None),
+        None => Err(format!(
+            "row_id {} is out of row_id_mapping, size: {}",
+            row_id,
+            row_id_mapping.len()
+        )),
+    })
+}
+
+/// Add `offset` to every `row_id`.
+pub fn offset_row_ids(index_path: &str, offset: i64) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "offset_row_ids";
+
+    rewrite_row_ids(FUNC_NAME, index_path, |row_id| match row_id.checked_add_signed(offset) {
+        Some(new_row_id) => Ok(Some(new_row_id)),
+        None => Err(format!("row_id {} overflow with offset {}", row_id, offset)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use tantivy::collector::Count;
+    use tantivy::query::QueryParser;
+    use tantivy::schema::Field;
+    use tantivy::Document;
+    use tempfile::TempDir;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::index::bridge::IndexWriterBridge;
+    use crate::index::implements::{offset_row_ids, remap_row_ids};
+    use crate::search::implements::query_term_bitmap_u64;
+    use crate::search::utils::ConvertUtils;
+
+    fn count(bridge: &Arc<IndexWriterBridge>, field_id: u32, query: &str) -> usize {
+        let searcher = bridge.index.reader().unwrap().searcher();
+        let parser = QueryParser::for_index(&bridge.index, vec![Field::from_field_id(field_id)]);
+        searcher.search(&parser.parse_query(query).unwrap(), &Count).unwrap()
+    }
+
+    #[test]
+    pub fn normal_test_remap_row_ids() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        assert_eq!(count(&bridge, 1, "Ancient"), 2);
+
+        // Reverse row ids and delete row 4.
+        let row_id_mapping: Vec<u64> = vec![3, 2, 1, 0, u64::MAX];
+        assert!(remap_row_ids(tmp_dir, &row_id_mapping).is_ok());
+        assert_eq!(bridge.index.reader().unwrap().searcher().num_docs(), 4);
+        assert_eq!(count(&bridge, 0, "4"), 0);
+        assert_eq!(count(&bridge, 0, "3"), 1);
+        // Text is still searchable, "Ancient" of row 4 is dropped.
+        assert_eq!(count(&bridge, 1, "Ancient"), 1);
+    }
+
+    #[test]
+    pub fn boundary_test_remap_row_ids_with_uncommitted_docs() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        let mut doc = Document::default();
+        doc.add_u64(Field::from_field_id(0), 5);
+        assert!(bridge.add_document(doc).is_ok());
+
+        // Uncommitted doc is neither committed nor dropped.
+        assert!(offset_row_ids(tmp_dir, 10).is_err());
+        assert_eq!(count(&bridge, 0, "0"), 1);
+        assert!(bridge.commit().is_ok());
+        assert_eq!(count(&bridge, 0, "5"), 1);
+        assert!(offset_row_ids(tmp_dir, 10).is_ok());
+        assert_eq!(count(&bridge, 0, "15"), 1);
+    }
+
+    #[test]
+    pub fn normal_test_offset_row_ids() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        assert!(offset_row_ids(tmp_dir, 10).is_ok());
+        assert_eq!(count(&bridge, 0, "0"), 0);
+        assert_eq!(count(&bridge, 0, "10"), 1);
+        assert_eq!(count(&bridge, 0, "14"), 1);
+
+        assert!(offset_row_ids(tmp_dir, -10).is_ok());
+        assert_eq!(count(&bridge, 0, "0"), 1);
+        assert_eq!(bridge.index.reader().unwrap().searcher().num_docs(), 5);
+    }
+
+    #[test]
+    pub fn boundary_test_rewrite_row_ids_rollback() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        // Mapping is too short, index keeps unchanged.
+        assert!(remap_row_ids(tmp_dir, &vec![1, 0]).is_err());
+        // Negative row_id.
+        assert!(offset_row_ids(tmp_dir, -1).is_err());
+        assert_eq!(count(&bridge, 0, "0"), 1);
+        assert_eq!(bridge.index.reader().unwrap().searcher().num_docs(), 5);
+
+        // Writer is still usable after rollback.
+        assert!(offset_row_ids(tmp_dir, 1).is_ok());
+        assert_eq!(count(&bridge, 0, "0"), 0);
+        assert_eq!(count(&bridge, 0, "5"), 1);
+    }
+
+    #[test]
+    fn normal_test_offset_row_ids_with_json_column() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_json_column_docs(tmp_dir);
+        assert!(offset_row_ids(tmp_dir, 10).is_ok());
+
+        // Json column is rebuilt from stored object.
+        let res = query_term_bitmap_u64(tmp_dir, "attrs.color", "red").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![10, 12]);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR, INFO};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::index::implements::index_manager::IndexManager;
+
+/// Discard all uncommitted adds and deletes since the last commit.
+/// Index writer is still usable after rollback.
+pub fn rollback_index(index_path: &str) -> Result<bool, TantivySearchError> {
+    static FUNC_NAME: &str = "rollback_index";
+
+    let bridge = IndexManager::get_index_writer_bridge(FUNC_NAME, index_path)?;
+    let opstamp = bridge.rollback().map_err(|e| {
+        let error_info = format!("Failed to rollback index writer: {}", e);
+        ERROR!(function: FUNC_NAME, "{}", error_info);
+        TantivySearchError::InternalError(error_info)
+    })?;
+    INFO!(function: FUNC_NAME, "Index writer rolled back to opstamp {}, index_path:[{}]", opstamp, index_path);
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::collector::Count;
+    use tantivy::query::QueryParser;
+    use tantivy::schema::Field;
+    use tantivy::{Document, Term};
+    use tempfile::TempDir;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::index::implements::{commit_index, create_index_with_parameter, delete_row_ids, rollback_index};
+    use crate::index::implements::index_manager::IndexManager;
+
+    #[test]
+    pub fn boundary_test_with_empty_directory() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        assert!(rollback_index(tmp_dir).is_err());
+    }
+
+    #[test]
+    pub fn normal_test_rollback_index() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_writer_bridge =
+            SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, true, false);
+        let query_parser = QueryParser::for_index(&index_writer_bridge.index, vec![Field::from_field_id(1)]);
+        let text_query = query_parser.parse_query("Ancient").unwrap();
+        let reader = index_writer_bridge.index.reader().unwrap();
+        assert_eq!(reader.searcher().search(&text_query, &Count).unwrap(), 2);
+
+        // Uncommitted adds and deletes are discarded.
+        let mut doc = Document::default();
+        doc.add_u64(Field::from_field_id(0), 5);
+        doc.add_text(Field::from_field_id(1), "Ancient wisdom");
+        assert!(index_writer_bridge.add_document(doc).is_ok());
+        assert!(index_writer_bridge.delete_terms(vec![Term::from_field_u64(Field::from_field_id(0), 4)]).is_ok());
+        assert!(rollback_index(tmp_dir).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        assert!(reader.reload().is_ok());
+        assert_eq!(reader.searcher().search(&text_query, &Count).unwrap(), 2);
+
+        // Writer is still usable after rollback.
+        assert!(delete_row_ids(tmp_dir, &vec![0]).is_ok());
+        assert!(reader.reload().is_ok());
+        assert_eq!(reader.searcher().search(&text_query, &Count).unwrap(), 1);
+    }
+
+    #[test]
+    pub fn normal_test_rollback_keeps_merge_policy() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_json_parameter = r#"{ "__writer__": { "merge_policy": { "type": "no_merge" } } }"#;
+        assert!(create_index_with_parameter(tmp_dir, SinglePartTest::get_column_names(), index_json_parameter).is_ok());
+        let bridge = IndexManager::get_index_writer_bridge("normal_test_rollback_keeps_merge_policy", tmp_dir).unwrap();
+        let mut doc = Document::default();
+        doc.add_u64(Field::from_field_id(0), 0);
+        doc.add_text(Field::from_field_id(1), "Ancient wisdom");
+        assert!(bridge.add_document(doc).is_ok());
+        assert!(rollback_index(tmp_dir).is_ok());
+
+        // Tantivy resets the merge policy on rollback, the configured one is set back.
+        let writer = bridge.writer.read().unwrap();
+        let merge_policy = writer.as_ref().unwrap().get_merge_policy();
+        assert!(format!("{:?}", merge_policy).contains("NoMergePolicy"));
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 use std::path::Path;
-use std::sync::{Arc, Mutex};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, RwLock};
 use tantivy::Index;
-use tantivy::schema::{FAST, INDEXED, IndexRecordOption, Schema, TEXT, TextFieldIndexing, TextOptions};
+use tantivy::schema::{FAST, INDEXED, IndexRecordOption, JsonObjectOptions, Schema, TEXT, TextFieldIndexing, TextOptions};
 use crate::common::errors::TantivySearchError;
 use crate::{common::constants::LOG_CALLBACK, ERROR, INFO};
 use crate::logger::logger_bridge::TantivySearchLogger;
@@ -11,12 +12,11 @@ use crate::common::constants::{FFI_INDEX_SEARCHER_CACHE, FFI_INDEX_WRITER_CACHE}
 use crate::index::bridge::IndexWriterBridge;
 use crate::index::implements::api_free_index_writer::free_index_writer;
 use crate::search::implements::free_index_reader;
+use crate::common::errors::TokenizerError;
+use crate::tokenizer::ingredient::{ColumnType, Config, WriterSettings};
 use crate::tokenizer::parser::{TokenizerConfig, TokenizerUtils};
 use crate::utils::index_utils::IndexUtils;
 
-const MEMORY_64MB: usize = 1024 * 1024 * 64;
-const BUILD_THREADS: usize = 2;
-
 pub struct IndexManager;
 
 impl IndexManager {
@@ -29,16 +29,32 @@ impl IndexManager {
         Ok(())
     }
 
-    pub(crate) fn persist_index_params(index_path: &str, index_json_parameter: &str) -> Result<(), TantivySearchError> {
+    pub(crate) fn persist_index_params(
+        index_path: &str,
+        index_json_parameter: &str,
+        column_names: &Vec<String>,
+        writer_settings: &WriterSettings,
+    ) -> Result<(), TantivySearchError> {
         let index_files_directory: &Path = Path::new(index_path);
         IndexUtils::save_index_parameter_to_disk(
             index_files_directory,
             index_json_parameter.to_string(),
+            column_names.clone(),
+            writer_settings.clone(),
             true,
         )?;
         Ok(())
     }
 
+    /// Column names in schema, without `row_id`.
+    pub(crate) fn get_column_names(schema: &Schema) -> Vec<String> {
+        schema
+            .fields()
+            .map(|(_, field_entry)| field_entry.name().to_string())
+            .filter(|name| name != "row_id")
+            .collect()
+    }
+
     pub(crate) fn build_schema(
         func_name: &str,
         column_names: &Vec<String>,
@@ -48,25 +64,81 @@ impl IndexManager {
         schema_builder.add_u64_field("row_id", FAST | INDEXED);
         let combine = |left:&str, right:&str| {format!("{}_{}", left, right)};
 
+        let config: Config = serde_json::from_str(index_json_parameter).map_err(|e| {
+            ERROR!(function: func_name, "{}", e.to_string());
+            TantivySearchError::TokenizerError(TokenizerError::JsonDeserializeError(e.to_string()))
+        })?;
+        let column_types: HashMap<String, ColumnType> = config
+            .get_columns()
+            .iter()
+            .map(|(col_name, column)| (col_name.clone(), column.get_column_type()))
+            .collect();
+        // Inverted index options of text and json columns, record option and whether fieldnorms kept.
+        let index_options: HashMap<String, (IndexRecordOption, bool)> = config
+            .get_columns()
+            .iter()
+            .map(|(col_name, column)| {
+                (col_name.clone(), (column.get_record_option().to_index_record_option(), column.has_fieldnorms()))
+            })
+            .collect();
+
         let config_map: HashMap<String, TokenizerConfig> =
-            TokenizerUtils::parser_index_json_parameter(index_json_parameter)
+            TokenizerUtils::parser_from_tokenizer_config(config)
                 .map_err(|e| {
                     ERROR!(function: func_name, "{}", e.to_string());
                     TantivySearchError::TokenizerError(e)
                 })?;
 
         for col_name in column_names {
-            if let Some(config) = config_map.get(col_name) {
+            let column_type = column_types.get(col_name).copied().unwrap_or_default();
+            let (record_option, fieldnorms) = index_options
+                .get(col_name)
+                .copied()
+                .unwrap_or((IndexRecordOption::WithFreqsAndPositions, true));
+            if column_type == ColumnType::Json {
+                // Json column is stored with `store_doc`, documents can only be rebuilt from the original object.
+                let tokenizer_name = config_map
+                    .get(col_name)
+                    .map(|config| combine(col_name, config.tokenizer_name.as_str()))
+                    .unwrap_or_else(|| "default".to_string());
+                let mut json_options = JsonObjectOptions::default().set_indexing_options(
+                    TextFieldIndexing::default()
+                        .set_tokenizer(tokenizer_name.as_str())
+                        .set_index_option(record_option)
+                        .set_fieldnorms(fieldnorms),
+                );
+                if config_map.get(col_name).is_some_and(|config| config.doc_store) {
+                    json_options = json_options.set_stored();
+                }
+                INFO!(function: func_name, "col_name:{}, json column tokenizer name: {}", col_name, tokenizer_name);
+                schema_builder.add_json_field(col_name, json_options);
+            } else if column_type != ColumnType::Text {
+                // Typed columns are stored as fast fields, used for range and term filters.
+                INFO!(function: func_name, "col_name:{}, column type: {:?}", col_name, column_type);
+                match column_type {
+                    ColumnType::U64 => schema_builder.add_u64_field(col_name, FAST | INDEXED),
+                    ColumnType::I64 => schema_builder.add_i64_field(col_name, FAST | INDEXED),
+                    ColumnType::F64 => schema_builder.add_f64_field(col_name, FAST | INDEXED),
+                    ColumnType::Date => schema_builder.add_date_field(col_name, FAST | INDEXED),
+                    ColumnType::Bool => schema_builder.add_bool_field(col_name, FAST | INDEXED),
+                    ColumnType::Text | ColumnType::Json => unreachable!(),
+                };
+            } else if let Some(config) = config_map.get(col_name) {
                 let tokenizer_name = combine(col_name, config.tokenizer_name.as_str());
                 let mut text_options = TextOptions::default().set_indexing_options(
                     TextFieldIndexing::default()
                         .set_tokenizer(tokenizer_name.as_str())
-                        .set_index_option(IndexRecordOption::WithFreqsAndPositions),
+                        .set_index_option(record_option)
+                        .set_fieldnorms(fieldnorms),
                 );
                 if config.doc_store {
                     text_options = text_options.set_stored();
                 }
-                INFO!(function: func_name, "col_name:{}, tokenizer name: {}", col_name, tokenizer_name);
+                INFO!(
+                    function: func_name,
+                    "col_name:{}, tokenizer name: {}, record option: {:?}, fieldnorms: {}",
+                    col_name, tokenizer_name, record_option, fieldnorms
+                );
                 schema_builder.add_text_field(col_name, text_options);
             } else {
                 INFO!(function: func_name, "col_name:{}, tokenizer name: {}", col_name, "TEXT");
@@ -76,25 +148,77 @@ impl IndexManager {
         Ok((schema_builder.build(), config_map))
     }
 
+    /// Writer settings come from `__writer__` in index json parameter,
+    /// `writer_json_parameter` can override them for current call.
+    pub(crate) fn parse_writer_settings(
+        func_name: &str,
+        index_json_parameter: &str,
+        writer_json_parameter: &str,
+    ) -> Result<WriterSettings, TantivySearchError> {
+        let config: Config = serde_json::from_str(index_json_parameter).map_err(|e| {
+            ERROR!(function: func_name, "{}", e.to_string());
+            TantivySearchError::TokenizerError(TokenizerError::JsonDeserializeError(e.to_string()))
+        })?;
+        config.get_writer_settings().with_override(writer_json_parameter).map_err(|e| {
+            ERROR!(function: func_name, "Invalid writer json parameter: {}", e.to_string());
+            TantivySearchError::InvalidArgument(e.to_string())
+        })
+    }
+
+    /// Create index in `index_path`, docstore settings come from `__docstore__` in index json parameter.
+    pub(crate) fn create_index_in_dir(
+        func_name: &str,
+        index_path: &str,
+        schema: Schema,
+        index_json_parameter: &str,
+    ) -> Result<Index, TantivySearchError> {
+        let config: Config = serde_json::from_str(index_json_parameter).map_err(|e| {
+            ERROR!(function: func_name, "{}", e.to_string());
+            TantivySearchError::TokenizerError(TokenizerError::JsonDeserializeError(e.to_string()))
+        })?;
+        let docstore_settings = config.get_docstore_settings();
+        INFO!(function: func_name, "docstore settings: {:?}", docstore_settings);
+
+        Index::builder()
+            .schema(schema)
+            .settings(docstore_settings.to_index_settings())
+            .create_in_dir(Path::new(index_path))
+            .map_err(|e| {
+                let error_info = format!(
+                    "Failed to create index in directory:{}, message:{}",
+                    index_path,
+                    e.to_string()
+                );
+                ERROR!(function: func_name, "{}", error_info);
+                TantivySearchError::TantivyError(e)
+            })
+    }
+
     pub(crate) fn create_writer(
         func_name: &str,
         index: &Index,
-        index_path: &str
+        index_path: &str,
+        writer_settings: &WriterSettings,
+        dropped_columns: Vec<String>,
     ) -> Result<IndexWriterBridge, TantivySearchError> {
-        let writer = index.writer_with_num_threads(BUILD_THREADS, MEMORY_64MB).map_err(|e| {
-            let error_info = format!("Failed to create tantivy writer: {}", e);
-            ERROR!(function: func_name, "{}", error_info);
-            TantivySearchError::TantivyError(e)
-        })?;
+        let writer = index
+            .writer_with_num_threads(writer_settings.num_threads, writer_settings.heap_size)
+            .map_err(|e| {
+                let error_info = format!("Failed to create tantivy writer: {}", e);
+                ERROR!(function: func_name, "{}", error_info);
+                TantivySearchError::TantivyError(e)
+            })?;
 
-        let mut merge_policy = tantivy::merge_policy::LogMergePolicy::default();
-        merge_policy.set_min_num_segments(5);
-        writer.set_merge_policy(Box::new(merge_policy));
+        writer.set_merge_policy(writer_settings.merge_policy.to_merge_policy());
+        INFO!(function: func_name, "index_path:{}, writer settings: {:?}", index_path, writer_settings);
 
         Ok(IndexWriterBridge {
             index: index.clone(),
             path: index_path.trim_end_matches('/').to_string(),
-            writer: Mutex::new(Some(writer)),
+            merge_policy: writer.get_merge_policy(),
+            writer: RwLock::new(Some(writer)),
+            pending_operations: AtomicBool::new(false),
+            dropped_columns: RwLock::new(dropped_columns),
         })
     }
 
@@ -0,0 +1,579 @@
+use std::sync::Arc;
+use tantivy::postings::{Postings, TermInfo};
+use tantivy::schema::{Field, FieldType, IndexRecordOption};
+use tantivy::tokenizer::{PreTokenizedString, Token};
+use tantivy::columnar::Column;
+use tantivy::fieldnorm::FieldNormReader;
+use tantivy::{DateTime, DocAddress, DocId, DocSet, Document, InvertedIndexReader, Searcher, SegmentReader};
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR};
+use crate::logger::logger_bridge::TantivySearchLogger;
+
+/// Row id mapped to this value will be dropped while rebuilding.
+pub(crate) const DROPPED_ROW_ID: u64 = u64::MAX;
+
+/// Documents of a segment are rebuilt in windows of this many doc ids.
+const DOC_WINDOW_SIZE: DocId = 64 * 1024;
+
+// Tokens of one field for a window of docs, each token is `(position, term_ord)`.
+// The term dictionary is kept for the whole segment, tokens only for the current window.
+struct UninvertedField {
+    inverted_index: Arc<InvertedIndexReader>,
+    terms: Vec<String>,
+    term_infos: Vec<TermInfo>,
+    // Lower bound of the next doc in postings of each term, `TERMINATED` once all docs are read.
+    next_docs: Vec<DocId>,
+    window_start: DocId,
+    doc_tokens: Vec<Vec<(u32, u32)>>,
+    // Field norms of a field indexed with `IndexRecordOption::Basic`, postings only keep
+    // distinct terms of such field, tokens are padded to the field norm of each doc.
+    basic_fieldnorms: Option<FieldNormReader>,
+}
+
+impl UninvertedField {
+    // Walk the term dictionary of a field, postings are read later window by window.
+    fn open(segment_reader: &SegmentReader, field: Field) -> tantivy::Result<Self> {
+        let inverted_index = segment_reader.inverted_index(field)?;
+        let mut terms: Vec<String> = Vec::new();
+        let mut term_infos: Vec<TermInfo> = Vec::new();
+        let mut term_stream = inverted_index.terms().stream()?;
+        while term_stream.advance() {
+            terms.push(String::from_utf8_lossy(term_stream.key()).to_string());
+            term_infos.push(term_stream.value().clone());
+        }
+        let next_docs = vec![0; terms.len()];
+        let record_option = match segment_reader.schema().get_field_entry(field).field_type() {
+            FieldType::Str(options) => options.get_indexing_options().map(|indexing| indexing.index_option()),
+            _ => None,
+        };
+        let basic_fieldnorms = match record_option {
+            Some(IndexRecordOption::Basic) => segment_reader.get_fieldnorms_reader(field).ok(),
+            _ => None,
+        };
+        Ok(UninvertedField {
+            inverted_index,
+            terms,
+            term_infos,
+            next_docs,
+            window_start: 0,
+            doc_tokens: Vec::new(),
+            basic_fieldnorms,
+        })
+    }
+
+    // Restore tokens of docs in `[window_start, window_end)` from postings.
+    // Postings of each term still having docs are opened again and seek to the window,
+    // so the cost of a window is about the number of such terms plus tokens in the window.
+    fn load_window(&mut self, window_start: DocId, window_end: DocId) -> tantivy::Result<()> {
+        self.window_start = window_start;
+        self.doc_tokens.clear();
+        self.doc_tokens.resize((window_end - window_start) as usize, Vec::new());
+        let mut positions: Vec<u32> = Vec::new();
+        for (term_ord, term_info) in self.term_infos.iter().enumerate() {
+            if self.next_docs[term_ord] >= window_end {
+                continue;
+            }
+            let mut postings = self
+                .inverted_index
+                .read_postings_from_terminfo(term_info, IndexRecordOption::WithFreqsAndPositions)?;
+            let mut doc_id = postings.doc();
+            if doc_id < window_start {
+                doc_id = postings.seek(window_start);
+            }
+            while doc_id < window_end {
+                postings.positions(&mut positions);
+                let tokens = &mut self.doc_tokens[(doc_id - window_start) as usize];
+                if positions.is_empty() {
+                    // Positions are not recorded, only keep term frequency.
+                    tokens.extend((0..postings.term_freq()).map(|_| (u32::MAX, term_ord as u32)));
+                } else {
+                    tokens.extend(positions.iter().map(|position| (*position, term_ord as u32)));
+                }
+                doc_id = postings.advance();
+            }
+            self.next_docs[term_ord] = doc_id;
+        }
+        Ok(())
+    }
+
+    // Tokens of a doc sorted by position, terms without positions get the next free positions.
+    fn take_tokens(&mut self, doc_id: DocId) -> Vec<Token> {
+        let mut doc_tokens = std::mem::take(&mut self.doc_tokens[(doc_id - self.window_start) as usize]);
+        if doc_tokens.is_empty() {
+            return Vec::new();
+        }
+        doc_tokens.sort_unstable();
+        let mut next_position: usize = 0;
+        let mut tokens: Vec<Token> = doc_tokens
+            .into_iter()
+            .map(|(position, term_ord)| {
+                let position = if position == u32::MAX { next_position } else { position as usize };
+                next_position = position + 1;
+                Token {
+                    offset_from: 0,
+                    offset_to: 0,
+                    position,
+                    text: self.terms[term_ord as usize].clone(),
+                    position_length: 1,
+                }
+            })
+            .collect();
+        // Repeating a term doesn't change postings without freqs, but keeps the field norm,
+        // so scores of the rebuilt doc stay the same.
+        if let Some(fieldnorms) = &self.basic_fieldnorms {
+            let num_tokens = fieldnorms.fieldnorm(doc_id) as usize;
+            let last_text = tokens[tokens.len() - 1].text.clone();
+            while tokens.len() < num_tokens {
+                tokens.push(Token {
+                    offset_from: 0,
+                    offset_to: 0,
+                    position: next_position,
+                    text: last_text.clone(),
+                    position_length: 1,
+                });
+                next_position += 1;
+            }
+        }
+        tokens
+    }
+}
+
+// Fast field column of a non-text field in one segment.
+enum FastFieldColumn {
+    U64(Column<u64>),
+    I64(Column<i64>),
+    F64(Column<f64>),
+    Bool(Column<bool>),
+    Date(Column<DateTime>),
+}
+
+impl FastFieldColumn {
+    fn open(segment_reader: &SegmentReader, field_name: &str, field_type: &FieldType) -> tantivy::Result<Option<Self>> {
+        let fast_fields = segment_reader.fast_fields();
+        let column = match field_type {
+            FieldType::U64(_) => FastFieldColumn::U64(fast_fields.u64(field_name)?),
+            FieldType::I64(_) => FastFieldColumn::I64(fast_fields.i64(field_name)?),
+            FieldType::F64(_) => FastFieldColumn::F64(fast_fields.f64(field_name)?),
+            FieldType::Bool(_) => FastFieldColumn::Bool(fast_fields.bool(field_name)?),
+            FieldType::Date(_) => FastFieldColumn::Date(fast_fields.date(field_name)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(column))
+    }
+
+    fn add_values(&self, field: Field, doc_id: DocId, doc: &mut Document) {
+        match self {
+            FastFieldColumn::U64(column) => column.values_for_doc(doc_id).for_each(|value| doc.add_u64(field, value)),
+            FastFieldColumn::I64(column) => column.values_for_doc(doc_id).for_each(|value| doc.add_i64(field, value)),
+            FastFieldColumn::F64(column) => column.values_for_doc(doc_id).for_each(|value| doc.add_f64(field, value)),
+            FastFieldColumn::Bool(column) => column.values_for_doc(doc_id).for_each(|value| doc.add_bool(field, value)),
+            FastFieldColumn::Date(column) => column.values_for_doc(doc_id).for_each(|value| doc.add_date(field, value)),
+        }
+    }
+}
+
+/// Rebuild alive documents of an index without tokenizing text again.
+/// - Dropped columns are skipped, so their space is reclaimed by the rebuilt docs.
+/// - Indexed text fields are restored from postings as pre-tokenized text, stored values
+///   are read from doc store and keep their text, the tokens are attached to the first value.
+/// - Positions, term freqs and field norms are kept. Field norms of a doc come from
+///   the number of tokens, for fields indexed without freqs the tokens are padded to the
+///   field norm of the source segment.
+/// - Other stored fields are copied from doc store, a stored json field is indexed again
+///   by its tokenizer.
+/// - Not stored typed fields are restored from fast fields.
+/// - Not stored json fields can't be rebuilt, an error is returned.
+pub(crate) struct IndexRebuilder;
+
+impl IndexRebuilder {
+    /// Rebuild documents segment by segment, `remap_row_id` gives the new row_id
+    /// or `None` to drop the doc, `add_documents` receives rebuilt docs of each window
+    /// of doc ids, so only one window of docs and tokens is kept in memory.
+    /// Return the number of rebuilt documents.
+    pub(crate) fn rebuild_documents<R, A>(
+        func_name: &str,
+        searcher: &Searcher,
+        dropped_columns: &[String],
+        mut remap_row_id: R,
+        mut add_documents: A,
+    ) -> Result<u64, TantivySearchError>
+    where
+        R: FnMut(u64) -> Option<u64>,
+        A: FnMut(Vec<Document>) -> Result<(), String>,
+    {
+        let schema = searcher.schema();
+        let row_id_field = schema.get_field("row_id").map_err(|e| {
+            ERROR!(function: func_name, "Failed to get row_id field: {}", e.to_string());
+            TantivySearchError::TantivyError(e)
+        })?;
+
+        let mut stored_fields: Vec<Field> = Vec::new();
+        let mut inverted_fields: Vec<Field> = Vec::new();
+        let mut fast_fields: Vec<Field> = Vec::new();
+        for (field, field_entry) in schema.fields() {
+            if field == row_id_field || dropped_columns.iter().any(|column_name| column_name == field_entry.name()) {
+                continue;
+            }
+            if let (FieldType::Str(_), true) = (field_entry.field_type(), field_entry.is_indexed()) {
+                inverted_fields.push(field);
+            } else if field_entry.is_stored() {
+                stored_fields.push(field);
+            } else if let FieldType::JsonObject(_) = field_entry.field_type() {
+                // Tokens of json column don't keep the object structure, it can't be restored from postings.
+                let error_info = format!("Json column {} is not stored, documents can't be rebuilt", field_entry.name());
+                ERROR!(function: func_name, "{}", error_info);
+                return Err(TantivySearchError::InvalidArgument(error_info));
+            } else if field_entry.is_fast() {
+                fast_fields.push(field);
+            }
+        }
+        let read_stored_doc = !stored_fields.is_empty()
+            || inverted_fields.iter().any(|field| schema.get_field_entry(*field).is_stored());
+
+        let mut rebuilt_docs: u64 = 0;
+        for (segment_ord, segment_reader) in searcher.segment_readers().iter().enumerate() {
+            let row_id_column = segment_reader.fast_fields().u64("row_id").map_err(|e| {
+                ERROR!(function: func_name, "Failed to read row_id fast field: {}", e.to_string());
+                TantivySearchError::TantivyError(e)
+            })?;
+
+            let mut uninverted_fields: Vec<UninvertedField> = Vec::with_capacity(inverted_fields.len());
+            for field in &inverted_fields {
+                let uninverted = UninvertedField::open(segment_reader, *field).map_err(|e| {
+                    ERROR!(function: func_name, "Failed to uninvert field {:?}: {}", field, e.to_string());
+                    TantivySearchError::TantivyError(e)
+                })?;
+                uninverted_fields.push(uninverted);
+            }
+
+            let mut fast_field_columns: Vec<(Field, FastFieldColumn)> = Vec::with_capacity(fast_fields.len());
+            for field in &fast_fields {
+                let field_entry = schema.get_field_entry(*field);
+                let column = FastFieldColumn::open(segment_reader, field_entry.name(), field_entry.field_type())
+                    .map_err(|e| {
+                        ERROR!(function: func_name, "Failed to read fast field {:?}: {}", field, e.to_string());
+                        TantivySearchError::TantivyError(e)
+                    })?;
+                if let Some(column) = column {
+                    fast_field_columns.push((*field, column));
+                }
+            }
+
+            let max_doc = segment_reader.max_doc();
+            let mut window_start: DocId = 0;
+            while window_start < max_doc {
+                let window_end = window_start.saturating_add(DOC_WINDOW_SIZE).min(max_doc);
+                for (field, uninverted) in inverted_fields.iter().zip(uninverted_fields.iter_mut()) {
+                    uninverted.load_window(window_start, window_end).map_err(|e| {
+                        ERROR!(function: func_name, "Failed to uninvert field {:?}: {}", field, e.to_string());
+                        TantivySearchError::TantivyError(e)
+                    })?;
+                }
+
+                let mut docs: Vec<Document> = Vec::new();
+                for doc_id in window_start..window_end {
+                    if segment_reader.is_deleted(doc_id) {
+                        continue;
+                    }
+                    let new_row_id = match row_id_column.values_for_doc(doc_id).next().and_then(&mut remap_row_id) {
+                        Some(row_id) => row_id,
+                        None => continue,
+                    };
+                    let mut doc = Document::default();
+                    doc.add_u64(row_id_field, new_row_id);
+
+                    let stored_doc = if read_stored_doc {
+                        let stored_doc = searcher.doc(DocAddress::new(segment_ord as u32, doc_id)).map_err(|e| {
+                            ERROR!(function: func_name, "Failed to read stored doc: {}", e.to_string());
+                            TantivySearchError::TantivyError(e)
+                        })?;
+                        Some(stored_doc)
+                    } else {
+                        None
+                    };
+                    if let Some(stored_doc) = &stored_doc {
+                        for field in &stored_fields {
+                            for value in stored_doc.get_all(*field) {
+                                doc.add_field_value(*field, value.clone());
+                            }
+                        }
+                    }
+
+                    for (field, column) in &fast_field_columns {
+                        column.add_values(*field, doc_id, &mut doc);
+                    }
+
+                    for (field, uninverted) in inverted_fields.iter().zip(uninverted_fields.iter_mut()) {
+                        let mut tokens = Some(uninverted.take_tokens(doc_id));
+                        if let Some(stored_doc) = &stored_doc {
+                            // Doc store keeps the text of pre-tokenized values, tokens are not stored.
+                            for text in stored_doc.get_all(*field).filter_map(|value| value.as_text()) {
+                                let tokens = tokens.take().unwrap_or_default();
+                                doc.add_pre_tokenized_text(*field, PreTokenizedString { text: text.to_string(), tokens });
+                            }
+                        }
+                        match tokens {
+                            Some(tokens) if !tokens.is_empty() => {
+                                doc.add_pre_tokenized_text(*field, PreTokenizedString { text: String::new(), tokens });
+                            }
+                            _ => {}
+                        }
+                    }
+                    docs.push(doc);
+                }
+
+                rebuilt_docs += docs.len() as u64;
+                add_documents(docs).map_err(|e| {
+                    ERROR!(function: func_name, "Failed to add rebuilt docs: {}", e);
+                    TantivySearchError::InternalError(e)
+                })?;
+                window_start = window_end;
+            }
+        }
+        Ok(rebuilt_docs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::collector::Count;
+    use tantivy::query::QueryParser;
+    use tantivy::schema::{IndexRecordOption, Schema, TextFieldIndexing, TextOptions, FAST, INDEXED, STORED, TEXT};
+    use tantivy::tokenizer::{RawTokenizer, Token};
+    use tantivy::{doc, DateTime, Document, Index, Searcher};
+    use crate::index::implements::index_rebuilder::{IndexRebuilder, UninvertedField};
+
+    fn count(index: &Index, searcher: &Searcher, field_name: &str, query: &str) -> usize {
+        let field = index.schema().get_field(field_name).unwrap();
+        let query_parser = QueryParser::for_index(index, vec![field]);
+        searcher.search(&query_parser.parse_query(query).unwrap(), &Count).unwrap()
+    }
+
+    fn token_texts(tokens: Vec<Token>) -> Vec<String> {
+        tokens.into_iter().map(|token| token.text).collect()
+    }
+
+    #[test]
+    fn test_uninvert_field_by_window() {
+        let mut schema_builder = Schema::builder();
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut writer = index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        writer.add_document(doc!(text => "moon tides")).unwrap();
+        writer.add_document(doc!(text => "ocean")).unwrap();
+        writer.add_document(doc!(text => "ocean moon")).unwrap();
+        writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+
+        let mut uninverted = UninvertedField::open(searcher.segment_reader(0), text).unwrap();
+        uninverted.load_window(0, 1).unwrap();
+        assert_eq!(token_texts(uninverted.take_tokens(0)), vec!["moon", "tides"]);
+        // Postings seek over the skipped window, only tokens of the current window are restored.
+        uninverted.load_window(2, 3).unwrap();
+        assert_eq!(token_texts(uninverted.take_tokens(2)), vec!["ocean", "moon"]);
+    }
+
+    #[test]
+    fn test_rebuild_documents_with_fast_fields() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        let price = schema_builder.add_f64_field("price", FAST | INDEXED);
+        let on_sale = schema_builder.add_bool_field("on_sale", FAST | INDEXED);
+        let created = schema_builder.add_date_field("created", FAST | INDEXED);
+        let schema = schema_builder.build();
+
+        let source_index = Index::create_in_ram(schema);
+        let mut source_writer = source_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        source_writer.add_document(doc!(
+            row_id => 0u64,
+            price => 9.5f64,
+            on_sale => true,
+            created => DateTime::from_timestamp_secs(1700000000)
+        )).unwrap();
+        source_writer.add_document(doc!(row_id => 1u64)).unwrap();
+        source_writer.commit().unwrap();
+        let searcher = source_index.reader().unwrap().searcher();
+
+        let mut rebuilt: Vec<Document> = Vec::new();
+        IndexRebuilder::rebuild_documents(
+            "test_rebuild_documents_with_fast_fields",
+            &searcher,
+            &[],
+            Some,
+            |docs| {
+                rebuilt.extend(docs);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(rebuilt.len(), 2);
+        assert_eq!(rebuilt[0].get_first(price).and_then(|value| value.as_f64()), Some(9.5));
+        assert_eq!(rebuilt[0].get_first(on_sale).and_then(|value| value.as_bool()), Some(true));
+        assert_eq!(
+            rebuilt[0].get_first(created).and_then(|value| value.as_date()),
+            Some(DateTime::from_timestamp_secs(1700000000))
+        );
+        // Null values are kept as null.
+        assert!(rebuilt[1].get_first(price).is_none());
+    }
+
+    #[test]
+    fn test_rebuild_documents_with_not_stored_json() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        schema_builder.add_json_field("attrs", TEXT);
+        let source_index = Index::create_in_ram(schema_builder.build());
+        let mut source_writer = source_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        source_writer.add_document(doc!(row_id => 0u64)).unwrap();
+        source_writer.commit().unwrap();
+        let searcher = source_index.reader().unwrap().searcher();
+
+        let result = IndexRebuilder::rebuild_documents("test_rebuild_documents_with_not_stored_json", &searcher, &[], Some, |_| Ok(()));
+        assert!(result.is_err());
+
+        // Dropped column is skipped, even if it can't be rebuilt.
+        let dropped_columns = vec!["attrs".to_string()];
+        let result = IndexRebuilder::rebuild_documents("test_rebuild_documents_with_not_stored_json", &searcher, &dropped_columns, Some, |_| Ok(()));
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_rebuild_documents() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        let stored = schema_builder.add_text_field("stored", TEXT | STORED);
+        let text = schema_builder.add_text_field("text", TEXT);
+        let schema = schema_builder.build();
+
+        let source_index = Index::create_in_ram(schema.clone());
+        let mut source_writer = source_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        source_writer.add_document(doc!(row_id => 0u64, stored => "Ancient Moon", text => "the tidal locking of the moon")).unwrap();
+        source_writer.add_document(doc!(row_id => 1u64, stored => "Ocean", text => "ocean tides follow the moon")).unwrap();
+        source_writer.add_document(doc!(row_id => 2u64, stored => "Empty")).unwrap();
+        source_writer.commit().unwrap();
+        let searcher = source_index.reader().unwrap().searcher();
+
+        // Drop row_id 1 and shift others.
+        let mut rebuilt: Vec<Document> = Vec::new();
+        let num_docs = IndexRebuilder::rebuild_documents(
+            "test_rebuild_documents",
+            &searcher,
+            &[],
+            |old_row_id| if old_row_id == 1 { None } else { Some(old_row_id + 100) },
+            |docs| {
+                rebuilt.extend(docs);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(num_docs, 2);
+
+        let target_index = Index::create_in_ram(schema);
+        let mut target_writer = target_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        for doc in rebuilt {
+            target_writer.add_document(doc).unwrap();
+        }
+        target_writer.commit().unwrap();
+        let target_searcher = target_index.reader().unwrap().searcher();
+
+        // Pre-tokenized text keeps positions.
+        assert_eq!(count(&target_index, &target_searcher, "text", "\"tidal locking\""), 1);
+        assert_eq!(count(&target_index, &target_searcher, "text", "\"locking tidal\""), 0);
+        assert_eq!(count(&target_index, &target_searcher, "text", "moon"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "text", "ocean"), 0);
+        // Stored fields are copied.
+        assert_eq!(count(&target_index, &target_searcher, "stored", "ancient"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "stored", "empty"), 1);
+        // Row ids are remapped.
+        assert_eq!(count(&target_index, &target_searcher, "row_id", "100"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "row_id", "102"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "row_id", "0"), 0);
+
+        // Field norms are the same as source.
+        let source_norms = searcher.segment_reader(0).get_fieldnorms_reader(text).unwrap();
+        let target_norms = target_searcher.segment_reader(0).get_fieldnorms_reader(text).unwrap();
+        assert_eq!(source_norms.fieldnorm(0), target_norms.fieldnorm(0));
+    }
+
+    #[test]
+    fn test_rebuild_stored_text_without_tokenizer() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        let stored = schema_builder.add_text_field("stored", TEXT | STORED);
+        let schema = schema_builder.build();
+
+        let source_index = Index::create_in_ram(schema.clone());
+        let mut source_writer = source_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        source_writer.add_document(doc!(row_id => 0u64, stored => "Ancient Moon", stored => "Tides")).unwrap();
+        source_writer.commit().unwrap();
+        let searcher = source_index.reader().unwrap().searcher();
+
+        let mut rebuilt: Vec<Document> = Vec::new();
+        IndexRebuilder::rebuild_documents("test_rebuild_stored_text_without_tokenizer", &searcher, &[], Some, |docs| {
+            rebuilt.extend(docs);
+            Ok(())
+        })
+        .unwrap();
+
+        // Target tokenizer would keep the whole text as one token, postings come from the source.
+        let target_index = Index::create_in_ram(schema);
+        target_index.tokenizers().register("default", RawTokenizer::default());
+        let mut target_writer = target_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        for doc in rebuilt {
+            target_writer.add_document(doc).unwrap();
+        }
+        target_writer.commit().unwrap();
+        let target_searcher = target_index.reader().unwrap().searcher();
+        assert_eq!(count(&target_index, &target_searcher, "stored", "ancient"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "stored", "tides"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "stored", "Ancient Moon"), 0);
+
+        // Stored values keep their text.
+        let stored_doc = target_searcher.doc(tantivy::DocAddress::new(0, 0)).unwrap();
+        let texts: Vec<&str> = stored_doc.get_all(stored).filter_map(|value| value.as_text()).collect();
+        assert_eq!(texts, vec!["Ancient Moon", "Tides"]);
+        let source_norms = searcher.segment_reader(0).get_fieldnorms_reader(stored).unwrap();
+        let target_norms = target_searcher.segment_reader(0).get_fieldnorms_reader(stored).unwrap();
+        assert_eq!(source_norms.fieldnorm(0), target_norms.fieldnorm(0));
+    }
+
+    #[test]
+    fn test_rebuild_keeps_field_norms_without_freqs() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        let basic_options = TextOptions::default().set_indexing_options(
+            TextFieldIndexing::default().set_tokenizer("default").set_index_option(IndexRecordOption::Basic),
+        );
+        let basic = schema_builder.add_text_field("basic", basic_options);
+        let schema = schema_builder.build();
+
+        let source_index = Index::create_in_ram(schema.clone());
+        let mut source_writer = source_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        source_writer.add_document(doc!(row_id => 0u64, basic => "moon moon moon tides")).unwrap();
+        source_writer.add_document(doc!(row_id => 1u64, basic => "ocean")).unwrap();
+        source_writer.commit().unwrap();
+        let searcher = source_index.reader().unwrap().searcher();
+
+        let mut rebuilt: Vec<Document> = Vec::new();
+        IndexRebuilder::rebuild_documents("test_rebuild_keeps_field_norms_without_freqs", &searcher, &[], Some, |docs| {
+            rebuilt.extend(docs);
+            Ok(())
+        })
+        .unwrap();
+
+        let target_index = Index::create_in_ram(schema);
+        let mut target_writer = target_index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        for doc in rebuilt {
+            target_writer.add_document(doc).unwrap();
+        }
+        target_writer.commit().unwrap();
+        let target_searcher = target_index.reader().unwrap().searcher();
+        assert_eq!(count(&target_index, &target_searcher, "basic", "moon"), 1);
+        assert_eq!(count(&target_index, &target_searcher, "basic", "ocean"), 1);
+
+        let source_norms = searcher.segment_reader(0).get_fieldnorms_reader(basic).unwrap();
+        let target_norms = target_searcher.segment_reader(0).get_fieldnorms_reader(basic).unwrap();
+        assert_eq!(target_norms.fieldnorm(0), 4);
+        assert_eq!(source_norms.fieldnorm(1), target_norms.fieldnorm(1));
+    }
+}
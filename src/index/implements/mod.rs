@@ -1,15 +1,40 @@
 mod index_manager;
+mod index_rebuilder;
 mod api_create_index;
 mod api_index_doc;
 mod api_delete_row_ids;
+mod api_delete_by_query;
 mod api_commit_index;
+mod api_rollback_index;
 mod api_free_index_writer;
+mod api_open_index_writer;
+mod api_merge_index;
+mod api_merge_parts;
+mod api_remap_row_ids;
+mod api_alter_column;
 
 pub use {
+    api_alter_column::add_column,
+    api_alter_column::drop_column,
     api_commit_index::commit_index,
+    api_commit_index::commit_index_with_payload,
     api_create_index::create_index_with_parameter,
+    api_create_index::create_index_with_writer_parameter,
     api_create_index::create_index,
     api_delete_row_ids::delete_row_ids,
+    api_delete_row_ids::delete_row_ids_u64,
+    api_delete_row_ids::delete_row_ids_by_u8_bitmap,
+    api_delete_row_ids::delete_row_ids_by_treemap,
+    api_delete_by_query::delete_by_query,
     api_free_index_writer::free_index_writer,
-    api_index_doc::index_multi_column_docs
+    api_merge_index::merge_index,
+    api_merge_parts::merge_parts,
+    api_remap_row_ids::offset_row_ids,
+    api_remap_row_ids::remap_row_ids,
+    api_rollback_index::rollback_index,
+    api_open_index_writer::open_index_writer,
+    api_index_doc::index_multi_column_docs,
+    api_index_doc::index_multi_column_docs_batch,
+    api_index_doc::index_multi_valued_docs,
+    api_index_doc::upsert_multi_column_docs
 };
\ No newline at end of file
@@ -67,6 +67,12 @@ pub mod ffi {
         pub error: FFIError,
     }
 
+    #[derive(Debug, Clone)]
+    pub struct FFIStringResult {
+        pub result: String,
+        pub error: FFIError,
+    }
+
     #[derive(Debug, Clone)]
     pub struct FFIVecU8Result {
         pub result: Vec<u8>,
@@ -98,13 +104,36 @@ pub mod ffi {
         /// arguments:
         /// - `index_path`: index directory.
         /// - `column_names`: which columns will be used to build index.
-        /// - `index_json_parameter`: config index with json.
+        /// - `index_json_parameter`: config index with json, non-text columns are declared
+        ///                           with `type`, such as `{"price": {"type": "f64"}}`,
+        ///                           json column is declared as `{"attrs": {"type": "json"}}`,
+        ///                           json column is stored only with `store_doc` in its tokenizer, docs with a
+        ///                           not stored json column can't be rebuilt by remap, merge parts or alter column,
+        ///                           text and json column can set `record` (`basic`, `freqs` or
+        ///                           `positions`) and `fieldnorms` to shrink index size, docstore
+        ///                           compressor and block size are set under the `__docstore__` key,
+        ///                           reader `reload_policy` (`on_commit` or `manual`) is set under the `__reader__` key.
         fn ffi_create_index_with_parameter(
             index_path: &CxxString,
             column_names: &CxxVector<CxxString>,
             index_json_parameter: &CxxString,
         ) -> FFIBoolResult;
 
+        /// Create tantivy index with writer settings given for current call.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_names`: which columns will be used to build index.
+        /// - `index_json_parameter`: config index with json, writer settings can be
+        ///                           persisted under the `__writer__` key.
+        /// - `writer_json_parameter`: override persisted writer settings, such as
+        ///                            `heap_size`, `num_threads` and `merge_policy`.
+        fn ffi_create_index_with_writer_parameter(
+            index_path: &CxxString,
+            column_names: &CxxVector<CxxString>,
+            index_json_parameter: &CxxString,
+            writer_json_parameter: &CxxString,
+        ) -> FFIBoolResult;
+
         /// Create tantivy index by default.
         /// arguments:
         /// - `index_path`: index directory.
@@ -116,7 +145,8 @@ pub mod ffi {
         /// - `index_path`: index directory.
         /// - `row_id`: row_id given by ClickHouse.
         /// - `column_names`: align with column_docs.
-        /// - `column_docs`: align with column_names.
+        /// - `column_docs`: align with column_names, values of typed columns are given as string,
+        ///                  empty means null, date is unix timestamp in seconds.
         fn ffi_index_multi_column_docs(
             index_path: &CxxString,
             row_id: u64,
@@ -124,22 +154,180 @@ pub mod ffi {
             column_docs: &CxxVector<CxxString>,
         ) -> FFIBoolResult;
 
+        /// Index a contiguous block of rows with given rowIds.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `row_ids`: row_ids given by ClickHouse, one for each row.
+        /// - `column_names`: which columns will be indexed.
+        /// - `column_docs`: columnar docs, size is `column_names.size() * row_ids.size()`,
+        ///                  all rows of the first column come first.
+        fn ffi_index_multi_column_docs_batch(
+            index_path: &CxxString,
+            row_ids: &CxxVector<u64>,
+            column_names: &CxxVector<CxxString>,
+            column_docs: &CxxVector<CxxString>,
+        ) -> FFIBoolResult;
+
+        /// Replace the doc with given rowId, or index it if not exists, delete and add are applied atomically.
+        /// Same as index, changes are visible after commit.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `row_id`: row_id given by ClickHouse.
+        /// - `column_names`: align with column_docs.
+        /// - `column_docs`: align with column_names.
+        fn ffi_upsert_multi_column_docs(
+            index_path: &CxxString,
+            row_id: u64,
+            column_names: &CxxVector<CxxString>,
+            column_docs: &CxxVector<CxxString>,
+        ) -> FFIBoolResult;
+
+        /// Index one row whose columns may have multiple values, such as `Array(String)`.
+        /// Phrase query won't match across values of the same column.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `row_id`: row_id given by ClickHouse.
+        /// - `column_names`: which columns will be indexed.
+        /// - `column_docs`: flattened values, `value_counts[0]` values of the first column come first.
+        /// - `value_counts`: align with column_names, number of values of each column.
+        fn ffi_index_multi_valued_docs(
+            index_path: &CxxString,
+            row_id: u64,
+            column_names: &CxxVector<CxxString>,
+            column_docs: &CxxVector<CxxString>,
+            value_counts: &CxxVector<u32>,
+        ) -> FFIBoolResult;
+
         /// Delete a group of rowIds.
         /// arguments:
         /// - `index_path`: index directory.
         /// - `row_ids`: a group of rowIds need be deleted.
         fn ffi_delete_row_ids(index_path: &CxxString, row_ids: &CxxVector<u32>) -> FFIBoolResult;
 
-        /// Commit index writer
+        /// Delete a group of u64 rowIds.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `row_ids`: a group of u64 rowIds need be deleted.
+        fn ffi_delete_row_ids_u64(index_path: &CxxString, row_ids: &CxxVector<u64>) -> FFIBoolResult;
+
+        /// Delete rowIds marked in u8 bitmap with one row_id set query, such as lightweight delete mask.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `u8_bitmap`: bit `i` set means rowId `i` need be deleted.
+        fn ffi_delete_row_ids_by_u8_bitmap(index_path: &CxxString, u8_bitmap: &CxxVector<u8>) -> FFIBoolResult;
+
+        /// Delete u64 rowIds in serialized RoaringTreemap with one row_id set query.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `treemap_bytes`: rowIds need be deleted, serialized in RoaringTreemap portable format.
+        fn ffi_delete_row_ids_by_treemap(index_path: &CxxString, treemap_bytes: &CxxVector<u8>) -> FFIBoolResult;
+
+        /// Delete all rows matching the query, no need to know rowIds.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute query, json column uses path such as `attrs.color`.
+        /// - `query`: term, sentence or `LIKE` pattern, same as bitmap queries.
+        /// - `query_type`: one of `term`, `sentence` and `regex`.
+        fn ffi_delete_by_query(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            query: &CxxString,
+            query_type: &CxxString,
+        ) -> FFIBoolResult;
+
+        /// Commit index writer, the payload of the last commit is not kept,
+        /// use `ffi_index_writer_commit_with_payload` to keep one.
         /// arguments:
         /// - `index_path`: index directory.
         fn ffi_index_writer_commit(index_path: &CxxString) -> FFIBoolResult;
 
+        /// Rollback index writer, discard all uncommitted adds and deletes since last commit.
+        /// arguments:
+        /// - `index_path`: index directory.
+        fn ffi_index_writer_rollback(index_path: &CxxString) -> FFIBoolResult;
+
+        /// Commit index writer with a user payload, return the commit opstamp.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `payload`: stored in commit meta, such as part name or mutation version.
+        ///              empty string means no payload, the payload of the last commit is cleared.
+        fn ffi_index_writer_commit_with_payload(index_path: &CxxString, payload: &CxxString) -> FFIU64Result;
+
         /// Free index writer
         /// arguments:
         /// - `index_path`: index directory.
         fn ffi_free_index_writer(index_path: &CxxString) -> FFIBoolResult;
 
+        /// Open index writer for an existing index, docs can be appended to it.
+        /// arguments:
+        /// - `index_path`: index directory.
+        fn ffi_open_index_writer(index_path: &CxxString) -> FFIBoolResult;
+
+        /// Merge committed segments, wait for completion and remove old files.
+        /// If columns have been dropped, docs are rebuilt without them first, which fails if there are uncommitted docs.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `num_segments`: max segments number after merge, 0 is treated as 1.
+        /// - `purge_deleted`: rewrite segments which contain deleted docs.
+        fn ffi_merge_index(index_path: &CxxString, num_segments: u32, purge_deleted: bool) -> FFIBoolResult;
+
+        /// Merge several part indexes into a new index, row_ids are remapped.
+        /// Columns dropped in any part stay dropped in the new index, their values are not copied.
+        /// Postings are not copied, docs of each part are rebuilt the same way as `ffi_remap_row_ids`
+        /// and indexed again. The target directory is kept empty if merging fails.
+        /// arguments:
+        /// - `index_path`: new index directory.
+        /// - `source_index_paths`: index directories of the parts to merge.
+        /// - `row_id_mappings`: concatenated row_id mappings of all parts, for each part
+        ///                      `mapping[old_row_id]` is the new row_id, UINT64_MAX means dropped.
+        /// - `mapping_sizes`: row_id mapping size of each part.
+        fn ffi_merge_parts(
+            index_path: &CxxString,
+            source_index_paths: &CxxVector<CxxString>,
+            row_id_mappings: &CxxVector<u64>,
+            mapping_sizes: &CxxVector<u64>,
+        ) -> FFIBoolResult;
+
+        /// Remap rowIds of an existing index, fails if there are uncommitted docs.
+        /// Text columns are rebuilt from their postings without tokenizing again, stored values keep
+        /// their text, positions and field norms are kept. Stored json columns are read from the
+        /// doc store and tokenized again, not stored json columns can't be remapped.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `row_id_mapping`: `row_id_mapping[old_row_id]` is the new row_id, UINT64_MAX means deleted.
+        fn ffi_remap_row_ids(index_path: &CxxString, row_id_mapping: &CxxVector<u64>) -> FFIBoolResult;
+
+        /// Add an offset to all rowIds of an existing index, fails if there are uncommitted docs.
+        /// Docs are rebuilt the same way as `ffi_remap_row_ids`.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `offset`: will be added to each rowId, can be negative.
+        fn ffi_offset_row_ids(index_path: &CxxString, offset: i64) -> FFIBoolResult;
+
+        /// Add a column to an existing index, existing docs have no value for it.
+        /// Tantivy schema is fixed once an index is created, so all docs are rebuilt into a new index
+        /// which then replaces the original directory. Fails if there are uncommitted docs, adding or
+        /// deleting docs is blocked until the index is replaced.
+        /// Dropped columns are removed from the new schema, a dropped column name can be added again.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: new column name.
+        /// - `column_json_parameter`: config of new column, such as `{"tokenizer": {"type": "default"}}`.
+        fn ffi_add_column(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            column_json_parameter: &CxxString,
+        ) -> FFIBoolResult;
+
+        /// Drop a column of an existing index without rebuilding docs, it can't be indexed or queried any more.
+        /// The column stays in tantivy schema until docs are rebuilt by `ffi_merge_index`, `ffi_add_column`,
+        /// `ffi_merge_parts`, `ffi_remap_row_ids` or `ffi_offset_row_ids`, which reclaim its space.
+        /// Segments merged in background keep the column.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: column need be dropped.
+        fn ffi_drop_column(index_path: &CxxString, column_name: &CxxString) -> FFIBoolResult;
+
         /// Load index reader
         /// arguments:
         /// - `index_path`: index directory.
@@ -155,10 +343,15 @@ pub mod ffi {
         /// - `index_path`: index directory.
         fn ffi_get_indexed_doc_counts(index_path: &CxxString) -> FFIU64Result;
 
+        /// Get payload stored by the last commit, empty string means no payload.
+        /// arguments:
+        /// - `index_path`: index directory.
+        fn ffi_get_commit_payload(index_path: &CxxString) -> FFIStringResult;
+
         /// Execute a term query and return rowIds u8 bitmap.
         /// arguments:
         /// - `index_path`: index directory.
-        /// - `column_name`: which column will execute search.
+        /// - `column_name`: which column will execute search, json column uses path such as `attrs.color`.
         /// - `term`: term needs to be searched.
         pub fn ffi_query_term_bitmap(
             index_path: &CxxString,
@@ -199,6 +392,50 @@ pub mod ffi {
             pattern: &CxxString,
         ) -> FFIVecU8Result;
 
+        /// Execute a term query and return u64 rowIds as serialized RoaringTreemap.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `term`: term needs to be searched.
+        pub fn ffi_query_term_bitmap_u64(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            term: &CxxString,
+        ) -> FFIVecU8Result;
+
+        /// Execute a group of terms query and return u64 rowIds as serialized RoaringTreemap.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `terms`: terms need to be searched.
+        pub fn ffi_query_terms_bitmap_u64(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            terms: &CxxVector<CxxString>,
+        ) -> FFIVecU8Result;
+
+        /// Execute a sentence query and return u64 rowIds as serialized RoaringTreemap.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `sentence`: sentence needs to be searched.
+        pub fn ffi_query_sentence_bitmap_u64(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            sentence: &CxxString,
+        ) -> FFIVecU8Result;
+
+        /// Execute a regex query and return u64 rowIds as serialized RoaringTreemap.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `pattern`: pattern should be given by ClickHouse.
+        pub fn ffi_regex_term_bitmap_u64(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            pattern: &CxxString,
+        ) -> FFIVecU8Result;
+
         /// Execute a bm25 query.
         /// arguments:
         /// - `index_path`: index directory.
@@ -219,6 +456,100 @@ pub mod ffi {
             statistics: &Statistics,
         ) -> FFIVecRowIdWithScoreResult;
 
+        /// Execute a bm25 query, alive rowIds are given by serialized RoaringTreemap.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `sentence`: from ClickHouse TextSearch function.
+        /// - `top_k`: only return top k related results.
+        /// - `u64_alive_bitmap`: alive u64 row ids given by serialized RoaringTreemap.
+        /// - `query_with_filter`:use alive_bitmap or not.
+        /// - `statistics`: for multi parts bm25 statistics info.
+        pub fn ffi_bm25_search_u64(
+            index_path: &CxxString,
+            sentence: &CxxString,
+            column_names: &CxxVector<CxxString>,
+            top_k: u32,
+            u64_alive_bitmap: &CxxVector<u8>,
+            query_with_filter: bool,
+            enable_nlq: bool,
+            operator_or: bool,
+            statistics: &Statistics,
+        ) -> FFIVecRowIdWithScoreResult;
+
+        /// Execute a term query with filter on typed columns.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `term`: term needs to be searched.
+        /// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+        pub fn ffi_query_term_bitmap_with_filter(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            term: &CxxString,
+            filter_query: &CxxString,
+        ) -> FFIVecU8Result;
+
+        /// Execute a group of terms query with filter on typed columns.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `terms`: terms need to be searched.
+        /// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+        pub fn ffi_query_terms_bitmap_with_filter(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            terms: &CxxVector<CxxString>,
+            filter_query: &CxxString,
+        ) -> FFIVecU8Result;
+
+        /// Execute a sentence query with filter on typed columns.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `sentence`: sentence needs to be searched.
+        /// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+        pub fn ffi_query_sentence_bitmap_with_filter(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            sentence: &CxxString,
+            filter_query: &CxxString,
+        ) -> FFIVecU8Result;
+
+        /// Execute a regex query with filter on typed columns.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `column_name`: which column will execute search.
+        /// - `pattern`: pattern should be given by ClickHouse.
+        /// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+        pub fn ffi_regex_term_bitmap_with_filter(
+            index_path: &CxxString,
+            column_name: &CxxString,
+            pattern: &CxxString,
+            filter_query: &CxxString,
+        ) -> FFIVecU8Result;
+
+        /// Execute a bm25 query with filter on typed columns.
+        /// arguments:
+        /// - `index_path`: index directory.
+        /// - `sentence`: from ClickHouse TextSearch function.
+        /// - `top_k`: only return top k related results.
+        /// - `u8_alive_bitmap`: alive row ids given by u8 bitmap.
+        /// - `query_with_filter`:use alive_bitmap or not.
+        /// - `statistics`: for multi parts bm25 statistics info.
+        /// - `filter_query`: filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
+        pub fn ffi_bm25_search_with_filter(
+            index_path: &CxxString,
+            sentence: &CxxString,
+            column_names: &CxxVector<CxxString>,
+            top_k: u32,
+            u8_alive_bitmap: &CxxVector<u8>,
+            query_with_filter: bool,
+            enable_nlq: bool,
+            operator_or: bool,
+            statistics: &Statistics,
+            filter_query: &CxxString,
+        ) -> FFIVecRowIdWithScoreResult;
+
         /// Get doc freq for current part.
         /// arguments:
         /// - `index_path`: index directory.
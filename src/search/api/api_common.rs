@@ -1,9 +1,10 @@
 use crate::search::implements::free_index_reader;
 use crate::search::implements::get_indexed_doc_counts;
+use crate::search::implements::get_commit_payload;
 use crate::search::implements::load_index_reader;
 use crate::CXX_STRING_CONVERTER;
 use cxx::CxxString;
-use crate::ffi::{FFIBoolResult, FFIError, FFIU64Result};
+use crate::ffi::{FFIBoolResult, FFIError, FFIStringResult, FFIU64Result};
 use crate::utils::api_utils::ApiUtils;
 
 pub fn ffi_load_index_reader(index_path: &CxxString) -> FFIBoolResult {
@@ -72,3 +73,27 @@ pub fn ffi_get_indexed_doc_counts(index_path: &CxxString) -> FFIU64Result {
         }
     }
 }
+
+pub fn ffi_get_commit_payload(index_path: &CxxString) -> FFIStringResult {
+    static FUNC_NAME: &str = "ffi_get_commit_payload";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    match get_commit_payload(&index_path) {
+        Ok(result) => FFIStringResult {
+            result,
+            error: FFIError {
+                is_error: false,
+                message: String::new(),
+            },
+        },
+        Err(e) => {
+            ApiUtils::handle_error(FUNC_NAME, "Error getting commit payload", e.to_string())
+        }
+    }
+}
@@ -1,7 +1,11 @@
+use std::sync::Arc;
+use roaring::RoaringTreemap;
 use crate::{cxx_vector_converter, CXX_VECTOR_STRING_CONVERTER};
 use crate::ffi::{FFIError, FFIFieldTokenNumsResult, FFIU64Result, FFIVecDocWithFreqResult, FFIVecRowIdWithScoreResult, Statistics};
-use crate::search::implements::{bm25_natural_language_search, bm25_standard_search, get_doc_freq,
-                                get_total_num_docs, get_total_num_tokens};
+use crate::search::implements::{bm25_natural_language_search, bm25_natural_language_search_u64,
+                                bm25_natural_language_search_with_filter, bm25_standard_search, bm25_standard_search_u64,
+                                bm25_standard_search_with_filter, get_doc_freq, get_total_num_docs, get_total_num_tokens};
+use crate::search::utils::ConvertUtils;
 use crate::CXX_STRING_CONVERTER;
 use cxx::CxxString;
 use cxx::CxxVector;
@@ -101,6 +105,206 @@ pub fn ffi_bm25_search(
     }
 }
 
+pub fn ffi_bm25_search_u64(
+    index_path: &CxxString,
+    sentence: &CxxString,
+    column_names: &CxxVector<CxxString>,
+    top_k: u32,
+    u64_alive_bitmap: &CxxVector<u8>,
+    query_with_filter: bool,
+    enable_nlq: bool,
+    operator_or: bool,
+    statistics: &Statistics,
+) -> FFIVecRowIdWithScoreResult {
+    static FUNC_NAME: &str = "ffi_bm25_search_u64";
+    static FUNC_NAME_WITH_NLQ: &str = "ffi_bm25_natural_language_search_u64";
+    static FUNC_NAME_WITHOUT_NLQ: &str = "ffi_bm25_standard_search_u64";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let sentence: String = match CXX_STRING_CONVERTER.convert(sentence) {
+        Ok(q) => q,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'sentence'", e.to_string());
+        }
+    };
+
+    let column_names: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_names) {
+        Ok(ts) => ts,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_names'", e.to_string());
+        }
+    };
+
+    let u64_alive_bitmap: Vec<u8> = match cxx_vector_converter::<u8>().convert(u64_alive_bitmap) {
+        Ok(bitmap) => bitmap,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'u64_alive_bitmap'", e.to_string());
+        }
+    };
+    let u64_alive_bitmap: RoaringTreemap = match ConvertUtils::bytes_to_treemap(&u64_alive_bitmap) {
+        Ok(treemap) => treemap,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't deserialize 'u64_alive_bitmap'", e.to_string());
+        }
+    };
+
+    if enable_nlq {
+        match bm25_natural_language_search_u64(
+            &index_path,
+            &sentence,
+            &column_names,
+            top_k,
+            Arc::new(u64_alive_bitmap),
+            query_with_filter,
+            operator_or,
+            statistics,
+            false,
+        ) {
+            Ok(result) => FFIVecRowIdWithScoreResult {
+                result,
+                error: FFIError {
+                    is_error: false,
+                    message: String::new(),
+                },
+            },
+            Err(e) => {
+                ApiUtils::handle_error(FUNC_NAME_WITH_NLQ, "Error performing BM25 natural language search with statistics", e.to_string())
+            }
+        }
+    } else {
+        match bm25_standard_search_u64(
+            &index_path,
+            &sentence,
+            &column_names,
+            top_k,
+            Arc::new(u64_alive_bitmap),
+            query_with_filter,
+            operator_or,
+            statistics,
+            false,
+        ) {
+            Ok(result) => FFIVecRowIdWithScoreResult {
+                result,
+                error: FFIError {
+                    is_error: false,
+                    message: String::new(),
+                },
+            },
+            Err(e) => {
+                ApiUtils::handle_error(FUNC_NAME_WITHOUT_NLQ, "Error performing BM25 standard search with statistics", e.to_string())
+            }
+        }
+    }
+}
+
+pub fn ffi_bm25_search_with_filter(
+    index_path: &CxxString,
+    sentence: &CxxString,
+    column_names: &CxxVector<CxxString>,
+    top_k: u32,
+    u8_alive_bitmap: &CxxVector<u8>,
+    query_with_filter: bool,
+    enable_nlq: bool,
+    operator_or: bool,
+    statistics: &Statistics,
+    filter_query: &CxxString,
+) -> FFIVecRowIdWithScoreResult {
+    static FUNC_NAME: &str = "ffi_bm25_search_with_filter";
+    static FUNC_NAME_WITH_NLQ: &str = "ffi_bm25_natural_language_search_with_filter";
+    static FUNC_NAME_WITHOUT_NLQ: &str = "ffi_bm25_standard_search_with_filter";
+
+    let index_path: String = match CXX_STRING_CONVERTER.convert(index_path) {
+        Ok(path) => path,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'index_path'", e.to_string());
+        }
+    };
+
+    let sentence: String = match CXX_STRING_CONVERTER.convert(sentence) {
+        Ok(q) => q,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'sentence'", e.to_string());
+        }
+    };
+
+    let column_names: Vec<String> = match CXX_VECTOR_STRING_CONVERTER.convert(column_names) {
+        Ok(ts) => ts,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'column_names'", e.to_string());
+        }
+    };
+
+    let u8_alive_bitmap: Vec<u8> = match cxx_vector_converter::<u8>().convert(u8_alive_bitmap) {
+        Ok(bitmap) => bitmap,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'u8_alive_bitmap'", e.to_string());
+        }
+    };
+
+    let filter_query: String = match CXX_STRING_CONVERTER.convert(filter_query) {
+        Ok(filter) => filter,
+        Err(e) => {
+            return ApiUtils::handle_error(FUNC_NAME, "Can't convert 'filter_query'", e.to_string());
+        }
+    };
+
+    if enable_nlq {
+        match bm25_natural_language_search_with_filter(
+            &index_path,
+            &sentence,
+            &column_names,
+            top_k,
+            &u8_alive_bitmap,
+            query_with_filter,
+            operator_or,
+            statistics,
+            false,
+            &filter_query,
+        ) {
+            Ok(result) => FFIVecRowIdWithScoreResult {
+                result,
+                error: FFIError {
+                    is_error: false,
+                    message: String::new(),
+                },
+            },
+            Err(e) => {
+                ApiUtils::handle_error(FUNC_NAME_WITH_NLQ, "Error performing BM25 natural language search with filter", e.to_string())
+            }
+        }
+    } else {
+        match bm25_standard_search_with_filter(
+            &index_path,
+            &sentence,
+            &column_names,
+            top_k,
+            &u8_alive_bitmap,
+            query_with_filter,
+            operator_or,
+            statistics,
+            false,
+            &filter_query,
+        ) {
+            Ok(result) => FFIVecRowIdWithScoreResult {
+                result,
+                error: FFIError {
+                    is_error: false,
+                    message: String::new(),
+                },
+            },
+            Err(e) => {
+                ApiUtils::handle_error(FUNC_NAME_WITHOUT_NLQ, "Error performing BM25 standard search with filter", e.to_string())
+            }
+        }
+    }
+}
+
 pub fn ffi_get_doc_freq(index_path: &CxxString, sentence: &CxxString) -> FFIVecDocWithFreqResult {
     static FUNC_NAME: &str = "ffi_get_doc_freq";
 
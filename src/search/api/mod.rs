@@ -4,6 +4,8 @@ mod api_myscale;
 
 pub use api_myscale::{
     ffi_bm25_search,
+    ffi_bm25_search_u64,
+    ffi_bm25_search_with_filter,
     ffi_get_total_num_tokens,
     ffi_get_doc_freq,
     ffi_get_total_num_docs
@@ -12,12 +14,21 @@ pub use api_myscale::{
 pub use api_common::{
     ffi_free_index_reader,
     ffi_get_indexed_doc_counts,
+    ffi_get_commit_payload,
     ffi_load_index_reader
 };
 
 pub use api_clickhouse::{
     ffi_query_sentence_bitmap,
+    ffi_query_sentence_bitmap_u64,
+    ffi_query_sentence_bitmap_with_filter,
     ffi_query_term_bitmap,
+    ffi_query_term_bitmap_u64,
+    ffi_query_term_bitmap_with_filter,
     ffi_query_terms_bitmap,
-    ffi_regex_term_bitmap
+    ffi_query_terms_bitmap_u64,
+    ffi_query_terms_bitmap_with_filter,
+    ffi_regex_term_bitmap,
+    ffi_regex_term_bitmap_u64,
+    ffi_regex_term_bitmap_with_filter
 };
\ No newline at end of file
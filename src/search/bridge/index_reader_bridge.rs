@@ -6,6 +6,8 @@ pub struct IndexReaderBridge {
     pub path: String,
     pub index: Index,
     pub reader: IndexReader,
+    /// Columns dropped by `drop_column`, they can't be queried.
+    pub dropped_columns: Vec<String>,
 }
 
 impl Drop for IndexReaderBridge {
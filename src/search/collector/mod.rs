@@ -1,3 +1,4 @@
 pub mod row_id_bitmap_collector;
+pub mod row_id_treemap_collector;
 mod test;
 pub mod top_dos_with_bitmap_collector;
@@ -5,6 +5,9 @@ use tantivy::collector::{Collector, SegmentCollector};
 use tantivy::columnar::Column;
 use tantivy::{Score, SegmentReader};
 
+use crate::{common::constants::LOG_CALLBACK, WARNING};
+use crate::logger::logger_bridge::TantivySearchLogger;
+
 /*
     Struct visualization.
 
@@ -73,6 +76,8 @@ impl Collector for RowIdRoaringCollector {
 pub struct RowIdRoaringSegmentCollector {
     row_id_reader: Column,
     row_id_roaring_bitmap: Arc<RoaringBitmap>,
+    // Row ids exceed u32 can't be stored in `RoaringBitmap`, use `RowIdTreemapCollector` for them.
+    overflow_row_ids: u64,
 }
 
 impl RowIdRoaringSegmentCollector {
@@ -80,6 +85,7 @@ impl RowIdRoaringSegmentCollector {
         RowIdRoaringSegmentCollector {
             row_id_reader,
             row_id_roaring_bitmap: Arc::new(RoaringBitmap::new()),
+            overflow_row_ids: 0,
         }
     }
 }
@@ -88,6 +94,7 @@ impl SegmentCollector for RowIdRoaringSegmentCollector {
     type Fruit = Arc<RoaringBitmap>;
 
     fn collect(&mut self, doc: u32, _score: Score) {
+        let mut overflow_row_ids: u64 = 0;
         let row_ids: Vec<u32> = self
             .row_id_reader
             .values_for_doc(doc)
@@ -95,10 +102,12 @@ impl SegmentCollector for RowIdRoaringSegmentCollector {
                 if row_id <= u32::MAX as u64 {
                     Some(row_id as u32)
                 } else {
+                    overflow_row_ids += 1;
                     None
                 }
             })
             .collect();
+        self.overflow_row_ids += overflow_row_ids;
 
         match Arc::get_mut(&mut self.row_id_roaring_bitmap) {
             Some(bitmap) => bitmap.extend(row_ids),
@@ -107,6 +116,12 @@ impl SegmentCollector for RowIdRoaringSegmentCollector {
     }
 
     fn harvest(self) -> <Self as SegmentCollector>::Fruit {
+        if self.overflow_row_ids != 0 {
+            WARNING!(
+                "RowIdRoaringSegmentCollector dropped {} row_ids exceed u32::MAX, u64 query api should be used.",
+                self.overflow_row_ids
+            );
+        }
         // Arc::clone(&self.row_id_roaring_bitmap)
         self.row_id_roaring_bitmap
     }
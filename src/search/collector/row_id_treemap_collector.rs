@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use roaring::RoaringTreemap;
+use tantivy::collector::{Collector, SegmentCollector};
+use tantivy::columnar::Column;
+use tantivy::{Score, SegmentReader};
+
+/*
+    Same as `RowIdRoaringCollector`, but row_ids are collected in `RoaringTreemap`,
+    so row_ids exceed u32 won't be dropped.
+
+    +-----------------------+       +------------------------------+
+    |                       |       |                              |
+    | RowIdTreemapCollector |       | RowIdTreemapSegmentCollector |
+    |                       |       |                              |
+    +-----------------------+       +------------------------------+
+          | implements                    | implements
+          v                               v
+    +------------+ [Trait]           +------------------+ [Trait]
+    |  Collector + <-.-.-.-.-.-.-.-. + SegmentCollector |
+    +------------+     Child Type    +------------------+
+*/
+
+pub struct RowIdTreemapCollector {
+    pub row_id_field: String,
+}
+
+impl RowIdTreemapCollector {
+    pub fn with_field(row_id_field: String) -> RowIdTreemapCollector {
+        RowIdTreemapCollector { row_id_field }
+    }
+}
+
+impl Collector for RowIdTreemapCollector {
+    type Fruit = Arc<RoaringTreemap>;
+    type Child = RowIdTreemapSegmentCollector;
+
+    // Create `RowIdTreemapSegmentCollector` for each segment.
+    fn for_segment(
+        &self,
+        _segment_local_id: u32,
+        segment_reader: &SegmentReader,
+    ) -> tantivy::Result<Self::Child> {
+        let row_id_reader = segment_reader.fast_fields().u64(&self.row_id_field)?;
+        Ok(RowIdTreemapSegmentCollector::new(row_id_reader))
+    }
+
+    fn requires_scoring(&self) -> bool {
+        false
+    }
+
+    // Merge fruits thorough each segment.
+    fn merge_fruits(
+        &self,
+        segment_row_ids: Vec<<Self::Child as SegmentCollector>::Fruit>,
+    ) -> tantivy::Result<Self::Fruit> {
+        let mut row_id_treemap = RoaringTreemap::new();
+        for segment_row_id_treemap in segment_row_ids {
+            match Arc::try_unwrap(segment_row_id_treemap) {
+                Ok(treemap) => row_id_treemap |= treemap,
+                Err(arc_treemap) => {
+                    // for multi reference, need call clone()
+                    row_id_treemap |= arc_treemap.as_ref().clone();
+                }
+            }
+        }
+        Ok(Arc::new(row_id_treemap))
+    }
+}
+
+pub struct RowIdTreemapSegmentCollector {
+    row_id_reader: Column,
+    row_id_treemap: RoaringTreemap,
+}
+
+impl RowIdTreemapSegmentCollector {
+    pub fn new(row_id_reader: Column) -> Self {
+        RowIdTreemapSegmentCollector {
+            row_id_reader,
+            row_id_treemap: RoaringTreemap::new(),
+        }
+    }
+}
+
+impl SegmentCollector for RowIdTreemapSegmentCollector {
+    type Fruit = Arc<RoaringTreemap>;
+
+    fn collect(&mut self, doc: u32, _score: Score) {
+        self.row_id_treemap.extend(self.row_id_reader.values_for_doc(doc));
+    }
+
+    fn harvest(self) -> <Self as SegmentCollector>::Fruit {
+        Arc::new(self.row_id_treemap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tantivy::query::QueryParser;
+    use tantivy::schema::{Schema, FAST, INDEXED, TEXT};
+    use tantivy::{doc, Index};
+
+    #[test]
+    fn test_rowid_treemap_collector_merge_fruits() {
+        let collector = RowIdTreemapCollector::with_field("row_id".to_string());
+        let treemap_1 = Arc::new(RoaringTreemap::from_iter(vec![1u64, 2, u64::MAX - 1]));
+        let treemap_2 = Arc::new(RoaringTreemap::from_iter(vec![2u64, u32::MAX as u64 + 1]));
+        let merged = collector.merge_fruits(vec![treemap_1, treemap_2]).unwrap();
+        let expected: Vec<u64> = vec![1, 2, u32::MAX as u64 + 1, u64::MAX - 1];
+        assert_eq!(merged.iter().collect::<Vec<u64>>(), expected);
+    }
+
+    #[test]
+    fn test_rowid_treemap_collector_keep_large_row_ids() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        let text = schema_builder.add_text_field("text", TEXT);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut writer = index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        writer.add_document(doc!(row_id => 3u64, text => "ancient empires")).unwrap();
+        writer.add_document(doc!(row_id => u32::MAX as u64 + 7, text => "ancient philosophies")).unwrap();
+        writer.add_document(doc!(row_id => u32::MAX as u64 + 8, text => "modern dilemmas")).unwrap();
+        writer.commit().unwrap();
+
+        let searcher = index.reader().unwrap().searcher();
+        let query = QueryParser::for_index(&index, vec![text]).parse_query("ancient").unwrap();
+        let treemap = searcher
+            .search(&query, &RowIdTreemapCollector::with_field("row_id".to_string()))
+            .unwrap();
+        assert_eq!(treemap.iter().collect::<Vec<u64>>(), vec![3, u32::MAX as u64 + 7]);
+    }
+}
@@ -2,7 +2,8 @@
 mod tests {
     use crate::search::collector::top_dos_with_bitmap_collector::TopDocsWithFilter;
 
-    use roaring::RoaringBitmap;
+    use std::sync::Arc;
+    use roaring::{RoaringBitmap, RoaringTreemap};
     use tantivy::merge_policy::LogMergePolicy;
     use tantivy::query::{Query, QueryParser};
     use tantivy::schema::{Field, Schema, FAST, INDEXED, STORED, TEXT};
@@ -105,6 +106,37 @@ mod tests {
         // assert_eq!(searched_results.len(), 1);
     }
 
+    #[test]
+    fn test_search_with_alive_filter() {
+        let temp_path = TempDir::new().expect("Can't create temp path");
+        let temp_path_str = temp_path.path().to_str().unwrap();
+        let (index_reader, _) = get_reader_and_writer_from_index_path(temp_path_str);
+        let (text_field, _, text_query, index_searcher) =
+            extract_from_index_reader(index_reader.clone());
+
+        let top_docs_collector = TopDocsWithFilter::with_limit(10)
+            .with_searcher(index_searcher.clone())
+            .with_text_fields(vec![text_field]);
+
+        // u8 bitmap, only row_id 4 is alive.
+        let searched_results = index_searcher
+            .search(&text_query, &top_docs_collector.with_alive_u8(vec![16]))
+            .expect("Can't execute search.");
+        assert_eq!(searched_results.len(), 1);
+        assert_eq!(searched_results[0].row_id, 4);
+
+        // treemap, only row_id 0 is alive.
+        let top_docs_collector = TopDocsWithFilter::with_limit(10)
+            .with_searcher(index_searcher.clone())
+            .with_text_fields(vec![text_field])
+            .with_alive_treemap(Arc::new(RoaringTreemap::from_iter(vec![0u64, u32::MAX as u64 + 4])));
+        let searched_results = index_searcher
+            .search(&text_query, &top_docs_collector)
+            .expect("Can't execute search.");
+        assert_eq!(searched_results.len(), 1);
+        assert_eq!(searched_results[0].row_id, 0);
+    }
+
     #[test]
     fn test_search_after_delete() {
         let temp_path = TempDir::new().expect("Can't create temp path");
@@ -1,6 +1,9 @@
 use std::collections::BinaryHeap;
+use std::sync::Arc;
 use std::{cmp, fmt};
 
+use roaring::RoaringTreemap;
+
 use tantivy::collector::{Collector, SegmentCollector};
 use tantivy::query::Weight;
 use tantivy::schema::Field;
@@ -26,6 +29,7 @@ static INITIAL_HEAP_SIZE: usize = 1000;
 pub struct TopDocsWithFilter {
     pub limit: usize,
     pub row_id_u8: Option<Vec<u8>>,
+    pub row_id_treemap: Option<Arc<RoaringTreemap>>,
     pub searcher: Option<Searcher>,
     pub text_fields: Option<Vec<Field>>,
     pub need_text: bool,
@@ -39,6 +43,7 @@ impl TopDocsWithFilter {
         Self {
             limit,
             row_id_u8: None,
+            row_id_treemap: None,
             searcher: None,
             text_fields: None,
             need_text: false,
@@ -52,6 +57,12 @@ impl TopDocsWithFilter {
         self
     }
 
+    // `row_id_treemap` is used to mark alive u64 row_ids, it takes precedence over `row_id_u8`.
+    pub fn with_alive_treemap(mut self, row_id_treemap: Arc<RoaringTreemap>) -> TopDocsWithFilter {
+        self.row_id_treemap = Some(row_id_treemap);
+        self
+    }
+
     // `searcher` is used to search origin text content.
     pub fn with_searcher(mut self, searcher: Searcher) -> TopDocsWithFilter {
         self.searcher = Some(searcher.clone());
@@ -99,6 +110,18 @@ impl TopDocsWithFilter {
         Ok(top_collector.into_sorted_vec())
     }
 
+    #[inline]
+    fn is_row_id_alive(&self, row_id: u64) -> bool {
+        if let Some(row_id_treemap) = &self.row_id_treemap {
+            return row_id_treemap.contains(row_id);
+        }
+        match &self.row_id_u8 {
+            // u8 bitmap can't mark row_ids exceed u32.
+            Some(row_id_u8) => row_id <= u32::MAX as u64 && ConvertUtils::is_row_id_exist(row_id as u32, row_id_u8),
+            None => true,
+        }
+    }
+
     #[inline]
     fn extract_doc_text(&self, doc: DocId, segment_ord: SegmentOrdinal) -> Vec<String> {
         let mut doc_texts: Vec<String> = vec![];
@@ -130,9 +153,10 @@ impl fmt::Debug for TopDocsWithFilter {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
-            "TopDocsWithFilter(limit:{}, row_id_u8_size:{}, text_fields_is_some:{}, searcher_is_some:{}, need_text:{}, initial_heap_size:{})",
+            "TopDocsWithFilter(limit:{}, row_id_u8_size:{}, row_id_treemap_size:{}, text_fields_is_some:{}, searcher_is_some:{}, need_text:{}, initial_heap_size:{})",
             self.limit,
             if self.row_id_u8.is_some() {self.row_id_u8.clone().unwrap().len()} else {0},
+            self.row_id_treemap.as_ref().map(|treemap| treemap.len()).unwrap_or(0),
             self.text_fields.is_some(),
             self.searcher.is_some(),
             self.need_text,
@@ -185,12 +209,7 @@ impl Collector for TopDocsWithFilter {
             let mut threshold = Score::MIN;
             weight.for_each_pruning(threshold, reader, &mut |doc, score| {
                 let row_id = row_id_field_reader.get_val(doc);
-                if self.row_id_u8.is_some()
-                    && !ConvertUtils::is_row_id_exist(
-                        row_id as u32,
-                        &self.row_id_u8.as_ref().unwrap(),
-                    )
-                {
+                if !self.is_row_id_alive(row_id) {
                     return threshold;
                 }
                 if alive_bitset.is_deleted(doc) {
@@ -217,12 +236,7 @@ impl Collector for TopDocsWithFilter {
         } else {
             weight.for_each_pruning(Score::MIN, reader, &mut |doc, score| {
                 let row_id = row_id_field_reader.get_val(doc);
-                if self.row_id_u8.is_some()
-                    && !ConvertUtils::is_row_id_exist(
-                        row_id as u32,
-                        &self.row_id_u8.as_ref().unwrap(),
-                    )
-                {
+                if !self.is_row_id_alive(row_id) {
                     return Score::MIN;
                 }
                 let heap_item = RowIdWithScore {
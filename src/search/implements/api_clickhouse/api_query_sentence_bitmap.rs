@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use crate::common::errors::TantivySearchError;
 use crate::search::implements::SearchUtils;
 use crate::search::implements::strategy::QueryExecutor;
@@ -11,23 +11,51 @@ pub fn query_sentence_bitmap(
     column_name: &str,
     sentence: &str,
 ) -> Result<Vec<u8>, TantivySearchError> {
-    static FUNC_NAME: &str = "query_sentence_bitmap";
+    query_sentence_bitmap_with_filter(index_path, column_name, sentence, "")
+}
+
+/// Same as `query_sentence_bitmap`, `filter_query` filters typed columns in the same query,
+/// such as `price:[0 TO 100} AND on_sale:true`, empty means no filter.
+pub fn query_sentence_bitmap_with_filter(
+    index_path: &str,
+    column_name: &str,
+    sentence: &str,
+    filter_query: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "query_sentence_bitmap_with_filter";
     let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
 
-    let strategy: SentenceQueryStrategy<'_> = SentenceQueryStrategy {column_name, sentence};
+    let strategy: SentenceQueryStrategy<'_> = SentenceQueryStrategy {column_name, sentence, filter_query};
     let executor: QueryExecutor<'_, Arc<RoaringBitmap>> = QueryExecutor::new(&strategy);
 
-    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge.reader.searcher())?;
+    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
     let row_ids: Vec<u32> = bitmap.iter().collect();
     Ok(ConvertUtils::row_ids_to_u8_bitmap(&row_ids))
 }
 
+/// Same as `query_sentence_bitmap`, row_ids are returned as serialized `RoaringTreemap`, so u64 row_ids are kept.
+pub fn query_sentence_bitmap_u64(
+    index_path: &str,
+    column_name: &str,
+    sentence: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "query_sentence_bitmap_u64";
+    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
+
+    let strategy: SentenceQueryStrategy<'_> = SentenceQueryStrategy {column_name, sentence, filter_query: ""};
+    let executor: QueryExecutor<'_, Arc<RoaringTreemap>> = QueryExecutor::new(&strategy);
+
+    let treemap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
+    SearchUtils::treemap_to_bytes(FUNC_NAME, &treemap)
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
     use crate::search::implements::api_common::load_index_reader;
-    use crate::search::implements::query_sentence_bitmap;
+    use crate::search::implements::{query_sentence_bitmap, query_sentence_bitmap_u64, query_sentence_bitmap_with_filter};
+    use crate::search::utils::ConvertUtils;
 
     #[test]
     fn normal_test() {
@@ -46,4 +74,57 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert_eq!(res[0], 18);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn normal_test_u64() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        let res = query_sentence_bitmap_u64(tmp_dir, "col2", "nature moral").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![1, 4]);
+    }
+
+    #[test]
+    fn normal_test_with_filter() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_typed_columns_docs(tmp_dir);
+
+        // row 0 and row 4 match `ancient`.
+        assert_eq!(query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "").unwrap(), vec![17]);
+        assert_eq!(query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "price:[0 TO 100}").unwrap(), vec![17]);
+        assert_eq!(query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "price:[0 TO 20]").unwrap(), vec![1]);
+        assert_eq!(query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "stock:[5 TO *]").unwrap(), vec![16]);
+        assert_eq!(
+            query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "created:[2023-11-16T00:00:00Z TO *]").unwrap(),
+            vec![16]
+        );
+        assert!(query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "on_sale:false").unwrap().is_empty());
+        // row 3 has null price.
+        assert!(query_sentence_bitmap_with_filter(tmp_dir, "title", "military", "price:[0 TO 100]").unwrap().is_empty());
+        // unknown column in filter
+        assert!(query_sentence_bitmap_with_filter(tmp_dir, "title", "ancient", "weight:[0 TO 1]").is_err());
+    }
+
+    #[test]
+    fn normal_test_json_path() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_json_column_docs(tmp_dir);
+
+        assert_eq!(query_sentence_bitmap(tmp_dir, "attrs.color", "blue red").unwrap(), vec![7]);
+        assert_eq!(query_sentence_bitmap(tmp_dir, "attrs.material.name", "red cotton").unwrap(), vec![2]);
+        let res = query_sentence_bitmap_u64(tmp_dir, "attrs.size", "red large").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![0, 4]);
+    }
+}
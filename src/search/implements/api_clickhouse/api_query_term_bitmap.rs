@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use crate::common::errors::TantivySearchError;
 use crate::search::implements::SearchUtils;
 use crate::search::implements::strategy::QueryExecutor;
@@ -11,23 +11,53 @@ pub fn query_term_bitmap(
     column_name: &str,
     term: &str,
 ) -> Result<Vec<u8>, TantivySearchError> {
-    static FUNC_NAME: &str = "query_term_bitmap";
+    query_term_bitmap_with_filter(index_path, column_name, term, "")
+}
+
+/// Same as `query_term_bitmap`, `filter_query` filters typed columns in the same query,
+/// such as `price:[0 TO 100} AND on_sale:true`, empty means no filter.
+pub fn query_term_bitmap_with_filter(
+    index_path: &str,
+    column_name: &str,
+    term: &str,
+    filter_query: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "query_term_bitmap_with_filter";
     let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
 
-    let strategy: SingleTermQueryStrategy<'_> = SingleTermQueryStrategy { column_name, term };
+    let strategy: SingleTermQueryStrategy<'_> = SingleTermQueryStrategy { column_name, term, filter_query };
     let executor: QueryExecutor<'_, Arc<RoaringBitmap>> = QueryExecutor::new(&strategy);
 
-    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge.reader.searcher())?;
+    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
     let row_ids: Vec<u32> = bitmap.iter().collect();
     Ok(ConvertUtils::row_ids_to_u8_bitmap(&row_ids))
 }
 
+/// Same as `query_term_bitmap`, row_ids are returned as serialized `RoaringTreemap`, so u64 row_ids are kept.
+pub fn query_term_bitmap_u64(
+    index_path: &str,
+    column_name: &str,
+    term: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "query_term_bitmap_u64";
+    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
+
+    let strategy: SingleTermQueryStrategy<'_> = SingleTermQueryStrategy { column_name, term, filter_query: "" };
+    let executor: QueryExecutor<'_, Arc<RoaringTreemap>> = QueryExecutor::new(&strategy);
+
+    let treemap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
+    SearchUtils::treemap_to_bytes(FUNC_NAME, &treemap)
+}
+
 #[cfg(test)]
 mod tests {
+    use tantivy::Document;
+    use tantivy::schema::Field;
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
     use crate::search::implements::api_common::load_index_reader;
-    use crate::search::implements::query_term_bitmap;
+    use crate::search::implements::{query_term_bitmap, query_term_bitmap_u64, query_term_bitmap_with_filter};
+    use crate::search::utils::ConvertUtils;
 
     #[test]
     fn normal_test() {
@@ -46,4 +76,77 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert_eq!(res[0], 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn normal_test_u64() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        let res = query_term_bitmap_u64(tmp_dir, "col1", "Ancient").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![0, 4]);
+    }
+
+    #[test]
+    fn boundary_test_row_ids_exceed_u32() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let bridge = SinglePartTest::create_index_and_get_writer_bridge(tmp_dir, false);
+        for row_id in [3u64, u32::MAX as u64 + 1, u64::MAX - 1] {
+            let mut doc = Document::default();
+            doc.add_u64(Field::from_field_id(0), row_id);
+            doc.add_text(Field::from_field_id(1), "Ancient empires");
+            assert!(bridge.add_document(doc).is_ok());
+        }
+        assert!(bridge.commit().is_ok());
+        assert!(bridge.wait_merging_threads().is_ok());
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        // u32 bitmap can only keep row_id 3.
+        let res = query_term_bitmap(tmp_dir, "col1", "Ancient").unwrap();
+        assert_eq!(ConvertUtils::u8_bitmap_to_row_ids(&res), vec![3]);
+
+        let res = query_term_bitmap_u64(tmp_dir, "col1", "Ancient").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![3, u32::MAX as u64 + 1, u64::MAX - 1]);
+    }
+
+    #[test]
+    fn normal_test_json_path() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_json_column_docs(tmp_dir);
+
+        assert_eq!(query_term_bitmap(tmp_dir, "attrs.color", "Red").unwrap(), vec![5]);
+        assert_eq!(query_term_bitmap(tmp_dir, "attrs.size", "red").unwrap(), vec![16]);
+        assert_eq!(query_term_bitmap(tmp_dir, "attrs.material.name", "cotton").unwrap(), vec![2]);
+        assert_eq!(query_term_bitmap(tmp_dir, "attrs.tags", "sale").unwrap(), vec![1]);
+        assert!(query_term_bitmap(tmp_dir, "attrs.weight", "red").unwrap().is_empty());
+        // Path can only be used on json column.
+        assert!(query_term_bitmap(tmp_dir, "title.color", "red").is_err());
+    }
+
+    #[test]
+    fn normal_test_with_filter() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_typed_columns_docs(tmp_dir);
+
+        // row 0 and row 4 match `ancient`.
+        assert_eq!(query_term_bitmap_with_filter(tmp_dir, "title", "ancient", "").unwrap(), vec![17]);
+        assert_eq!(query_term_bitmap_with_filter(tmp_dir, "title", "ancient", "price:[0 TO 20]").unwrap(), vec![1]);
+        assert_eq!(query_term_bitmap_with_filter(tmp_dir, "title", "ancient", "stock:[5 TO *]").unwrap(), vec![16]);
+        assert!(query_term_bitmap_with_filter(tmp_dir, "title", "ancient", "on_sale:false").unwrap().is_empty());
+        assert!(query_term_bitmap_with_filter(tmp_dir, "title", "ancient", "weight:[0 TO 1]").is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use std::sync::Arc;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use crate::common::errors::TantivySearchError;
 use crate::search::implements::SearchUtils;
 use crate::search::implements::strategy::QueryExecutor;
@@ -11,23 +11,51 @@ pub fn query_terms_bitmap(
     column_name: &str,
     terms: &Vec<String>,
 ) -> Result<Vec<u8>, TantivySearchError> {
-    static FUNC_NAME: &str = "query_terms_bitmap";
+    query_terms_bitmap_with_filter(index_path, column_name, terms, "")
+}
+
+/// Same as `query_terms_bitmap`, `filter_query` filters typed columns in the same query,
+/// such as `price:[0 TO 100} AND on_sale:true`, empty means no filter.
+pub fn query_terms_bitmap_with_filter(
+    index_path: &str,
+    column_name: &str,
+    terms: &Vec<String>,
+    filter_query: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "query_terms_bitmap_with_filter";
     let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
 
-    let strategy: TermSetQueryStrategy<'_> = TermSetQueryStrategy { column_name, terms };
+    let strategy: TermSetQueryStrategy<'_> = TermSetQueryStrategy { column_name, terms, filter_query };
     let executor: QueryExecutor<'_, Arc<RoaringBitmap>> = QueryExecutor::new(&strategy);
 
-    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge.reader.searcher())?;
+    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
     let row_ids: Vec<u32> = bitmap.iter().collect();
     Ok(ConvertUtils::row_ids_to_u8_bitmap(&row_ids))
 }
 
+/// Same as `query_terms_bitmap`, row_ids are returned as serialized `RoaringTreemap`, so u64 row_ids are kept.
+pub fn query_terms_bitmap_u64(
+    index_path: &str,
+    column_name: &str,
+    terms: &Vec<String>,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "query_terms_bitmap_u64";
+    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
+
+    let strategy: TermSetQueryStrategy<'_> = TermSetQueryStrategy { column_name, terms, filter_query: "" };
+    let executor: QueryExecutor<'_, Arc<RoaringTreemap>> = QueryExecutor::new(&strategy);
+
+    let treemap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
+    SearchUtils::treemap_to_bytes(FUNC_NAME, &treemap)
+}
+
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
     use crate::search::implements::api_common::load_index_reader;
-    use crate::search::implements::query_terms_bitmap;
+    use crate::search::implements::{query_terms_bitmap, query_terms_bitmap_u64, query_terms_bitmap_with_filter};
+    use crate::search::utils::ConvertUtils;
 
     #[test]
     fn normal_test() {
@@ -50,4 +78,49 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert_eq!(res[0], 18);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn normal_test_u64() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        let res = query_terms_bitmap_u64(
+            tmp_dir, "col1", &vec!["ancient".to_string(), "reflect".to_string()]
+        ).unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn normal_test_json_path() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_json_column_docs(tmp_dir);
+
+        let res = query_terms_bitmap(tmp_dir, "attrs.color", &vec!["blue".to_string()]).unwrap();
+        assert_eq!(res, vec![6]);
+        let res = query_terms_bitmap(tmp_dir, "attrs.color", &vec!["blue".to_string(), "red".to_string()]).unwrap();
+        assert_eq!(res, vec![7]);
+    }
+
+    #[test]
+    fn normal_test_with_filter() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_typed_columns_docs(tmp_dir);
+
+        let terms: Vec<String> = vec!["ancient".to_string(), "military".to_string()];
+        // row 0 and row 4 match `ancient`, rows matching `military` have no price in range.
+        assert_eq!(query_terms_bitmap_with_filter(tmp_dir, "title", &terms, "price:[0 TO 100]").unwrap(), vec![17]);
+        assert_eq!(query_terms_bitmap_with_filter(tmp_dir, "title", &terms, "price:[0 TO 20]").unwrap(), vec![1]);
+        assert!(query_terms_bitmap_with_filter(tmp_dir, "title", &terms, "weight:[0 TO 1]").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 use std::sync::Arc;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use crate::common::errors::TantivySearchError;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
@@ -12,49 +12,82 @@ use crate::tokenizer::ingredient::Config;
 use crate::tokenizer::parser::TokenizerWrapper;
 use crate::utils::index_utils::IndexUtils;
 
-pub fn regex_term_bitmap(
-    index_path: &str,
-    column_name: &str,
-    pattern: &str,
-) -> Result<Vec<u8>, TantivySearchError> {
-    static FUNC_NAME: &str = "query_terms_bitmap";
-    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
-
+// Keep pattern case same with the column tokenizer.
+fn handle_pattern(func_name: &str, index_path: &str, column_name: &str, pattern: &str) -> Result<String, TantivySearchError> {
     let config: Config =
         IndexUtils::load_tokenizer_config(Path::new(index_path)).map_err(|e| {
-            ERROR!(function: FUNC_NAME, "{}", e);
+            ERROR!(function: func_name, "{}", e);
             TantivySearchError::IndexUtilsError(e)
         })?;
 
     let lower_case = config
         .get_columns()
         .get(column_name)
-        .map(|column|{
-            let tokenizer = column.get_tokenizer();
-            TokenizerWrapper::new(tokenizer).is_lower_case()
-        }).unwrap_or(true);
+        .and_then(|column| column.get_tokenizer())
+        .map(|tokenizer| TokenizerWrapper::new(tokenizer).is_lower_case())
+        .unwrap_or(true);
 
     let mut pattern_handled = pattern.to_string();
     if lower_case {
         pattern_handled.make_ascii_lowercase(); // Keep same with Lower Case Tokenizer.
     }
+    Ok(pattern_handled)
+}
+
+pub fn regex_term_bitmap(
+    index_path: &str,
+    column_name: &str,
+    pattern: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    regex_term_bitmap_with_filter(index_path, column_name, pattern, "")
+}
+
+/// Same as `regex_term_bitmap`, `filter_query` filters typed columns in the same query,
+/// such as `price:[0 TO 100} AND on_sale:true`, empty means no filter.
+pub fn regex_term_bitmap_with_filter(
+    index_path: &str,
+    column_name: &str,
+    pattern: &str,
+    filter_query: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "regex_term_bitmap_with_filter";
+    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
+    let pattern_handled = handle_pattern(FUNC_NAME, index_path, column_name, pattern)?;
 
-    let strategy: RegexQueryStrategy<'_> = RegexQueryStrategy {column_name, pattern: &pattern_handled};
+    let strategy: RegexQueryStrategy<'_> = RegexQueryStrategy {column_name, pattern: &pattern_handled, filter_query};
     let executor: QueryExecutor<'_, Arc<RoaringBitmap>> = QueryExecutor::new(&strategy);
 
-    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge.reader.searcher())?;
+    let bitmap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
     let row_ids: Vec<u32> = bitmap.iter().collect();
     Ok(ConvertUtils::row_ids_to_u8_bitmap(&row_ids))
 
 }
 
+/// Same as `regex_term_bitmap`, row_ids are returned as serialized `RoaringTreemap`, so u64 row_ids are kept.
+pub fn regex_term_bitmap_u64(
+    index_path: &str,
+    column_name: &str,
+    pattern: &str,
+) -> Result<Vec<u8>, TantivySearchError> {
+    static FUNC_NAME: &str = "regex_term_bitmap_u64";
+    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
+    let pattern_handled = handle_pattern(FUNC_NAME, index_path, column_name, pattern)?;
+
+    let strategy: RegexQueryStrategy<'_> = RegexQueryStrategy {column_name, pattern: &pattern_handled, filter_query: ""};
+    let executor: QueryExecutor<'_, Arc<RoaringTreemap>> = QueryExecutor::new(&strategy);
+
+    let treemap = SearchUtils::execute_query(FUNC_NAME, executor, &bridge)?;
+    SearchUtils::treemap_to_bytes(FUNC_NAME, &treemap)
+}
+
 
 #[cfg(test)]
 mod tests {
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
     use crate::search::implements::api_common::load_index_reader;
-    use crate::search::implements::regex_term_bitmap;
+    use crate::search::implements::{regex_term_bitmap, regex_term_bitmap_u64, regex_term_bitmap_with_filter};
+    use crate::search::utils::ConvertUtils;
 
     #[test]
     fn normal_test() {
@@ -81,4 +114,45 @@ mod tests {
         assert_eq!(res.len(), 1);
         assert_eq!(res[0], 4);
     }
+
+    #[test]
+    fn normal_test_u64() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        let res = regex_term_bitmap_u64(tmp_dir, "col1", "An%ent").unwrap();
+        let row_ids: Vec<u64> = ConvertUtils::bytes_to_treemap(&res).unwrap().iter().collect();
+        assert_eq!(row_ids, vec![0, 4]);
+    }
+
+    #[test]
+    fn boundary_test_json_column() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_json_column_docs(tmp_dir);
+
+        assert!(regex_term_bitmap(tmp_dir, "attrs", "%red%").is_err());
+        assert!(regex_term_bitmap(tmp_dir, "attrs.color", "%red%").is_err());
+    }
+
+    #[test]
+    fn normal_test_with_filter() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_typed_columns_docs(tmp_dir);
+
+        // row 0 and row 4 match `ancient`.
+        assert_eq!(regex_term_bitmap_with_filter(tmp_dir, "title", "%ncien%", "").unwrap(), vec![17]);
+        assert_eq!(regex_term_bitmap_with_filter(tmp_dir, "title", "%ncien%", "price:[0 TO 20]").unwrap(), vec![1]);
+        assert_eq!(regex_term_bitmap_with_filter(tmp_dir, "title", "%ncien%", "stock:[5 TO *]").unwrap(), vec![16]);
+        assert!(regex_term_bitmap_with_filter(tmp_dir, "title", "%ncien%", "weight:[0 TO 1]").is_err());
+    }
 }
@@ -3,7 +3,7 @@ mod api_query_terms_bitmap;
 mod api_query_sentence_bitmap;
 mod api_regex_term_bitmap;
 
-pub use api_query_term_bitmap::query_term_bitmap;
-pub use api_query_terms_bitmap::query_terms_bitmap;
-pub use api_query_sentence_bitmap::query_sentence_bitmap;
-pub use api_regex_term_bitmap::regex_term_bitmap;
\ No newline at end of file
+pub use api_query_term_bitmap::{query_term_bitmap, query_term_bitmap_u64, query_term_bitmap_with_filter};
+pub use api_query_terms_bitmap::{query_terms_bitmap, query_terms_bitmap_u64, query_terms_bitmap_with_filter};
+pub use api_query_sentence_bitmap::{query_sentence_bitmap, query_sentence_bitmap_u64, query_sentence_bitmap_with_filter};
+pub use api_regex_term_bitmap::{regex_term_bitmap, regex_term_bitmap_u64, regex_term_bitmap_with_filter};
\ No newline at end of file
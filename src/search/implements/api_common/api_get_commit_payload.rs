@@ -0,0 +1,43 @@
+use crate::common::errors::TantivySearchError;
+use crate::{common::constants::LOG_CALLBACK, ERROR};
+use crate::logger::logger_bridge::TantivySearchLogger;
+use crate::search::implements::SearchUtils;
+
+/// Read the payload stored by the last commit from disk, empty string means no payload.
+pub fn get_commit_payload(index_path: &str) -> Result<String, TantivySearchError> {
+    static FUNC_NAME: &str = "get_commit_payload";
+    let bridge = SearchUtils::get_index_reader_bridge(FUNC_NAME, index_path)?;
+
+    let metas = bridge.index.load_metas().map_err(|e| {
+        ERROR!(function: FUNC_NAME, "Failed to load index metas: {}", e.to_string());
+        TantivySearchError::TantivyError(e)
+    })?;
+    Ok(metas.payload.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+    use crate::common::{SinglePartTest, TEST_MUTEX};
+    use crate::index::implements::commit_index_with_payload;
+    use crate::search::implements::api_common::load_index_reader;
+    use crate::search::implements::get_commit_payload;
+
+    #[test]
+    fn normal_test() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_writer_bridge(tmp_dir, true, false, false);
+        assert!(commit_index_with_payload(tmp_dir, "all_1_1_0_5").is_ok());
+
+        assert!(get_commit_payload(tmp_dir).is_err());
+        assert!(load_index_reader(tmp_dir).unwrap());
+        assert_eq!(get_commit_payload(tmp_dir).unwrap(), "all_1_1_0_5");
+
+        // Payload follows the latest commit.
+        assert!(commit_index_with_payload(tmp_dir, "").is_ok());
+        assert_eq!(get_commit_payload(tmp_dir).unwrap(), "");
+    }
+}
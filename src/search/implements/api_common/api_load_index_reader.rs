@@ -1,6 +1,6 @@
 use std::path::Path;
 use std::sync::Arc;
-use tantivy::{Index, IndexReader, ReloadPolicy};
+use tantivy::{Index, IndexReader};
 use crate::common::constants::FFI_INDEX_SEARCHER_CACHE;
 use crate::common::errors::TantivySearchError;
 use crate::{common::constants::LOG_CALLBACK, DEBUG, ERROR};
@@ -22,6 +22,18 @@ pub fn load_index_reader(index_path: &str) -> Result<bool, TantivySearchError> {
     }
     let index_path = index_path.trim_end_matches('/');
 
+    // Refuse index settings written in a newer format, older format is migrated while loading.
+    let index_parameter = IndexUtils::load_index_parameter(index_files_directory).map_err(|e| {
+        ERROR!(function: FUNC_NAME, "{}", e.to_string());
+        e
+    })?;
+    DEBUG!(
+        function: FUNC_NAME,
+        "index settings format version: {}, written by tantivy_search {}",
+        index_parameter.format_version,
+        index_parameter.library_version
+    );
+
     // Free old reader bridge.
     let bridge = FFI_INDEX_SEARCHER_CACHE.get_index_reader_bridge(index_path.to_string());
     if bridge.is_ok() {
@@ -34,6 +46,11 @@ pub fn load_index_reader(index_path: &str) -> Result<bool, TantivySearchError> {
         e
     })?;
     IndexUtils::register_tokenizers_from_disk(index_files_directory, &index)?;
+    IndexUtils::check_column_names(&index_parameter, &index.schema()).map_err(|e| {
+        ERROR!(function: FUNC_NAME, "{}", e.to_string());
+        e
+    })?;
+    let reader_settings = IndexUtils::load_reader_settings(index_files_directory)?;
 
     #[cfg(feature = "use-shared-search-pool")]
     {
@@ -60,11 +77,11 @@ pub fn load_index_reader(index_path: &str) -> Result<bool, TantivySearchError> {
         })?;
     }
 
-    // Create a reader for the index with an appropriate reload policy.
-    // OnCommit: reload when commiting; Manual: developer need call IndexReader::reload() to reload.
+    // Create a reader for the index with reload policy from `__reader__` key of index json parameter.
+    // OnCommit: reload when commiting; Manual: only reloaded by index operations of this library, such as commit.
     let reader: IndexReader = index
         .reader_builder()
-        .reload_policy(ReloadPolicy::OnCommit)
+        .reload_policy(reader_settings.to_reload_policy())
         .try_into()
         .map_err(|e| {
             ERROR!(function: FUNC_NAME, "Failed to create tantivy index reader: {}", e);
@@ -76,6 +93,7 @@ pub fn load_index_reader(index_path: &str) -> Result<bool, TantivySearchError> {
         index,
         reader,
         path: index_path.trim_end_matches('/').to_string(),
+        dropped_columns: index_parameter.dropped_columns,
     };
 
     FFI_INDEX_SEARCHER_CACHE
@@ -92,8 +110,10 @@ pub fn load_index_reader(index_path: &str) -> Result<bool, TantivySearchError> {
 mod tests {
     use tempfile::TempDir;
     use crate::common::{SinglePartTest, TEST_MUTEX};
-    use crate::common::constants::FFI_INDEX_SEARCHER_CACHE;
+    use crate::common::constants::{FFI_INDEX_SEARCHER_CACHE, INDEX_INFO_FILE_NAME};
+    use crate::index::implements::{commit_index, create_index_with_parameter, free_index_writer, index_multi_column_docs};
     use crate::search::implements::api_common::load_index_reader;
+    use crate::utils::index_utils::INDEX_SETTING_FORMAT_VERSION;
 
     #[test]
     fn normal_test() {
@@ -113,4 +133,53 @@ mod tests {
 
         assert!(load_index_reader(tmp_dir).unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn boundary_test_settings_format() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        let settings_path = std::path::Path::new(tmp_dir).join(INDEX_INFO_FILE_NAME);
+
+        // Settings written before the file was versioned are migrated.
+        std::fs::write(&settings_path, r#"{"tokenizers_json_parameter":"{}"}"#).unwrap();
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        // Settings written by a newer library are refused.
+        let json = format!(
+            r#"{{"format_version":{},"tokenizers_json_parameter":"{{}}"}}"#,
+            INDEX_SETTING_FORMAT_VERSION + 1
+        );
+        std::fs::write(&settings_path, json).unwrap();
+        assert!(load_index_reader(tmp_dir).is_err());
+
+        // Column names don't match the schema.
+        let json = format!(
+            r#"{{"format_version":{},"tokenizers_json_parameter":"{{}}","column_names":["col1","col4"]}}"#,
+            INDEX_SETTING_FORMAT_VERSION
+        );
+        std::fs::write(&settings_path, json).unwrap();
+        assert!(load_index_reader(tmp_dir).is_err());
+    }
+
+    #[test]
+    fn normal_test_manual_reload_policy() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let index_json_parameter = r#"{ "__reader__": { "reload_policy": "manual" } }"#;
+        assert!(create_index_with_parameter(tmp_dir, SinglePartTest::get_column_names(), index_json_parameter).is_ok());
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        // Reader is reloaded by commit of this library.
+        let column_docs: Vec<String> = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        assert!(index_multi_column_docs(tmp_dir, 0, SinglePartTest::get_column_names(), &column_docs).is_ok());
+        assert!(commit_index(tmp_dir).is_ok());
+        let bridge = FFI_INDEX_SEARCHER_CACHE.get_index_reader_bridge(tmp_dir.to_string()).unwrap();
+        assert_eq!(bridge.reader.searcher().num_docs(), 1);
+        assert!(free_index_writer(tmp_dir).unwrap());
+    }
+}
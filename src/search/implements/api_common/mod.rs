@@ -1,7 +1,9 @@
 mod api_load_index_reader;
 mod api_free_index_reader;
 mod api_get_indexed_doc_counts;
+mod api_get_commit_payload;
 
 pub use api_load_index_reader::load_index_reader;
 pub use api_free_index_reader::free_index_reader;
-pub use api_get_indexed_doc_counts::get_indexed_doc_counts;
\ No newline at end of file
+pub use api_get_indexed_doc_counts::get_indexed_doc_counts;
+pub use api_get_commit_payload::get_commit_payload;
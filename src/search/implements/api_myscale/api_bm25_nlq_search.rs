@@ -1,3 +1,5 @@
+use std::sync::Arc;
+use roaring::RoaringTreemap;
 use crate::common::errors::TantivySearchError;
 use crate::ffi::{RowIdWithScore, Statistics};
 use crate::search::implements::api_myscale::bm25_inner_search;
@@ -13,25 +15,85 @@ pub fn bm25_natural_language_search(
     operation_or: bool,
     statistics: &Statistics,
     need_doc: bool,
+) -> Result<Vec<RowIdWithScore>, TantivySearchError> {
+    bm25_natural_language_search_with_filter(
+        index_path,
+        sentence,
+        column_names,
+        top_k,
+        u8_alive_bitmap,
+        query_with_filter,
+        operation_or,
+        statistics,
+        need_doc,
+        "",
+    )
+}
+
+/// Same as `bm25_natural_language_search`, `filter_query` filters typed columns in the same query,
+/// such as `price:[0 TO 100} AND on_sale:true`, empty means no filter.
+pub fn bm25_natural_language_search_with_filter(
+    index_path: &str,
+    sentence: &str,
+    column_names: &Vec<String>,
+    top_k: u32,
+    u8_alive_bitmap: &Vec<u8>,
+    query_with_filter: bool,
+    operation_or: bool,
+    statistics: &Statistics,
+    need_doc: bool,
+    filter_query: &str,
 ) -> Result<Vec<RowIdWithScore>, TantivySearchError> {
     // Choose query strategy to construct query executor.
-    let bm25_natural_language_query: BM25NaturalLanguageStrategy<'_> =
-        BM25NaturalLanguageStrategy {
-            sentence,
-            column_names,
-            top_k: &top_k,
-            u8_alive_bitmap,
-            query_with_filter: &query_with_filter,
-            need_doc: &need_doc,
-            operation_or: &operation_or,
-        };
+    let bm25_natural_language_query: BM25NaturalLanguageStrategy<'_> = BM25NaturalLanguageStrategy {
+        sentence,
+        column_names,
+        top_k: &top_k,
+        query_with_filter: &query_with_filter,
+        u8_alive_bitmap,
+        u64_alive_bitmap: &None,
+        need_doc: &need_doc,
+        operation_or: &operation_or,
+        filter_query,
+    };
+
+    bm25_inner_search(index_path, statistics, &bm25_natural_language_query)
+}
+
+/// Same as `bm25_natural_language_search`, alive row_ids are given by `RoaringTreemap`, so u64 row_ids can be filtered.
+pub fn bm25_natural_language_search_u64(
+    index_path: &str,
+    sentence: &str,
+    column_names: &Vec<String>,
+    top_k: u32,
+    u64_alive_bitmap: Arc<RoaringTreemap>,
+    query_with_filter: bool,
+    operation_or: bool,
+    statistics: &Statistics,
+    need_doc: bool,
+) -> Result<Vec<RowIdWithScore>, TantivySearchError> {
+    let u64_alive_bitmap: Option<Arc<RoaringTreemap>> = Some(u64_alive_bitmap);
+    let bm25_natural_language_query: BM25NaturalLanguageStrategy<'_> = BM25NaturalLanguageStrategy {
+        sentence,
+        column_names,
+        top_k: &top_k,
+        query_with_filter: &query_with_filter,
+        u8_alive_bitmap: &vec![],
+        u64_alive_bitmap: &u64_alive_bitmap,
+        need_doc: &need_doc,
+        operation_or: &operation_or,
+        filter_query: "",
+    };
 
     bm25_inner_search(index_path, statistics, &bm25_natural_language_query)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::common::{MultiPartsTest, SinglePartTest};
+    use tempfile::TempDir;
+    use crate::common::{MultiPartsTest, SinglePartTest, TEST_MUTEX};
+    use crate::ffi::Statistics;
+    use crate::search::implements::bm25_natural_language_search_with_filter;
 
     #[test]
     fn normal_test_single_part_operation_or() {
@@ -109,4 +171,19 @@ mod tests {
         assert_eq!(MultiPartsTest::multi_parts_test_helper(true, "Ancient OR (education access)", &vec![16], true, false), 1);
         assert_eq!(MultiPartsTest::multi_parts_test_helper(true, "Human health", &vec![16], true, false), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn normal_test_single_part_with_filter_query() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_typed_columns_docs(tmp_dir);
+
+        let res = bm25_natural_language_search_with_filter(
+            tmp_dir, "title:ancient", &vec![], 10, &vec![], false, true, &Statistics::default(), false, "stock:[5 TO *]",
+        ).unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].row_id, 4);
+    }
+}
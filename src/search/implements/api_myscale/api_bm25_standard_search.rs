@@ -1,3 +1,5 @@
+use std::sync::Arc;
+use roaring::RoaringTreemap;
 use crate::common::errors::TantivySearchError;
 use crate::ffi::{RowIdWithScore, Statistics};
 use crate::search::implements::api_myscale::bm25_inner_search::bm25_inner_search;
@@ -13,6 +15,34 @@ pub fn bm25_standard_search(
     operation_or: bool,
     statistics: &Statistics,
     need_doc: bool,
+) -> Result<Vec<RowIdWithScore>, TantivySearchError> {
+    bm25_standard_search_with_filter(
+        index_path,
+        sentence,
+        column_names,
+        top_k,
+        u8_alive_bitmap,
+        query_with_filter,
+        operation_or,
+        statistics,
+        need_doc,
+        "",
+    )
+}
+
+/// Same as `bm25_standard_search`, `filter_query` filters typed columns in the same query,
+/// such as `price:[0 TO 100} AND on_sale:true`, empty means no filter.
+pub fn bm25_standard_search_with_filter(
+    index_path: &str,
+    sentence: &str,
+    column_names: &Vec<String>,
+    top_k: u32,
+    u8_alive_bitmap: &Vec<u8>,
+    query_with_filter: bool,
+    operation_or: bool,
+    statistics: &Statistics,
+    need_doc: bool,
+    filter_query: &str,
 ) -> Result<Vec<RowIdWithScore>, TantivySearchError> {
     // Choose query strategy to construct query executor.
     let bm25_standard_query: BM25StandardQueryStrategy<'_> = BM25StandardQueryStrategy {
@@ -21,17 +51,51 @@ pub fn bm25_standard_search(
         top_k: &top_k,
         query_with_filter: &query_with_filter,
         u8_alive_bitmap,
+        u64_alive_bitmap: &None,
         need_doc: &need_doc,
         operation_or: &operation_or,
+        filter_query,
     };
 
     bm25_inner_search(index_path, statistics, &bm25_standard_query)
 }
 
+/// Same as `bm25_standard_search`, alive row_ids are given by `RoaringTreemap`, so u64 row_ids can be filtered.
+pub fn bm25_standard_search_u64(
+    index_path: &str,
+    sentence: &str,
+    column_names: &Vec<String>,
+    top_k: u32,
+    u64_alive_bitmap: Arc<RoaringTreemap>,
+    query_with_filter: bool,
+    operation_or: bool,
+    statistics: &Statistics,
+    need_doc: bool,
+) -> Result<Vec<RowIdWithScore>, TantivySearchError> {
+    let u64_alive_bitmap: Option<Arc<RoaringTreemap>> = Some(u64_alive_bitmap);
+    let bm25_standard_query: BM25StandardQueryStrategy<'_> = BM25StandardQueryStrategy {
+        sentence,
+        column_names,
+        top_k: &top_k,
+        query_with_filter: &query_with_filter,
+        u8_alive_bitmap: &vec![],
+        u64_alive_bitmap: &u64_alive_bitmap,
+        need_doc: &need_doc,
+        operation_or: &operation_or,
+        filter_query: "",
+    };
+
+    bm25_inner_search(index_path, statistics, &bm25_standard_query)
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::common::{MultiPartsTest, SinglePartTest};
+    use std::sync::Arc;
+    use roaring::RoaringTreemap;
+    use tempfile::TempDir;
+    use crate::common::{MultiPartsTest, SinglePartTest, TEST_MUTEX};
+    use crate::ffi::Statistics;
+    use crate::search::implements::{bm25_standard_search_u64, bm25_standard_search_with_filter, load_index_reader};
 
     #[test]
     fn normal_test_single_part_operation_or() {
@@ -105,6 +169,78 @@ mod tests {
         assert_eq!(format!("{:.3}", res[1].score), format!("{:.3}", 0.8952658));
     }
 
+    #[test]
+    fn normal_test_single_part_with_u64_filter() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        let _ = SinglePartTest::index_docs_and_get_reader_bridge(tmp_dir, true, true, true);
+        assert!(load_index_reader(tmp_dir).unwrap());
+
+        let alive_row_ids: RoaringTreemap = vec![4u64, u32::MAX as u64 + 4].into_iter().collect();
+        let res = bm25_standard_search_u64(
+            tmp_dir,
+            "col2:(ancient rise fall)",
+            &vec![],
+            10,
+            Arc::new(alive_row_ids),
+            true,
+            true,
+            &Statistics::default(),
+            false,
+        ).unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].row_id, 4);
+        assert_eq!(format!("{:.3}", res[0].score), format!("{:.3}", 0.8952658));
+    }
+
+    #[test]
+    fn normal_test_single_part_with_filter_query() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_typed_columns_docs(tmp_dir);
+
+        let search = |filter_query: &str| {
+            bm25_standard_search_with_filter(
+                tmp_dir, "ancient", &vec![], 10, &vec![], false, true, &Statistics::default(), false, filter_query,
+            ).unwrap()
+        };
+        let no_filter = search("");
+        assert_eq!(no_filter.len(), 2);
+
+        let res = search("price:[0 TO 20] AND on_sale:true");
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].row_id, 0);
+        // Filter doesn't change bm25 score.
+        let score = no_filter.iter().find(|item| item.row_id == 0).unwrap().score;
+        assert_eq!(format!("{:.3}", res[0].score), format!("{:.3}", score));
+
+        assert!(search("stock:[100 TO *]").is_empty());
+    }
+
+    #[test]
+    fn normal_test_single_part_json_path() {
+        let _guard = TEST_MUTEX.lock().unwrap();
+        let tmp_dir = TempDir::new().unwrap();
+        let tmp_dir = tmp_dir.path().to_str().unwrap();
+
+        SinglePartTest::index_json_column_docs(tmp_dir);
+
+        let search = |column_names: &Vec<String>| {
+            bm25_standard_search_with_filter(
+                tmp_dir, "red", column_names, 10, &vec![], false, true, &Statistics::default(), false, "",
+            ).unwrap()
+        };
+        let mut row_ids: Vec<u64> = search(&vec!["attrs.color".to_string()]).iter().map(|item| item.row_id).collect();
+        row_ids.sort();
+        assert_eq!(row_ids, vec![0, 2]);
+        // Json column isn't searched by default.
+        assert!(search(&vec![]).is_empty());
+    }
+
     #[test]
     fn normal_test_multi_parts_no_filter(){
         assert_eq!(MultiPartsTest::multi_parts_test_helper(false, "Ancient provide wisdom modern dilemmas", &vec![], false, true), 6);
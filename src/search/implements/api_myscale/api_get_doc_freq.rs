@@ -22,7 +22,7 @@ pub fn get_doc_freq(
 
     for (col_field, col_field_entry) in schema.fields() {
         let field_type = col_field_entry.field_type();
-        if !field_type.is_indexed() {
+        if !field_type.is_indexed() || bridge.dropped_columns.iter().any(|column_name| column_name == col_field_entry.name()) {
             continue;
         }
         if let FieldType::Str(ref str_options) = field_type {
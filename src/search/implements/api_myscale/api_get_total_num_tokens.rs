@@ -16,7 +16,7 @@ pub fn get_total_num_tokens(index_path: &str) -> Result<Vec<FieldTokenNums>, Tan
 
     for (col_field, col_field_entry) in schema.fields() {
         let field_type = col_field_entry.field_type();
-        if !field_type.is_indexed() {
+        if !field_type.is_indexed() || bridge.dropped_columns.iter().any(|column_name| column_name == col_field_entry.name()) {
             continue;
         }
         if let FieldType::Str(_) = field_type {
@@ -25,7 +25,7 @@ pub(super) fn bm25_inner_search(
 
     // Not use statistics info.
     if statistics.docs_freq.len() == 0 {
-        let result: Vec<RowIdWithScore> = query_executor.execute(searcher).map_err(|e| {
+        let result: Vec<RowIdWithScore> = query_executor.execute(searcher, &bridge.dropped_columns).map_err(|e| {
             ERROR!(function: FUNC_NAME, "{}", e);
             TantivySearchError::IndexSearcherError(e)
         })?;
@@ -61,7 +61,7 @@ pub(super) fn bm25_inner_search(
 
     let _ = searcher.update_multi_parts_statistics(multi_parts_statistics);
 
-    let result: Vec<RowIdWithScore> = query_executor.execute(searcher).map_err(|e| {
+    let result: Vec<RowIdWithScore> = query_executor.execute(searcher, &bridge.dropped_columns).map_err(|e| {
         ERROR!(function: FUNC_NAME, "{}", e);
         TantivySearchError::IndexSearcherError(e)
     })?;
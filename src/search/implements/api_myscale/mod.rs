@@ -6,8 +6,8 @@ mod api_get_total_num_docs;
 mod api_get_total_num_tokens;
 
 use bm25_inner_search::bm25_inner_search;
-pub use api_bm25_nlq_search::bm25_natural_language_search;
-pub use api_bm25_standard_search::bm25_standard_search;
+pub use api_bm25_nlq_search::{bm25_natural_language_search, bm25_natural_language_search_u64, bm25_natural_language_search_with_filter};
+pub use api_bm25_standard_search::{bm25_standard_search, bm25_standard_search_u64, bm25_standard_search_with_filter};
 pub use api_get_doc_freq::get_doc_freq;
 pub use api_get_total_num_docs::get_total_num_docs;
 pub use api_get_total_num_tokens::get_total_num_tokens;
\ No newline at end of file
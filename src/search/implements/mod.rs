@@ -9,20 +9,33 @@ use search_utils::SearchUtils;
 
 pub use api_clickhouse::{
     query_term_bitmap,
+    query_term_bitmap_u64,
+    query_term_bitmap_with_filter,
     query_terms_bitmap,
+    query_terms_bitmap_u64,
+    query_terms_bitmap_with_filter,
     query_sentence_bitmap,
-    regex_term_bitmap
+    query_sentence_bitmap_u64,
+    query_sentence_bitmap_with_filter,
+    regex_term_bitmap,
+    regex_term_bitmap_u64,
+    regex_term_bitmap_with_filter
 };
 
 pub use api_common::{
     load_index_reader,
     free_index_reader,
-    get_indexed_doc_counts
+    get_indexed_doc_counts,
+    get_commit_payload
 };
 
 pub use api_myscale::{
     bm25_natural_language_search,
+    bm25_natural_language_search_u64,
+    bm25_natural_language_search_with_filter,
     bm25_standard_search,
+    bm25_standard_search_u64,
+    bm25_standard_search_with_filter,
     get_doc_freq,
     get_total_num_docs,
     get_total_num_tokens
@@ -1,12 +1,12 @@
 use std::sync::Arc;
-use roaring::RoaringBitmap;
-use tantivy::Searcher;
+use roaring::RoaringTreemap;
 use crate::common::constants::FFI_INDEX_SEARCHER_CACHE;
 use crate::search::bridge::index_reader_bridge::IndexReaderBridge;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::common::errors::TantivySearchError;
 use crate::search::implements::strategy::QueryExecutor;
+use crate::search::utils::ConvertUtils;
 
 pub struct SearchUtils;
 
@@ -23,11 +23,12 @@ impl SearchUtils {
         return Ok(reader_bridge);
     }
 
-    pub(super) fn execute_query(
-        func_name: &str, query_executor: QueryExecutor<'_, Arc<RoaringBitmap>>, searcher: &Searcher
-    ) -> Result<Arc<RoaringBitmap>, TantivySearchError> {
-        let result: Arc<RoaringBitmap> = query_executor
-            .execute(searcher)
+    // Execute query with the last searcher of reader bridge, dropped columns can't be queried.
+    pub(super) fn execute_query<T>(
+        func_name: &str, query_executor: QueryExecutor<'_, T>, bridge: &IndexReaderBridge
+    ) -> Result<T, TantivySearchError> {
+        let result: T = query_executor
+            .execute(&bridge.reader.searcher(), &bridge.dropped_columns)
             .map_err(|e| {
                 ERROR!(function: func_name, "{}", e);
                 TantivySearchError::IndexSearcherError(e)
@@ -35,4 +36,12 @@ impl SearchUtils {
         Ok(result)
     }
 
+    pub(super) fn treemap_to_bytes(
+        func_name: &str, treemap: &RoaringTreemap
+    ) -> Result<Vec<u8>, TantivySearchError> {
+        ConvertUtils::treemap_to_bytes(treemap).map_err(|e| {
+            ERROR!(function: func_name, "Failed to serialize row_ids treemap: {}", e);
+            TantivySearchError::InternalError(e.to_string())
+        })
+    }
 }
\ No newline at end of file
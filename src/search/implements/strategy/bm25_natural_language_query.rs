@@ -4,13 +4,16 @@ use crate::search::collector::top_dos_with_bitmap_collector::TopDocsWithFilter;
 use crate::search::implements::strategy::query_strategy::QueryStrategy;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
+use roaring::RoaringTreemap;
+use std::sync::Arc;
 use tantivy::query::{Query, QueryParser, QueryParserError};
 use tantivy::schema::Schema;
 use tantivy::{Searcher, TantivyError};
 use crate::search::implements::strategy::utils::StrategyUtils;
 
 /// Execute query for a sentence and get bm25 score.
-/// Query will be run in all schema fields but `row_id`.
+/// Query will be run in all text fields but `row_id` if `column_names` is empty.
+/// Json column can be queried in sentence with path, such as `attrs.color:red`.
 /// This sentence may be written by natural language, or just simple terms.
 /// If `query_with_filter` is true, when calculating bm25 score, only in `alive_row_ids` will be recorded.
 ///
@@ -18,26 +21,30 @@ use crate::search::implements::strategy::utils::StrategyUtils;
 /// - `sentence`: Sentence need to be parsed and query.
 /// - `top_k`: max-heap build with top_k
 /// - `u8_alive_bitmap`: Represent row_ids who are alive.
-/// - `query_with_filter`: Whether collect row_ids with `u8_alive_bitmap`
+/// - `u64_alive_bitmap`: Represent u64 row_ids who are alive, used instead of `u8_alive_bitmap` if given.
+/// - `query_with_filter`: Whether collect row_ids with `u8_alive_bitmap` or `u64_alive_bitmap`
+/// - `filter_query`: Filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
 ///
 pub struct BM25NaturalLanguageStrategy<'a> {
     pub sentence: &'a str,
     pub column_names: &'a Vec<String>,
     pub top_k: &'a u32,
     pub u8_alive_bitmap: &'a Vec<u8>,
+    pub u64_alive_bitmap: &'a Option<Arc<RoaringTreemap>>,
     pub query_with_filter: &'a bool,
     pub need_doc: &'a bool,
     pub operation_or: &'a bool,
+    pub filter_query: &'a str,
 }
 
 impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25NaturalLanguageStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Vec<RowIdWithScore>, IndexSearcherError> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Vec<RowIdWithScore>, IndexSearcherError> {
         static FUNC_NAME: &str = "BM25NaturalLanguageStrategy";
 
-        let schema: Schema = searcher.index().schema();
+        let schema: Schema = StrategyUtils::get_schema(searcher.index(), dropped_columns)?;
 
         let fields = if self.column_names.is_empty() {
-            StrategyUtils::get_fields_without_row_id(&schema)
+            StrategyUtils::get_text_fields(&schema)
         } else {
             StrategyUtils::get_fileds_with_columns(&schema, self.column_names)?
         };
@@ -49,10 +56,14 @@ impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25NaturalLanguageStrategy<'a>
                 .with_stored_text(*self.need_doc);
 
         if *self.query_with_filter {
-            top_docs_collector = top_docs_collector.with_alive_u8(self.u8_alive_bitmap.clone());
+            if let Some(u64_alive_bitmap) = self.u64_alive_bitmap {
+                top_docs_collector = top_docs_collector.with_alive_treemap(u64_alive_bitmap.clone());
+            } else {
+                top_docs_collector = top_docs_collector.with_alive_u8(self.u8_alive_bitmap.clone());
+            }
         }
 
-        let mut query_parser: QueryParser = QueryParser::for_index(searcher.index(), fields);
+        let mut query_parser: QueryParser = QueryParser::new(schema.clone(), fields, searcher.index().tokenizers().clone());
         if !*self.operation_or {
             query_parser.set_conjunction_by_default();
         }
@@ -64,7 +75,9 @@ impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25NaturalLanguageStrategy<'a>
             },
         )?;
 
-        searcher.search(&text_query, &top_docs_collector).map_err(|e: TantivyError|{
+        let query: Box<dyn Query> = StrategyUtils::with_filter(FUNC_NAME, searcher.index(), &schema, text_query, self.filter_query)?;
+
+        searcher.search(query.as_ref(), &top_docs_collector).map_err(|e: TantivyError|{
             ERROR!(function: FUNC_NAME, "Error when execute: {}. {}", self.sentence, e);
             IndexSearcherError::TantivyError(e)
         })
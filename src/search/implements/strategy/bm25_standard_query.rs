@@ -4,8 +4,10 @@ use crate::search::collector::top_dos_with_bitmap_collector::TopDocsWithFilter;
 use crate::search::implements::strategy::query_strategy::QueryStrategy;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
+use roaring::RoaringTreemap;
+use std::sync::Arc;
 use tantivy::query::{BooleanQuery, Occur, Query};
-use tantivy::schema::{FieldType, Schema, TextFieldIndexing};
+use tantivy::schema::{Field, FieldType, Schema};
 use tantivy::tokenizer::{BoxTokenStream, TextAnalyzer};
 use tantivy::{Searcher, Term};
 use crate::search::implements::strategy::utils::StrategyUtils;
@@ -15,21 +17,25 @@ pub struct BM25StandardQueryStrategy<'a> {
     pub column_names: &'a Vec<String>,
     pub top_k: &'a u32,
     pub u8_alive_bitmap: &'a Vec<u8>,
+    pub u64_alive_bitmap: &'a Option<Arc<RoaringTreemap>>,
     pub query_with_filter: &'a bool,
     pub need_doc: &'a bool,
     pub operation_or: &'a bool,
+    pub filter_query: &'a str,
 }
 
 impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25StandardQueryStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Vec<RowIdWithScore>, IndexSearcherError> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Vec<RowIdWithScore>, IndexSearcherError> {
         static FUNC_NAME: &str = "BM25StandardQueryStrategy";
 
-        let schema: Schema = searcher.index().schema();
-        let fields = if self.column_names.is_empty() {
-            StrategyUtils::get_fields_without_row_id(&schema)
+        let schema: Schema = StrategyUtils::get_schema(searcher.index(), dropped_columns)?;
+        // Json column is given with path, such as `attrs.color`.
+        let fields_with_path: Vec<(Field, &str)> = if self.column_names.is_empty() {
+            StrategyUtils::get_text_fields(&schema).into_iter().map(|field| (field, "")).collect()
         } else {
-            StrategyUtils::get_fileds_with_columns(&schema, self.column_names)?
+            StrategyUtils::get_fields_and_json_paths(&schema, self.column_names)?
         };
+        let fields: Vec<Field> = fields_with_path.iter().map(|(field, _)| *field).collect();
 
         for col_field in &fields {
             let field_type: &FieldType = schema.get_field_entry(*col_field).field_type();
@@ -50,25 +56,19 @@ impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25StandardQueryStrategy<'a> {
                 .with_stored_text(*self.need_doc);
 
         if *self.query_with_filter {
-            top_docs_collector = top_docs_collector.with_alive_u8(self.u8_alive_bitmap.clone());
+            if let Some(u64_alive_bitmap) = self.u64_alive_bitmap {
+                top_docs_collector = top_docs_collector.with_alive_treemap(u64_alive_bitmap.clone());
+            } else {
+                top_docs_collector = top_docs_collector.with_alive_u8(self.u8_alive_bitmap.clone());
+            }
         }
 
         let mut subqueries: Vec<(Occur, Box<dyn Query>)> = Vec::new();
 
-        for col_field in &fields {
+        for (col_field, json_path) in &fields_with_path {
             let mut terms: Vec<Term> = Vec::new();
             let field_type: &FieldType = schema.get_field_entry(*col_field).field_type();
-            if let FieldType::Str(ref str_options) = field_type {
-                let indexing_options: &TextFieldIndexing =
-                    str_options.get_indexing_options().ok_or_else(|| {
-                        let error_msg: String = format!(
-                            "column field:{} not indexed, but this error msg shouldn't display",
-                            schema.get_field_name(*col_field)
-                        );
-                        ERROR!(function: FUNC_NAME, "{}", error_msg);
-                        IndexSearcherError::InternalError(error_msg)
-                    })?;
-
+            if let Some(indexing_options) = StrategyUtils::get_text_indexing_options(field_type) {
                 let mut text_analyzer: TextAnalyzer = searcher
                     .index()
                     .tokenizers()
@@ -77,7 +77,7 @@ impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25StandardQueryStrategy<'a> {
 
                 let mut token_stream: BoxTokenStream<'_> = text_analyzer.token_stream(self.sentence);
                 token_stream.process(&mut |token| {
-                    terms.push(Term::from_field_text(*col_field, &token.text));
+                    terms.push(StrategyUtils::text_term(field_type, *col_field, json_path, &token.text));
                 });
 
                 if *self.operation_or {
@@ -88,14 +88,17 @@ impl<'a> QueryStrategy<Vec<RowIdWithScore>> for BM25StandardQueryStrategy<'a> {
                     subqueries.push((Occur::Should, subquery));
                 }
             } else {
-                let error_msg = "Not expected, column field type must be str type.";
+                let error_msg = "Not expected, column field type must be str or json type.";
                 ERROR!(function: FUNC_NAME, "{}", error_msg);
                 return Err(IndexSearcherError::InternalError(error_msg.to_string()));
             }
         }
 
+        let query: Box<dyn Query> =
+            StrategyUtils::with_filter(FUNC_NAME, searcher.index(), &schema, Box::new(BooleanQuery::new(subqueries)), self.filter_query)?;
+
         searcher
-            .search(&BooleanQuery::new(subqueries), &top_docs_collector)
+            .search(query.as_ref(), &top_docs_collector)
             .map_err(|e| {
                 ERROR!(function: FUNC_NAME, "{}", e);
                 IndexSearcherError::TantivyError(e)
@@ -23,14 +23,14 @@ pub struct ParserQueryStrategy<'a> {
 }
 
 impl<'a> QueryStrategy<Arc<RoaringBitmap>> for ParserQueryStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
         static FUNC_NAME: &str = "ParserQueryStrategy";
 
-        let schema: Schema = searcher.index().schema();
+        let schema: Schema = StrategyUtils::get_schema(searcher.index(), dropped_columns)?;
         let field = StrategyUtils::get_field_with_column(&schema, self.column_name)?;
 
         let row_id_collector: RowIdRoaringCollector = RowIdRoaringCollector::with_field("row_id".to_string());
-        let query_parser: QueryParser = QueryParser::for_index(searcher.index(), [field].to_vec());
+        let query_parser: QueryParser = QueryParser::new(schema, [field].to_vec(), searcher.index().tokenizers().clone());
 
         let text_query = query_parser.parse_query(self.sentence).map_err(|e| {
             ERROR!(function: FUNC_NAME, "Error when parse: {}. {}", self.sentence, e);
@@ -2,7 +2,7 @@ use crate::common::errors::IndexSearcherError;
 use tantivy::Searcher;
 
 pub trait QueryStrategy<T> {
-    fn execute(&self, searcher: &Searcher) -> Result<T, IndexSearcherError>;
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<T, IndexSearcherError>;
 }
 
 pub struct QueryExecutor<'a, T> {
@@ -14,7 +14,7 @@ impl<'a, T> QueryExecutor<'a, T> {
     pub fn new(strategy: &'a dyn QueryStrategy<T>) -> Self {
         QueryExecutor { strategy }
     }
-    pub fn execute(&self, searcher: &Searcher) -> Result<T, IndexSearcherError> {
-        self.strategy.execute(searcher)
+    pub fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<T, IndexSearcherError> {
+        self.strategy.execute(searcher, dropped_columns)
     }
 }
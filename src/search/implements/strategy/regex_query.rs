@@ -1,43 +1,67 @@
 use crate::common::errors::IndexSearcherError;
 use crate::search::collector::row_id_bitmap_collector::RowIdRoaringCollector;
+use crate::search::collector::row_id_treemap_collector::RowIdTreemapCollector;
 use crate::search::implements::strategy::query_strategy::QueryStrategy;
 use crate::search::utils::ConvertUtils;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use std::sync::Arc;
-use tantivy::query::RegexQuery;
-use tantivy::schema::Schema;
-use tantivy::Searcher;
+use tantivy::query::{Query, RegexQuery};
+use tantivy::schema::{FieldType, Schema};
+use tantivy::{Index, Searcher};
 use crate::search::implements::strategy::utils::StrategyUtils;
 
 /// Execute regex query for a given pattern.
 ///
 /// Params:
-/// - `column_name`: Execute query in which column.
+/// - `column_name`: Execute query in which column, json column isn't supported.
 /// - `pattern`: Regex query will execute with given pattern str.
+/// - `filter_query`: Filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
 ///
 pub struct RegexQueryStrategy<'a> {
     pub column_name: &'a str,
     pub pattern: &'a str,
+    pub filter_query: &'a str,
 }
 
-impl<'a> QueryStrategy<Arc<RoaringBitmap>> for RegexQueryStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+impl<'a> RegexQueryStrategy<'a> {
+    pub(crate) fn build_query(&self, index: &Index, dropped_columns: &[String]) -> Result<Box<dyn Query>, IndexSearcherError> {
         static FUNC_NAME: &str = "RegexQueryStrategy";
 
-        let schema: Schema = searcher.index().schema();
+        let schema: Schema = StrategyUtils::get_schema(index, dropped_columns)?;
         let field = StrategyUtils::get_field_with_column(&schema, self.column_name)?;
+        if let FieldType::JsonObject(_) = schema.get_field_entry(field).field_type() {
+            let error_msg: String = format!("column field:{} is a json column, regex query isn't supported.", self.column_name);
+            ERROR!(function: FUNC_NAME, "{}", error_msg);
+            return Err(IndexSearcherError::InternalError(error_msg));
+        }
 
-        let row_id_collector: RowIdRoaringCollector = RowIdRoaringCollector::with_field("row_id".to_string());
         let regex_query: RegexQuery = RegexQuery::from_pattern(&ConvertUtils::like_to_regex(self.pattern), field).map_err(|e|{
             ERROR!(function: FUNC_NAME, "Error when parse regex query:{}. {}", ConvertUtils::like_to_regex(self.pattern), e);
             IndexSearcherError::TantivyError(e)
         })?;
 
-        searcher.search(&regex_query, &row_id_collector).map_err(|e|{
-            ERROR!(function: FUNC_NAME, "Error when execute regex query:{}. {}", ConvertUtils::like_to_regex(self.pattern), e);
-            IndexSearcherError::TantivyError(e)
-        })
+        StrategyUtils::with_filter(FUNC_NAME, index, &schema, Box::new(regex_query), self.filter_query)
+    }
+}
+
+impl<'a> QueryStrategy<Arc<RoaringBitmap>> for RegexQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "RegexQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher.index(), dropped_columns)?;
+        let row_id_collector: RowIdRoaringCollector =
+            RowIdRoaringCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
+    }
+}
+
+impl<'a> QueryStrategy<Arc<RoaringTreemap>> for RegexQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringTreemap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "RegexQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher.index(), dropped_columns)?;
+        let row_id_collector: RowIdTreemapCollector =
+            RowIdTreemapCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
     }
 }
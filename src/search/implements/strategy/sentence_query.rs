@@ -1,14 +1,15 @@
 use crate::common::errors::IndexSearcherError;
 use crate::search::collector::row_id_bitmap_collector::RowIdRoaringCollector;
+use crate::search::collector::row_id_treemap_collector::RowIdTreemapCollector;
 use crate::search::implements::strategy::query_strategy::QueryStrategy;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use std::sync::Arc;
-use tantivy::query::TermSetQuery;
-use tantivy::schema::{FieldType, Schema, TextFieldIndexing};
+use tantivy::query::{Query, TermSetQuery};
+use tantivy::schema::{FieldType, Schema};
 use tantivy::tokenizer::{BoxTokenStream, TextAnalyzer};
-use tantivy::{Searcher, Term};
+use tantivy::{Index, Searcher, Term};
 use crate::search::implements::strategy::utils::StrategyUtils;
 
 /// Execute query for a sentence, without natural language search.
@@ -16,20 +17,22 @@ use crate::search::implements::strategy::utils::StrategyUtils;
 /// It will convert to terms query when executing.
 ///
 /// Params:
-/// - `column_name`: Execute query in which column.
+/// - `column_name`: Execute query in which column, json column is given with path, such as `attrs.color`.
 /// - `sentence`: Sentence need to query.
+/// - `filter_query`: Filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
 ///
 pub struct SentenceQueryStrategy<'a> {
     pub column_name: &'a str,
     pub sentence: &'a str,
+    pub filter_query: &'a str,
 }
 
-impl<'a> QueryStrategy<Arc<RoaringBitmap>> for SentenceQueryStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+impl<'a> SentenceQueryStrategy<'a> {
+    pub(crate) fn build_query(&self, index: &Index, dropped_columns: &[String]) -> Result<Box<dyn Query>, IndexSearcherError> {
         static FUNC_NAME: &str = "SentenceQueryStrategy";
 
-        let schema: Schema = searcher.index().schema();
-        let field = StrategyUtils::get_field_with_column(&schema, self.column_name)?;
+        let schema: Schema = StrategyUtils::get_schema(index, dropped_columns)?;
+        let (field, json_path) = StrategyUtils::get_field_and_json_path(&schema, self.column_name)?;
         let field_type: &FieldType = schema.get_field_entry(field).field_type();
         if !field_type.is_indexed() {
             let error_msg: String = format!("column field:{} not indexed.", self.column_name);
@@ -39,41 +42,43 @@ impl<'a> QueryStrategy<Arc<RoaringBitmap>> for SentenceQueryStrategy<'a> {
 
         let mut terms: Vec<Term> = Vec::new();
 
-        if let FieldType::Str(ref str_options) = field_type {
-            let indexing_options: &TextFieldIndexing =
-                str_options.get_indexing_options().ok_or_else(|| {
-                    let error_msg: String = format!(
-                        "column field:{} not indexed, but this error msg shouldn't display",
-                        self.column_name
-                    );
-                    ERROR!(function: FUNC_NAME, "{}", error_msg);
-                    IndexSearcherError::InternalError(error_msg)
-                })?;
-
-            let mut text_analyzer: TextAnalyzer = searcher
-                .index()
+        if let Some(indexing_options) = StrategyUtils::get_text_indexing_options(field_type) {
+            let mut text_analyzer: TextAnalyzer = index
                 .tokenizers()
                 .get(indexing_options.tokenizer())
                 .unwrap();
 
             let mut token_stream: BoxTokenStream<'_> = text_analyzer.token_stream(self.sentence);
             token_stream.process(&mut |token| {
-                terms.push(Term::from_field_text(field, &token.text));
+                terms.push(StrategyUtils::text_term(field_type, field, json_path, &token.text));
             });
         } else {
-            let error_msg = "Not expected, column field type must be str type.";
+            let error_msg = "Not expected, column field type must be str or json type.";
             ERROR!(function: FUNC_NAME, "{}", error_msg);
             return Err(IndexSearcherError::InternalError(error_msg.to_string()));
         }
 
         let ter_set_query: TermSetQuery = TermSetQuery::new(terms);
-        let row_id_collector: RowIdRoaringCollector = RowIdRoaringCollector::with_field("row_id".to_string());
+        StrategyUtils::with_filter(FUNC_NAME, index, &schema, Box::new(ter_set_query), self.filter_query)
+    }
+}
+
+impl<'a> QueryStrategy<Arc<RoaringBitmap>> for SentenceQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "SentenceQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher.index(), dropped_columns)?;
+        let row_id_collector: RowIdRoaringCollector =
+            RowIdRoaringCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
+    }
+}
 
-        searcher
-            .search(&ter_set_query, &row_id_collector)
-            .map_err(|e| {
-                ERROR!(function: FUNC_NAME, "{}", e);
-                IndexSearcherError::TantivyError(e)
-            })
+impl<'a> QueryStrategy<Arc<RoaringTreemap>> for SentenceQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringTreemap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "SentenceQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher.index(), dropped_columns)?;
+        let row_id_collector: RowIdTreemapCollector =
+            RowIdTreemapCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
     }
 }
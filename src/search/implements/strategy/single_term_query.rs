@@ -1,33 +1,36 @@
 use crate::common::errors::IndexSearcherError;
 use crate::search::collector::row_id_bitmap_collector::RowIdRoaringCollector;
+use crate::search::collector::row_id_treemap_collector::RowIdTreemapCollector;
 use crate::search::implements::strategy::query_strategy::QueryStrategy;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use std::sync::Arc;
-use tantivy::query::{TermQuery, TermSetQuery};
-use tantivy::schema::{FieldType, IndexRecordOption, Schema, TextFieldIndexing};
+use tantivy::query::{Query, TermQuery, TermSetQuery};
+use tantivy::schema::{FieldType, IndexRecordOption, Schema};
 use tantivy::tokenizer::{BoxTokenStream, TextAnalyzer};
-use tantivy::{Searcher, Term};
+use tantivy::{Index, Searcher, Term};
 use crate::search::implements::strategy::utils::StrategyUtils;
 
 /// Execute query for one term.
 ///
 /// Params:
-/// - `column_name`: Execute query in which column.
+/// - `column_name`: Execute query in which column, json column is given with path, such as `attrs.color`.
 /// - `term`: Term need to be queried.
+/// - `filter_query`: Filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
 ///
 pub struct SingleTermQueryStrategy<'a> {
     pub column_name: &'a str,
     pub term: &'a str,
+    pub filter_query: &'a str,
 }
 
-impl<'a> QueryStrategy<Arc<RoaringBitmap>> for SingleTermQueryStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+impl<'a> SingleTermQueryStrategy<'a> {
+    pub(crate) fn build_query(&self, index: &Index, dropped_columns: &[String]) -> Result<Box<dyn Query>, IndexSearcherError> {
         static FUNC_NAME: &str = "SingleTermQueryStrategy";
 
-        let schema: Schema = searcher.index().schema();
-        let field = StrategyUtils::get_field_with_column(&schema, self.column_name)?;
+        let schema: Schema = StrategyUtils::get_schema(index, dropped_columns)?;
+        let (field, json_path) = StrategyUtils::get_field_and_json_path(&schema, self.column_name)?;
         let field_type: &FieldType = schema.get_field_entry(field).field_type();
         if !field_type.is_indexed() {
             let error_msg: String = format!("column field:{} not indexed.", self.column_name);
@@ -35,51 +38,46 @@ impl<'a> QueryStrategy<Arc<RoaringBitmap>> for SingleTermQueryStrategy<'a> {
             return Err(IndexSearcherError::InternalError(error_msg));
         }
 
-        if let FieldType::Str(ref str_options) = field_type {
-            let indexing_options: &TextFieldIndexing =
-                str_options.get_indexing_options().ok_or_else(|| {
-                    let error_msg: String = format!(
-                        "column field:{} not indexed, but this error msg shouldn't display",
-                        self.column_name
-                    );
-                    ERROR!(function: FUNC_NAME, "{}", error_msg);
-                    IndexSearcherError::InternalError(error_msg)
-                })?;
+        let query: Box<dyn Query> = if let Some(indexing_options) = StrategyUtils::get_text_indexing_options(field_type) {
             let mut terms: Vec<Term> = Vec::new();
-            let mut text_analyzer: TextAnalyzer = searcher
-                .index()
+            let mut text_analyzer: TextAnalyzer = index
                 .tokenizers()
                 .get(indexing_options.tokenizer())
                 .unwrap();
             let mut token_stream: BoxTokenStream<'_> = text_analyzer.token_stream(self.term);
             token_stream.process(&mut |token| {
-                let term: Term = Term::from_field_text(field, &token.text);
+                let term: Term = StrategyUtils::text_term(field_type, field, json_path, &token.text);
                 terms.push(term);
             });
 
             let ter_set_query: TermSetQuery = TermSetQuery::new(terms);
-            let row_id_collector: RowIdRoaringCollector =
-                RowIdRoaringCollector::with_field("row_id".to_string());
-
-            searcher
-                .search(&ter_set_query, &row_id_collector)
-                .map_err(|e| {
-                    ERROR!(function: FUNC_NAME, "{}", e);
-                    IndexSearcherError::TantivyError(e)
-                })
+            Box::new(ter_set_query)
         } else {
             // FixMe: Not Expected.
             let term: Term = Term::from_field_text(field, self.term);
             let term_query: TermQuery = TermQuery::new(term, IndexRecordOption::WithFreqs);
-            let row_id_collector: RowIdRoaringCollector =
-                RowIdRoaringCollector::with_field("row_id".to_string());
+            Box::new(term_query)
+        };
+        StrategyUtils::with_filter(FUNC_NAME, index, &schema, query, self.filter_query)
+    }
+}
 
-            searcher
-                .search(&term_query, &row_id_collector)
-                .map_err(|e| {
-                    ERROR!(function: FUNC_NAME, "{}", e);
-                    IndexSearcherError::TantivyError(e)
-                })
-        }
+impl<'a> QueryStrategy<Arc<RoaringBitmap>> for SingleTermQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "SingleTermQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher.index(), dropped_columns)?;
+        let row_id_collector: RowIdRoaringCollector =
+            RowIdRoaringCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
+    }
+}
+
+impl<'a> QueryStrategy<Arc<RoaringTreemap>> for SingleTermQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringTreemap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "SingleTermQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher.index(), dropped_columns)?;
+        let row_id_collector: RowIdTreemapCollector =
+            RowIdTreemapCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
     }
 }
@@ -1,37 +1,38 @@
 use crate::common::errors::IndexSearcherError;
 use crate::search::collector::row_id_bitmap_collector::RowIdRoaringCollector;
+use crate::search::collector::row_id_treemap_collector::RowIdTreemapCollector;
 use crate::search::implements::strategy::query_strategy::QueryStrategy;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
-use roaring::RoaringBitmap;
+use crate::search::implements::strategy::utils::StrategyUtils;
+use roaring::{RoaringBitmap, RoaringTreemap};
 use std::sync::Arc;
-use tantivy::query::TermSetQuery;
-use tantivy::schema::{Field, FieldType, Schema, TextFieldIndexing};
+use tantivy::query::{Query, TermSetQuery};
+use tantivy::schema::{Field, FieldType, Schema};
 use tantivy::tokenizer::{BoxTokenStream, TextAnalyzer};
 use tantivy::{Searcher, Term};
 
 /// Execute query for a group of terms.
 ///
 /// Params:
-/// - `column_name`: Execute query in which column.
+/// - `column_name`: Execute query in which column, json column is given with path, such as `attrs.color`.
 /// - `terms`: A group of terms.
+/// - `filter_query`: Filter on typed columns, such as `price:[0 TO 100}`, empty means no filter.
 ///
 pub struct TermSetQueryStrategy<'a> {
     pub column_name: &'a str,
     pub terms: &'a Vec<String>,
+    pub filter_query: &'a str,
 }
 
-impl<'a> QueryStrategy<Arc<RoaringBitmap>> for TermSetQueryStrategy<'a> {
-    fn execute(&self, searcher: &Searcher) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+impl<'a> TermSetQueryStrategy<'a> {
+    fn build_query(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Box<dyn Query>, IndexSearcherError> {
         static FUNC_NAME: &str = "TermSetQueryStrategy";
 
-        let schema: Schema = searcher.index().schema();
+        let schema: Schema = StrategyUtils::get_schema(searcher.index(), dropped_columns)?;
 
-        let col_field: Field = schema.get_field(self.column_name).map_err(|e| {
-            let error: IndexSearcherError = IndexSearcherError::TantivyError(e);
-            ERROR!(function: FUNC_NAME, "{}", error);
-            error
-        })?;
+        let (col_field, json_path): (Field, &str) =
+            StrategyUtils::get_field_and_json_path(&schema, self.column_name)?;
 
         let field_type: &FieldType = schema.get_field_entry(col_field).field_type();
         if !field_type.is_indexed() {
@@ -42,16 +43,7 @@ impl<'a> QueryStrategy<Arc<RoaringBitmap>> for TermSetQueryStrategy<'a> {
 
         let mut terms: Vec<Term> = Vec::new();
 
-        if let FieldType::Str(ref str_options) = field_type {
-            let indexing_options: &TextFieldIndexing =
-                str_options.get_indexing_options().ok_or_else(|| {
-                    let error_msg: String = format!(
-                        "column field:{} not indexed, but this error msg shouldn't display",
-                        self.column_name
-                    );
-                    ERROR!(function: FUNC_NAME, "{}", error_msg);
-                    IndexSearcherError::InternalError(error_msg)
-                })?;
+        if let Some(indexing_options) = StrategyUtils::get_text_indexing_options(field_type) {
             let mut text_analyzer: TextAnalyzer = searcher
                 .index()
                 .tokenizers()
@@ -61,7 +53,7 @@ impl<'a> QueryStrategy<Arc<RoaringBitmap>> for TermSetQueryStrategy<'a> {
             for term in self.terms {
                 let mut token_stream: BoxTokenStream<'_> = text_analyzer.token_stream(term);
                 token_stream.process(&mut |token| {
-                    terms.push(Term::from_field_text(col_field, &token.text));
+                    terms.push(StrategyUtils::text_term(field_type, col_field, json_path, &token.text));
                 });
             }
         } else {
@@ -72,14 +64,26 @@ impl<'a> QueryStrategy<Arc<RoaringBitmap>> for TermSetQueryStrategy<'a> {
         }
 
         let ter_set_query: TermSetQuery = TermSetQuery::new(terms);
+        StrategyUtils::with_filter(FUNC_NAME, searcher.index(), &schema, Box::new(ter_set_query), self.filter_query)
+    }
+}
+
+impl<'a> QueryStrategy<Arc<RoaringBitmap>> for TermSetQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringBitmap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "TermSetQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher, dropped_columns)?;
         let row_id_collector: RowIdRoaringCollector =
             RowIdRoaringCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
+    }
+}
 
-        searcher
-            .search(&ter_set_query, &row_id_collector)
-            .map_err(|e| {
-                ERROR!(function: FUNC_NAME, "{}", e);
-                IndexSearcherError::TantivyError(e)
-            })
+impl<'a> QueryStrategy<Arc<RoaringTreemap>> for TermSetQueryStrategy<'a> {
+    fn execute(&self, searcher: &Searcher, dropped_columns: &[String]) -> Result<Arc<RoaringTreemap>, IndexSearcherError> {
+        static FUNC_NAME: &str = "TermSetQueryStrategy";
+        let query: Box<dyn Query> = self.build_query(searcher, dropped_columns)?;
+        let row_id_collector: RowIdTreemapCollector =
+            RowIdTreemapCollector::with_field("row_id".to_string());
+        StrategyUtils::collect_row_ids(FUNC_NAME, searcher, query.as_ref(), &row_id_collector)
     }
 }
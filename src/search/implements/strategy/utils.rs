@@ -1,14 +1,49 @@
-use tantivy::schema::{Field, Schema};
+use tantivy::collector::Collector;
+use tantivy::query::{BooleanQuery, BoostQuery, Occur, Query, QueryParser};
+use tantivy::json_utils::JsonTermWriter;
+use tantivy::schema::{Field, FieldEntry, FieldType, Schema, TextFieldIndexing};
+use tantivy::{Index, Searcher, Term};
 use crate::common::errors::IndexSearcherError;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
 use crate::logger::logger_bridge::TantivySearchLogger;
 pub(super) struct StrategyUtils;
 
+/// Dropped columns are renamed with this prefix in the schema used by queries.
+const DROPPED_COLUMN_PREFIX: &str = "__dropped_";
+
 impl StrategyUtils {
-    pub(super) fn get_fields_without_row_id(schema: &Schema) -> Vec<Field> {
+    // Schema of the index with dropped columns renamed, fields keep their ids.
+    // Dropped columns can't be resolved by name any more, neither by column lookup nor by query parser.
+    pub(super) fn get_schema(index: &Index, dropped_columns: &[String]) -> Result<Schema, IndexSearcherError> {
+        let schema: Schema = index.schema();
+        if dropped_columns.is_empty() {
+            return Ok(schema);
+        }
+        let mut schema_builder = Schema::builder();
+        for (_, field_entry) in schema.fields() {
+            if !dropped_columns.iter().any(|column_name| column_name == field_entry.name()) {
+                schema_builder.add_field(field_entry.clone());
+                continue;
+            }
+            // `FieldEntry` can't be renamed in place, rename it through its serialized form.
+            let mut renamed = serde_json::to_value(field_entry).map_err(|e| IndexSearcherError::InternalError(e.to_string()))?;
+            renamed["name"] = serde_json::Value::String(format!("{}{}", DROPPED_COLUMN_PREFIX, field_entry.name()));
+            let renamed: FieldEntry =
+                serde_json::from_value(renamed).map_err(|e| IndexSearcherError::InternalError(e.to_string()))?;
+            schema_builder.add_field(renamed);
+        }
+        Ok(schema_builder.build())
+    }
+
+    // Text fields in schema, `row_id`, typed columns and dropped columns are excluded.
+    pub(super) fn get_text_fields(schema: &Schema) -> Vec<Field> {
         let fields: Vec<Field> = schema
             .fields()
-            .filter(|(field, _)| schema.get_field_name(*field) != "row_id")
+            .filter(|(field, field_entry)| {
+                schema.get_field_name(*field) != "row_id"
+                    && !field_entry.name().starts_with(DROPPED_COLUMN_PREFIX)
+                    && matches!(field_entry.field_type(), FieldType::Str(_))
+            })
             .map(|(field, _field_entry)| field)
             .collect();
         return fields;
@@ -31,4 +66,89 @@ impl StrategyUtils {
             })
         }).collect()
     }
-}
\ No newline at end of file
+
+    // Resolve column name to field and json path, json column can be queried with path such as `attrs.color`.
+    // Json path is empty for other columns.
+    pub(super) fn get_field_and_json_path<'b>(
+        schema: &Schema, column_name: &'b str
+    ) -> Result<(Field, &'b str), IndexSearcherError> {
+        let (field, json_path) = schema.find_field(column_name).ok_or_else(|| {
+            let error_msg: String = format!("column field:{} not found in schema.", column_name);
+            ERROR!("{}", error_msg);
+            IndexSearcherError::InternalError(error_msg)
+        })?;
+        if !json_path.is_empty() && !matches!(schema.get_field_entry(field).field_type(), FieldType::JsonObject(_)) {
+            let error_msg: String = format!("column field:{} is not a json column, can't query with path.", column_name);
+            ERROR!("{}", error_msg);
+            return Err(IndexSearcherError::InternalError(error_msg));
+        }
+        Ok((field, json_path))
+    }
+
+    pub(super) fn get_fields_and_json_paths<'b>(
+        schema: &Schema, column_names: &'b Vec<String>
+    ) -> Result<Vec<(Field, &'b str)>, IndexSearcherError> {
+        column_names
+            .iter()
+            .map(|column_name| Self::get_field_and_json_path(schema, column_name))
+            .collect()
+    }
+
+    // Text indexing options of a text or json column, other columns return `None`.
+    pub(super) fn get_text_indexing_options(field_type: &FieldType) -> Option<&TextFieldIndexing> {
+        match field_type {
+            FieldType::Str(str_options) => str_options.get_indexing_options(),
+            FieldType::JsonObject(json_options) => json_options.get_text_indexing_options(),
+            _ => None,
+        }
+    }
+
+    // Build term for a text token, json path is written ahead of the token for json column.
+    pub(super) fn text_term(field_type: &FieldType, field: Field, json_path: &str, text: &str) -> Term {
+        if let FieldType::JsonObject(json_options) = field_type {
+            let mut term: Term = Term::from_field_text(field, "");
+            {
+                let mut json_term_writer = JsonTermWriter::from_field_and_json_path(
+                    field,
+                    json_path,
+                    json_options.is_expand_dots_enabled(),
+                    &mut term,
+                );
+                json_term_writer.set_str(text);
+            }
+            term
+        } else {
+            Term::from_field_text(field, text)
+        }
+    }
+
+    // Combine `query` with a filter on typed columns, such as `price:[0 TO 100} AND on_sale:true`.
+    // Filter is parsed by tantivy `QueryParser` and doesn't change score, empty filter is ignored.
+    // `schema` is given by `get_schema`, so the filter can't use dropped columns.
+    pub(super) fn with_filter(
+        func_name: &str, index: &Index, schema: &Schema, query: Box<dyn Query>, filter_query: &str
+    ) -> Result<Box<dyn Query>, IndexSearcherError> {
+        if filter_query.trim().is_empty() {
+            return Ok(query);
+        }
+        let query_parser: QueryParser = QueryParser::new(schema.clone(), vec![], index.tokenizers().clone());
+        let filter: Box<dyn Query> = query_parser.parse_query(filter_query).map_err(|e| {
+            ERROR!(function: func_name, "Error when parse filter: {}. {}", filter_query, e);
+            IndexSearcherError::QueryParserError(e.to_string())
+        })?;
+        Ok(Box::new(BooleanQuery::new(vec![
+            (Occur::Must, query),
+            (Occur::Must, Box::new(BoostQuery::new(filter, 0.0))),
+        ])))
+    }
+
+    // Execute query and collect row_ids with the given collector.
+    pub(super) fn collect_row_ids<C: Collector>(
+        func_name: &str, searcher: &Searcher, query: &dyn Query, collector: &C
+    ) -> Result<C::Fruit, IndexSearcherError> {
+        searcher.search(query, collector).map_err(|e| {
+            ERROR!(function: func_name, "{}", e);
+            IndexSearcherError::TantivyError(e)
+        })
+    }
+}
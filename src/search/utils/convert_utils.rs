@@ -1,4 +1,4 @@
-use roaring::RoaringBitmap;
+use roaring::{RoaringBitmap, RoaringTreemap};
 
 pub struct ConvertUtils;
 
@@ -102,13 +102,28 @@ impl ConvertUtils {
         let byte = bitmap[idx as usize];
         (byte & (1 << offset)) != 0
     }
+
+    // Serialize u64 row_ids with RoaringTreemap portable format.
+    pub fn treemap_to_bytes(treemap: &RoaringTreemap) -> Result<Vec<u8>, std::io::Error> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(treemap.serialized_size());
+        treemap.serialize_into(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    // Deserialize u64 row_ids from RoaringTreemap portable format, empty bytes means empty treemap.
+    pub fn bytes_to_treemap(bytes: &[u8]) -> Result<RoaringTreemap, std::io::Error> {
+        if bytes.is_empty() {
+            return Ok(RoaringTreemap::new());
+        }
+        RoaringTreemap::deserialize_from(bytes)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     mod convert_utils {
         use super::super::*;
-        use roaring::RoaringBitmap;
+        use roaring::{RoaringBitmap, RoaringTreemap};
         use std::time::Instant;
 
         #[test]
@@ -331,5 +346,17 @@ mod tests {
             assert_eq!(ConvertUtils::is_row_id_exist(0, &bitmap4), false);
             assert_eq!(ConvertUtils::is_row_id_exist(1, &bitmap4), false);
         }
+
+        #[test]
+        fn test_treemap_bytes_convert() {
+            let treemap: RoaringTreemap = vec![0u64, 7, u32::MAX as u64 + 1, u64::MAX - 1].into_iter().collect();
+            let bytes = ConvertUtils::treemap_to_bytes(&treemap).unwrap();
+            assert_eq!(ConvertUtils::bytes_to_treemap(&bytes).unwrap(), treemap);
+
+            // empty bytes
+            assert!(ConvertUtils::bytes_to_treemap(&[]).unwrap().is_empty());
+            // broken bytes
+            assert!(ConvertUtils::bytes_to_treemap(&bytes[..bytes.len() - 1]).is_err());
+        }
     }
 }
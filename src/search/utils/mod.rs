@@ -1,3 +1,5 @@
 mod convert_utils;
+mod row_id_treemap_query;
 
-pub use convert_utils::ConvertUtils;
\ No newline at end of file
+pub use convert_utils::ConvertUtils;
+pub use row_id_treemap_query::RowIdTreemapQuery;
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+
+use roaring::RoaringTreemap;
+use tantivy::columnar::Column;
+use tantivy::query::{ConstScorer, EnableScoring, Explanation, Query, Scorer, Weight};
+use tantivy::{DocId, DocSet, Score, SegmentReader, TantivyError, TERMINATED};
+
+/// Match docs whose `row_id` fast field is contained in a `RoaringTreemap`.
+/// Row_ids are tested against the treemap directly, no `Term` is built for each row_id.
+#[derive(Clone, Debug)]
+pub struct RowIdTreemapQuery {
+    row_id_treemap: Arc<RoaringTreemap>,
+}
+
+impl RowIdTreemapQuery {
+    pub fn new(row_id_treemap: Arc<RoaringTreemap>) -> Self {
+        Self { row_id_treemap }
+    }
+}
+
+impl Query for RowIdTreemapQuery {
+    fn weight(&self, _enable_scoring: EnableScoring<'_>) -> tantivy::Result<Box<dyn Weight>> {
+        Ok(Box::new(RowIdTreemapWeight { row_id_treemap: self.row_id_treemap.clone() }))
+    }
+}
+
+struct RowIdTreemapWeight {
+    row_id_treemap: Arc<RoaringTreemap>,
+}
+
+impl Weight for RowIdTreemapWeight {
+    fn scorer(&self, reader: &SegmentReader, boost: Score) -> tantivy::Result<Box<dyn Scorer>> {
+        let row_id_column = reader.fast_fields().u64("row_id")?;
+        let docset = RowIdTreemapDocSet::new(row_id_column, self.row_id_treemap.clone(), reader.max_doc());
+        Ok(Box::new(ConstScorer::new(docset, boost)))
+    }
+
+    fn explain(&self, reader: &SegmentReader, doc: DocId) -> tantivy::Result<Explanation> {
+        let mut scorer = self.scorer(reader, 1.0)?;
+        if scorer.seek(doc) != doc {
+            return Err(TantivyError::InvalidArgument(format!("Document #({}) does not match", doc)));
+        }
+        Ok(Explanation::new("RowIdTreemapQuery", 1.0))
+    }
+}
+
+// Walk the `row_id` column of a segment and stop at docs whose row_id is in the treemap.
+struct RowIdTreemapDocSet {
+    row_id_column: Column<u64>,
+    row_id_treemap: Arc<RoaringTreemap>,
+    max_doc: DocId,
+    doc: DocId,
+}
+
+impl RowIdTreemapDocSet {
+    fn new(row_id_column: Column<u64>, row_id_treemap: Arc<RoaringTreemap>, max_doc: DocId) -> Self {
+        let mut docset = Self { row_id_column, row_id_treemap, max_doc, doc: 0 };
+        docset.doc = docset.next_match(0);
+        docset
+    }
+
+    fn next_match(&self, from: DocId) -> DocId {
+        (from..self.max_doc)
+            .find(|doc| self.row_id_column.values_for_doc(*doc).any(|row_id| self.row_id_treemap.contains(row_id)))
+            .unwrap_or(TERMINATED)
+    }
+}
+
+impl DocSet for RowIdTreemapDocSet {
+    fn advance(&mut self) -> DocId {
+        if self.doc != TERMINATED {
+            self.doc = self.next_match(self.doc + 1);
+        }
+        self.doc
+    }
+
+    fn seek(&mut self, target: DocId) -> DocId {
+        if self.doc < target {
+            self.doc = self.next_match(target);
+        }
+        self.doc
+    }
+
+    fn doc(&self) -> DocId {
+        self.doc
+    }
+
+    fn size_hint(&self) -> u32 {
+        self.max_doc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use roaring::RoaringTreemap;
+    use tantivy::collector::Count;
+    use tantivy::schema::{Schema, FAST, INDEXED};
+    use tantivy::{doc, Index};
+    use crate::search::utils::RowIdTreemapQuery;
+
+    #[test]
+    fn test_row_id_treemap_query() {
+        let mut schema_builder = Schema::builder();
+        let row_id = schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        let index = Index::create_in_ram(schema_builder.build());
+        let mut writer = index.writer_with_num_threads(1, 1024 * 1024 * 64).unwrap();
+        for value in [0u64, 3, u32::MAX as u64 + 1, 7] {
+            writer.add_document(doc!(row_id => value)).unwrap();
+        }
+        writer.commit().unwrap();
+        let searcher = index.reader().unwrap().searcher();
+
+        let count = |row_ids: Vec<u64>| {
+            let query = RowIdTreemapQuery::new(Arc::new(RoaringTreemap::from_iter(row_ids)));
+            searcher.search(&query, &Count).unwrap()
+        };
+        assert_eq!(count(vec![3, 7, 8]), 2);
+        assert_eq!(count(vec![u32::MAX as u64 + 1]), 1);
+        assert_eq!(count(vec![]), 0);
+    }
+}
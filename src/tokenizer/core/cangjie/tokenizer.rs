@@ -11,22 +11,25 @@ pub struct CangjieTokenizer {
     pub worker: Arc<Jieba>,
     /// Separation config
     pub option: CangjieOption,
+    /// Stop cutting text once this many tokens are emitted.
+    pub max_tokens: Option<usize>,
 }
 
+/// With `max_tokens`, text is cut chunk by chunk so that only the needed part is cut.
+const CUT_CHUNK_SIZE: usize = 16 * 1024;
+
 impl Default for CangjieTokenizer {
     fn default() -> Self {
         CangjieTokenizer {
             worker: Arc::new(Jieba::empty()),
             option: CangjieOption::Default { hmm: false },
+            max_tokens: None,
         }
     }
 }
 
-impl Tokenizer for CangjieTokenizer {
-    type TokenStream<'a> = CangjieTokenStream<'a>;
-
-    /// Cut text into tokens
-    fn token_stream<'a>(&mut self, text: &'a str) -> CangjieTokenStream<'a> {
+impl CangjieTokenizer {
+    fn cut<'a>(&self, text: &'a str) -> Vec<&'a str> {
         let result = match self.option {
             CangjieOption::All => self.worker.cut_all(text),
             CangjieOption::Default { hmm: use_hmm } => self.worker.cut(text, use_hmm),
@@ -42,7 +45,29 @@ impl Tokenizer for CangjieTokenizer {
                     .1
             }
         };
-        let result = TokenUtils::remove_nonsense_token(result);
+        TokenUtils::remove_nonsense_token(result)
+    }
+}
+
+impl Tokenizer for CangjieTokenizer {
+    type TokenStream<'a> = CangjieTokenStream<'a>;
+
+    /// Cut text into tokens
+    fn token_stream<'a>(&mut self, text: &'a str) -> CangjieTokenStream<'a> {
+        let result = match self.max_tokens {
+            None => self.cut(text),
+            Some(max_tokens) => {
+                let mut result: Vec<&str> = Vec::new();
+                for chunk in TokenUtils::split_chunks(text, CUT_CHUNK_SIZE) {
+                    result.extend(self.cut(chunk));
+                    if result.len() >= max_tokens {
+                        result.truncate(max_tokens);
+                        break;
+                    }
+                }
+                result
+            }
+        };
         trace!("{:?}->{:?}", text, result);
         CangjieTokenStream::new(text, result)
     }
@@ -56,6 +81,7 @@ mod tests {
     use tantivy::tokenizer::{TextAnalyzer, Token};
     use crate::tokenizer::core::cangjie::{CangjieOption, CangjieTokenizer};
     use crate::tokenizer::core::tests::assert_token;
+    use crate::tokenizer::core::TokenUtils;
 
     #[test]
     fn test_search_mode_with_hmm() {
@@ -129,10 +155,61 @@ mod tests {
         assert_token(&tokens[8], 8, "起", 27, 30);
     }
 
+    #[test]
+    fn test_max_tokens() {
+        let mut text_analyzer = TextAnalyzer::from(CangjieTokenizer {
+            worker: Arc::new(Jieba::default()),
+            option: CangjieOption::Default { hmm: false },
+            max_tokens: Some(3),
+        });
+        // Longer than one chunk, only the first chunk is cut.
+        let text = "潮汐锁定。".repeat(10000);
+        let mut tokens: Vec<Token> = vec![];
+        text_analyzer.token_stream(&text).process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "潮汐", 0, 6);
+        assert_token(&tokens[1], 1, "锁定", 6, 12);
+        assert_token(&tokens[2], 2, "潮汐", 15, 21);
+    }
+
+    #[test]
+    fn test_max_tokens_word_across_chunks() {
+        // `锁定` straddles the end of the first chunk window, it is cut as without `max_tokens`.
+        let text = "锁定".repeat(3000);
+        let tokenizer = CangjieTokenizer {
+            worker: Arc::new(Jieba::default()),
+            option: CangjieOption::Default { hmm: false },
+            max_tokens: Some(10000),
+        };
+        let mut tokens: Vec<Token> = vec![];
+        TextAnalyzer::from(tokenizer).token_stream(&text).process(&mut |token: &Token| tokens.push(token.clone()));
+        let expected = token_stream_helper(&text, Jieba::default(), CangjieOption::Default { hmm: false });
+        assert_eq!(tokens.len(), expected.len());
+        assert!(tokens.iter().zip(expected.iter()).all(|(left, right)| {
+            left.text == right.text && left.offset_from == right.offset_from
+        }));
+    }
+
+    #[test]
+    fn test_split_chunks() {
+        let chunks: Vec<&str> = TokenUtils::split_chunks("潮汐 锁定 系统", 8).collect();
+        assert_eq!(chunks, vec!["潮汐 ", "锁定 ", "系统"]);
+        // No separator in the window, the chunk grows until the next one.
+        let chunks: Vec<&str> = TokenUtils::split_chunks("潮汐锁定，系统", 8).collect();
+        assert_eq!(chunks, vec!["潮汐锁定，", "系统"]);
+        let chunks: Vec<&str> = TokenUtils::split_chunks("潮汐锁定", 8).collect();
+        assert_eq!(chunks, vec!["潮汐锁定"]);
+        // `.` may be inside a word such as `3.14`.
+        let chunks: Vec<&str> = TokenUtils::split_chunks("pi 3.14", 5).collect();
+        assert_eq!(chunks, vec!["pi ", "3.14"]);
+        assert_eq!(TokenUtils::split_chunks("", 8).count(), 0);
+    }
+
     fn token_stream_helper(text: &str, jieba_mode: Jieba, option: CangjieOption) -> Vec<Token> {
         let tokenizer = CangjieTokenizer {
             worker: Arc::new(jieba_mode),
             option,
+            max_tokens: None,
         };
         let mut text_analyzer = TextAnalyzer::from(tokenizer);
         let mut token_stream = text_analyzer.token_stream(text);
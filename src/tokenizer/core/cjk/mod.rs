@@ -0,0 +1,4 @@
+mod stream;
+mod tokenizer;
+
+pub use {stream::CjkTokenStream, tokenizer::CjkTokenizer};
@@ -0,0 +1,52 @@
+use tantivy::tokenizer::Token;
+
+#[derive(Debug)]
+pub struct CjkTokenStream<'a> {
+    src: &'a str,
+    result: Vec<&'a str>,
+    // Index in `result` of the next token to emit, also used as its position.
+    index: usize,
+    token: Token,
+}
+
+impl<'a> CjkTokenStream<'a> {
+    pub fn new(src: &'a str, result: Vec<&'a str>) -> Self {
+        CjkTokenStream {
+            src,
+            result,
+            index: 0,
+            token: Token::default(),
+        }
+    }
+}
+
+impl<'a> ::tantivy::tokenizer::TokenStream for CjkTokenStream<'a> {
+    fn advance(&mut self) -> bool {
+        if self.index < self.result.len() {
+            let current_word = self.result[self.index];
+            let offset_from = current_word.as_ptr() as usize - self.src.as_ptr() as usize;
+            let offset_to = offset_from + current_word.len();
+
+            self.token = Token {
+                offset_from,
+                offset_to,
+                position: self.index,
+                text: current_word.to_string(),
+                position_length: 1,
+            };
+
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
@@ -0,0 +1,168 @@
+use super::*;
+use log::trace;
+
+/// Emits overlapping bigrams for runs of Han, Hiragana, Katakana and Hangul,
+/// and one token per alphanumeric word for other scripts, like Lucene's CJKAnalyzer.
+/// A CJK run made of a single character is emitted as a unigram.
+#[derive(Clone, Debug, Default)]
+pub struct CjkTokenizer {
+    /// Stop cutting text once this many tokens are emitted.
+    pub max_tokens: Option<usize>,
+}
+
+/// Position of the CJK run being cut.
+#[derive(Default)]
+struct CjkRun {
+    /// Byte offset of the previous char in the run.
+    last: Option<usize>,
+    len: usize,
+}
+
+impl CjkTokenizer {
+    fn is_cjk(c: char) -> bool {
+        matches!(c as u32,
+            0x1100..=0x11FF     // Hangul Jamo
+            | 0x3005 | 0x3007   // Ideographic iteration mark, ideographic number zero
+            | 0x3040..=0x309F   // Hiragana
+            | 0x30A0..=0x30FF   // Katakana
+            | 0x3130..=0x318F   // Hangul Compatibility Jamo
+            | 0x31F0..=0x31FF   // Katakana Phonetic Extensions
+            | 0x3400..=0x4DBF   // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF   // CJK Unified Ideographs
+            | 0xA960..=0xA97F   // Hangul Jamo Extended-A
+            | 0xAC00..=0xD7AF   // Hangul Syllables
+            | 0xD7B0..=0xD7FF   // Hangul Jamo Extended-B
+            | 0xF900..=0xFAFF   // CJK Compatibility Ideographs
+            | 0xFF66..=0xFF9F   // Halfwidth Katakana
+            | 0x20000..=0x2FA1F // CJK Unified Ideographs Extension B..F, Compatibility Supplement
+        )
+    }
+
+    /// Extend the CJK run with the char at `offset`, emitting the bigram it ends.
+    fn push_cjk_char<'a>(text: &'a str, run: &mut CjkRun, offset: usize, c: char, result: &mut Vec<&'a str>) {
+        if let Some(last) = run.last {
+            result.push(&text[last..offset + c.len_utf8()]);
+        }
+        run.last = Some(offset);
+        run.len += 1;
+    }
+
+    /// End the CJK run at `end`, a run of a single char is emitted as a unigram.
+    fn end_cjk_run<'a>(text: &'a str, run: &mut CjkRun, end: usize, result: &mut Vec<&'a str>) {
+        if let (Some(last), 1) = (run.last, run.len) {
+            result.push(&text[last..end]);
+        }
+        *run = CjkRun::default();
+    }
+
+    fn push_word<'a>(text: &'a str, start: &mut Option<usize>, end: usize, result: &mut Vec<&'a str>) {
+        if let Some(from) = start.take() {
+            result.push(&text[from..end]);
+        }
+    }
+}
+
+impl ::tantivy::tokenizer::Tokenizer for CjkTokenizer {
+    type TokenStream<'a> = CjkTokenStream<'a>;
+
+    /// Cut text into tokens
+    fn token_stream<'a>(&mut self, text: &'a str) -> CjkTokenStream<'a> {
+        let max_tokens = self.max_tokens.unwrap_or(usize::MAX);
+        let mut result: Vec<&str> = Vec::new();
+        let mut cjk_run = CjkRun::default();
+        let mut word_start: Option<usize> = None;
+
+        for (offset, c) in text.char_indices() {
+            if result.len() >= max_tokens {
+                break;
+            }
+            if Self::is_cjk(c) {
+                Self::push_word(text, &mut word_start, offset, &mut result);
+                Self::push_cjk_char(text, &mut cjk_run, offset, c, &mut result);
+            } else if c.is_alphanumeric() {
+                Self::end_cjk_run(text, &mut cjk_run, offset, &mut result);
+                word_start.get_or_insert(offset);
+            } else {
+                Self::push_word(text, &mut word_start, offset, &mut result);
+                Self::end_cjk_run(text, &mut cjk_run, offset, &mut result);
+            }
+        }
+        if result.len() < max_tokens {
+            Self::push_word(text, &mut word_start, text.len(), &mut result);
+            Self::end_cjk_run(text, &mut cjk_run, text.len(), &mut result);
+        }
+        result.truncate(max_tokens);
+
+        trace!("{:?}->{:?}", text, result);
+        CjkTokenStream::new(text, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::tokenizer::{TextAnalyzer, Token};
+    use crate::tokenizer::core::cjk::CjkTokenizer;
+    use crate::tokenizer::core::tests::assert_token;
+
+    #[test]
+    fn test_japanese() {
+        let tokens = token_stream_helper("東京タワーへ行く");
+        assert_eq!(tokens.len(), 7);
+        assert_token(&tokens[0], 0, "東京", 0, 6);
+        assert_token(&tokens[1], 1, "京タ", 3, 9);
+        assert_token(&tokens[2], 2, "タワ", 6, 12);
+        assert_token(&tokens[3], 3, "ワー", 9, 15);
+        assert_token(&tokens[4], 4, "ーへ", 12, 18);
+        assert_token(&tokens[5], 5, "へ行", 15, 21);
+        assert_token(&tokens[6], 6, "行く", 18, 24);
+    }
+
+    #[test]
+    fn test_korean() {
+        let tokens = token_stream_helper("조석 고정 시스템");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "조석", 0, 6);
+        assert_token(&tokens[1], 1, "고정", 7, 13);
+        assert_token(&tokens[2], 2, "시스", 14, 20);
+        assert_token(&tokens[3], 3, "스템", 17, 23);
+    }
+
+    #[test]
+    fn test_mixed_scripts() {
+        let tokens = token_stream_helper("月(Tidal Locking)潮汐力, v2");
+        assert_eq!(tokens.len(), 6);
+        assert_token(&tokens[0], 0, "月", 0, 3);
+        assert_token(&tokens[1], 1, "Tidal", 4, 9);
+        assert_token(&tokens[2], 2, "Locking", 10, 17);
+        assert_token(&tokens[3], 3, "潮汐", 18, 24);
+        assert_token(&tokens[4], 4, "汐力", 21, 27);
+        assert_token(&tokens[5], 5, "v2", 29, 31);
+    }
+
+    #[test]
+    fn test_max_tokens() {
+        let tokenizer = CjkTokenizer { max_tokens: Some(3) };
+        let tokens = token_stream_helper_with(tokenizer.clone(), "東京タワーへ行く");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[2], 2, "タワ", 6, 12);
+        let tokens = token_stream_helper_with(tokenizer, "月 Tidal Locking");
+        assert_eq!(tokens.len(), 3);
+        assert_token(&tokens[0], 0, "月", 0, 3);
+        assert_token(&tokens[2], 2, "Locking", 10, 17);
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        token_stream_helper_with(CjkTokenizer::default(), text)
+    }
+
+    fn token_stream_helper_with(tokenizer: CjkTokenizer, text: &str) -> Vec<Token> {
+        let mut text_analyzer = TextAnalyzer::from(tokenizer);
+        let mut token_stream = text_analyzer.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
@@ -0,0 +1,4 @@
+mod stripper;
+mod tokenizer;
+
+pub use {stripper::HtmlStripper, stripper::StrippedText, tokenizer::HtmlStripTokenStream, tokenizer::HtmlStripTokenizer};
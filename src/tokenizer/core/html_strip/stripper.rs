@@ -0,0 +1,235 @@
+use std::borrow::Cow;
+
+/// Elements whose contents never reach the tokenizer.
+const SKIPPED_ELEMENTS: &[&str] = &["script", "style"];
+
+/// Inline elements are removed without a separator, so `in<b>cred</b>ible` stays one word.
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "abbr", "b", "bdi", "bdo", "cite", "code", "data", "dfn", "em", "font", "i", "kbd",
+    "mark", "q", "s", "samp", "small", "span", "strike", "strong", "sub", "sup", "time", "tt",
+    "u", "var", "wbr",
+];
+
+/// Length of the longest named entity `&CounterClockwiseContourIntegral;`, without the leading `&`.
+const MAX_ENTITY_LEN: usize = 32;
+
+/// Text left after stripping markup, with the original byte range of every output byte.
+#[derive(Clone, Debug, Default)]
+pub struct StrippedText {
+    pub text: String,
+    starts: Vec<usize>,
+    ends: Vec<usize>,
+}
+
+impl StrippedText {
+    /// Map a byte range of the stripped text back to a byte range of the original text.
+    pub fn original_range(&self, from: usize, to: usize) -> (usize, usize) {
+        if self.starts.is_empty() {
+            return (0, 0);
+        }
+        let last = self.starts.len() - 1;
+        let original_from = self.starts[from.min(last)];
+        if to <= from {
+            return (original_from, original_from);
+        }
+        (original_from, self.ends[(to - 1).min(last)])
+    }
+
+    fn clear(&mut self) {
+        self.text.clear();
+        self.starts.clear();
+        self.ends.clear();
+    }
+
+    fn push(&mut self, c: char, start: usize, end: usize) {
+        self.text.push(c);
+        for _ in 0..c.len_utf8() {
+            self.starts.push(start);
+            self.ends.push(end);
+        }
+    }
+}
+
+pub struct HtmlStripper;
+
+impl HtmlStripper {
+    /// Remove tags, comments and `script`/`style` contents from `src`, decode entities,
+    /// and write the result into `output`.
+    pub fn strip(src: &str, output: &mut StrippedText) {
+        output.clear();
+        let bytes = src.as_bytes();
+        let mut pos = 0;
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'<' => {
+                    if let Some(end) = Self::skip_markup(src, pos, output) {
+                        pos = end;
+                        continue;
+                    }
+                }
+                b'&' => {
+                    if let Some((decoded, end)) = Self::decode_entity(src, pos) {
+                        for c in decoded.chars() {
+                            output.push(c, pos, end);
+                        }
+                        pos = end;
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            let c = src[pos..].chars().next().unwrap();
+            output.push(c, pos, pos + c.len_utf8());
+            pos += c.len_utf8();
+        }
+    }
+
+    /// Skip the markup starting at `start` and return the position right after it.
+    /// Returns `None` when the `<` does not open a tag, comment or declaration.
+    fn skip_markup(src: &str, start: usize, output: &mut StrippedText) -> Option<usize> {
+        let rest = &src[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").map_or(src.len(), |idx| start + 4 + idx + 3);
+            output.push(' ', start, end);
+            return Some(end);
+        }
+
+        let after_lt = rest[1..].chars().next()?;
+        if !(after_lt.is_ascii_alphabetic() || after_lt == '/' || after_lt == '!' || after_lt == '?') {
+            return None;
+        }
+        let tag_end = Self::find_tag_end(src, start + 1);
+        let closing = after_lt == '/';
+        let name_start = if closing { start + 2 } else { start + 1 };
+        let name: String = src[name_start.min(tag_end)..tag_end]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        let self_closing = src[..tag_end].ends_with("/>");
+        if !closing && !self_closing && SKIPPED_ELEMENTS.contains(&name.as_str()) {
+            let end = Self::find_closing_tag(src, tag_end, &name);
+            output.push(' ', start, end);
+            return Some(end);
+        }
+        if !INLINE_ELEMENTS.contains(&name.as_str()) {
+            output.push(' ', start, tag_end);
+        }
+        Some(tag_end)
+    }
+
+    /// Find the end of a tag starting at `from`, ignoring `>` inside quoted attribute values.
+    fn find_tag_end(src: &str, from: usize) -> usize {
+        let mut quote: Option<u8> = None;
+        for (idx, &b) in src.as_bytes()[from..].iter().enumerate() {
+            match quote {
+                Some(q) if b == q => quote = None,
+                Some(_) => {}
+                None if b == b'"' || b == b'\'' => quote = Some(b),
+                None if b == b'>' => return from + idx + 1,
+                None => {}
+            }
+        }
+        src.len()
+    }
+
+    /// Find the end of `</name ...>` after `from`, or the end of `src` if it is missing.
+    /// `name` is lowercase, the closing tag is matched case-insensitively.
+    fn find_closing_tag(src: &str, from: usize, name: &str) -> usize {
+        let name = name.as_bytes();
+        let position = src.as_bytes()[from..]
+            .windows(name.len() + 2)
+            .position(|window| window.starts_with(b"</") && window[2..].eq_ignore_ascii_case(name));
+        match position {
+            Some(idx) => Self::find_tag_end(src, from + idx + name.len() + 2),
+            None => src.len(),
+        }
+    }
+
+    /// Decode the entity starting at `start`, returning its text and the position after `;`.
+    /// Named entities use the full HTML5 entity table, no-break spaces become plain spaces.
+    fn decode_entity(src: &str, start: usize) -> Option<(String, usize)> {
+        let rest = &src[start + 1..];
+        let semicolon = rest
+            .bytes()
+            .take(MAX_ENTITY_LEN)
+            .position(|b| b == b';')?;
+        let body = &rest[..semicolon];
+        let end = start + 1 + semicolon + 1;
+        let decoded: String = if let Some(number) = body.strip_prefix('#') {
+            let code = match number.strip_prefix('x').or_else(|| number.strip_prefix('X')) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse::<u32>().ok()?,
+            };
+            char::from_u32(code)?.to_string()
+        } else {
+            let entity = &src[start..end];
+            match html_escape::decode_html_entities(entity) {
+                Cow::Owned(decoded) => decoded,
+                // Unknown entity is kept as it is.
+                Cow::Borrowed(_) => return None,
+            }
+        };
+        let decoded = decoded
+            .chars()
+            .map(|c| if c.is_whitespace() { ' ' } else { c })
+            .collect();
+        Some((decoded, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HtmlStripper, StrippedText};
+
+    fn strip(src: &str) -> StrippedText {
+        let mut output = StrippedText::default();
+        HtmlStripper::strip(src, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_strip_tags() {
+        assert_eq!(strip("<div>Hello</div><p>World</p>").text, " Hello  World ");
+        assert_eq!(strip("in<b>cred</b>ible").text, "incredible");
+        assert_eq!(strip("<a href=\"x>y\">link</a>").text, "link");
+        assert_eq!(strip("a <!-- comment <p> --> b").text, "a   b");
+        assert_eq!(strip("1 < 2 and 3 > 2").text, "1 < 2 and 3 > 2");
+    }
+
+    #[test]
+    fn test_skip_script_and_style() {
+        let stripped = strip("a<script type=\"text/javascript\">var x = '<p>';</script>b<STYLE>p{}</STYLE>c");
+        assert_eq!(stripped.text, "a b c");
+        assert_eq!(strip("a<script>never closed").text, "a ");
+        assert_eq!(strip("a<script>var s = '中文';</ScRiPt >b<style>x</style>c").text, "a b c");
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(strip("Tom&amp;Jerry&nbsp;&lt;3").text, "Tom&Jerry <3");
+        assert_eq!(strip("&#20013;&#x6587;").text, "中文");
+        assert_eq!(strip("&unknown; & &amp").text, "&unknown; & &amp");
+        assert_eq!(strip("caf&eacute; M&uuml;nchen &ccedil;a &Ouml;l").text, "café München ça Öl");
+        assert_eq!(strip("&emsp;&CounterClockwiseContourIntegral;").text, " ∳");
+    }
+
+    #[test]
+    fn test_original_range() {
+        let src = "<p>caf&eacute; &amp; th&#233;</p>";
+        let stripped = strip("<p>Tom &amp; Jerry</p>");
+        // "Tom"
+        assert_eq!(stripped.original_range(1, 4), (3, 6));
+        // "&"
+        assert_eq!(stripped.original_range(5, 6), (7, 12));
+        // "Jerry"
+        assert_eq!(stripped.original_range(7, 12), (13, 18));
+        let stripped = strip(src);
+        assert_eq!(stripped.text, " café & thé ");
+        // "café"
+        assert_eq!(stripped.original_range(1, 6), (3, 14));
+        // "thé"
+        assert_eq!(stripped.original_range(9, 13), (21, 29));
+    }
+}
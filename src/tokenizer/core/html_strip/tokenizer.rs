@@ -0,0 +1,105 @@
+use super::{HtmlStripper, StrippedText};
+use tantivy::tokenizer::{Token, TokenStream, Tokenizer};
+
+/// `HtmlStripTokenizer` feeds the wrapped tokenizer with the text left after
+/// stripping HTML markup, then maps token offsets back to the original text.
+#[derive(Clone, Debug)]
+pub struct HtmlStripTokenizer<T> {
+    inner: T,
+    stripped: StrippedText,
+}
+
+impl<T> HtmlStripTokenizer<T> {
+    pub fn new(inner: T) -> Self {
+        HtmlStripTokenizer {
+            inner,
+            stripped: StrippedText::default(),
+        }
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for HtmlStripTokenizer<T> {
+    type TokenStream<'a> = HtmlStripTokenStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        HtmlStripper::strip(text, &mut self.stripped);
+        HtmlStripTokenStream {
+            tail: self.inner.token_stream(&self.stripped.text),
+            stripped: &self.stripped,
+        }
+    }
+}
+
+pub struct HtmlStripTokenStream<'a, T> {
+    tail: T,
+    stripped: &'a StrippedText,
+}
+
+impl<'a, T: TokenStream> TokenStream for HtmlStripTokenStream<'a, T> {
+    fn advance(&mut self) -> bool {
+        if !self.tail.advance() {
+            return false;
+        }
+        let token = self.tail.token_mut();
+        let (offset_from, offset_to) = self.stripped.original_range(token.offset_from, token.offset_to);
+        token.offset_from = offset_from;
+        token.offset_to = offset_to;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use jieba_rs::Jieba;
+    use tantivy::tokenizer::{LowerCaser, SimpleTokenizer, TextAnalyzer, Token};
+    use crate::tokenizer::core::cangjie::{CangjieOption, CangjieTokenizer};
+    use crate::tokenizer::core::html_strip::HtmlStripTokenizer;
+    use crate::tokenizer::core::tests::assert_token;
+
+    #[test]
+    fn test_simple_tokenizer_offsets() {
+        let text = "<div class=\"nav\">Tom&nbsp;&amp;&nbsp;<b>Jerry</b></div><script>var div;</script>";
+        let mut text_analyzer = TextAnalyzer::builder(HtmlStripTokenizer::new(SimpleTokenizer::default()))
+            .filter(LowerCaser)
+            .build();
+        let tokens = token_stream_helper(&mut text_analyzer, text);
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "tom", 17, 20);
+        assert_token(&tokens[1], 1, "jerry", 40, 45);
+        assert_eq!(&text[tokens[1].offset_from..tokens[1].offset_to], "Jerry");
+    }
+
+    #[test]
+    fn test_chinese_tokenizer_offsets() {
+        let text = "<p>月球&lt;Moon&gt;</p>";
+        let tokenizer = CangjieTokenizer {
+            worker: Arc::new(Jieba::default()),
+            option: CangjieOption::Default { hmm: false },
+            max_tokens: None,
+        };
+        let mut text_analyzer = TextAnalyzer::from(HtmlStripTokenizer::new(tokenizer));
+        let tokens = token_stream_helper(&mut text_analyzer, text);
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[0], 0, "月球", 3, 9);
+        assert_token(&tokens[1], 1, "Moon", 13, 17);
+    }
+
+    fn token_stream_helper(text_analyzer: &mut TextAnalyzer, text: &str) -> Vec<Token> {
+        let mut token_stream = text_analyzer.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
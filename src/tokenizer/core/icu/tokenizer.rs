@@ -16,44 +16,66 @@ enum SegmentWorker {
 pub struct IcuTokenizer {
     /// Separation config
     pub option: IcuOption,
+    /// Stop segmenting text once this many tokens are emitted.
+    pub max_tokens: Option<usize>,
 }
 
 impl Default for IcuTokenizer {
     fn default() -> Self {
         IcuTokenizer {
             option: IcuOption::Word,
+            max_tokens: None,
         }
     }
 }
 
+impl IcuTokenizer {
+    /// Collect tokens between breakpoints, segmentation stops once `max_tokens` is reached.
+    fn collect_tokens(
+        text: &str,
+        mut breakpoints: impl Iterator<Item = usize>,
+        max_tokens: Option<usize>,
+    ) -> Vec<&str> {
+        let max_tokens = max_tokens.unwrap_or(usize::MAX);
+        let mut tokens: Vec<&str> = Vec::new();
+        let Some(mut from) = breakpoints.next() else {
+            return tokens;
+        };
+        for to in breakpoints {
+            tokens.extend(TokenUtils::remove_nonsense_token(vec![&text[from..to]]));
+            if tokens.len() >= max_tokens {
+                tokens.truncate(max_tokens);
+                break;
+            }
+            from = to;
+        }
+        tokens
+    }
+}
+
 impl ::tantivy::tokenizer::Tokenizer for IcuTokenizer {
     type TokenStream<'a> = IcuTokenStream<'a>;
 
     /// Cut text into tokens
     fn token_stream<'a>(&mut self, text: &'a str) -> IcuTokenStream<'a> {
-        let breakpoints: Vec<usize> = match self.option {
+        let processed_tokens: Vec<&str> = match self.option {
             IcuOption::Grapheme => {
                 let icu_grapheme = GraphemeClusterSegmenter::new();
-                icu_grapheme.segment_str(text).collect()
+                Self::collect_tokens(text, icu_grapheme.segment_str(text), self.max_tokens)
             },
             IcuOption::Line => {
                 let icu_line = LineSegmenter::new_auto();
-                icu_line.segment_str(text).collect()
+                Self::collect_tokens(text, icu_line.segment_str(text), self.max_tokens)
             },
             IcuOption::Sentence => {
                 let icu_sentence = SentenceSegmenter::new();
-                icu_sentence.segment_str(text).collect()
+                Self::collect_tokens(text, icu_sentence.segment_str(text), self.max_tokens)
             }
             IcuOption::Word => {
                 let icu_word = WordSegmenter::new_auto();
-                icu_word.segment_str(text).collect()
+                Self::collect_tokens(text, icu_word.segment_str(text), self.max_tokens)
             }
         };
-        let tokens: Vec<&str> = breakpoints
-            .windows(2)
-            .map(|window| &text[window[0]..window[1]])
-            .collect();
-        let processed_tokens = TokenUtils::remove_nonsense_token(tokens);
         trace!("{:?}->{:?}", text, processed_tokens);
         IcuTokenStream::new(text, processed_tokens)
     }
@@ -350,9 +372,24 @@ mod tests {
         assert_token(&tokens[16], 16, "Luna", 115, 119);
     }
 
+    #[test]
+    fn test_max_tokens() {
+        let mut text_analyzer = TextAnalyzer::from(IcuTokenizer {
+            option: IcuOption::Word,
+            max_tokens: Some(2),
+        });
+        let mut tokens: Vec<Token> = vec![];
+        text_analyzer
+            .token_stream("Tidal Locking, forze mareali")
+            .process(&mut |token: &Token| tokens.push(token.clone()));
+        assert_eq!(tokens.len(), 2);
+        assert_token(&tokens[1], 1, "Locking", 6, 13);
+    }
+
     fn token_stream_helper(text: &str, option: IcuOption) -> Vec<Token> {
         let tokenizer = IcuTokenizer {
             option,
+            max_tokens: None,
         };
         let mut text_analyzer = TextAnalyzer::from(tokenizer);
         let mut token_stream = text_analyzer.token_stream(text);
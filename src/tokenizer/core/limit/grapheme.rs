@@ -0,0 +1,103 @@
+use tantivy::tokenizer::{Token, TokenFilter, TokenStream, Tokenizer};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// `GraphemeLimitFilter` removes tokens longer than a given number of grapheme clusters.
+/// Unlike `RemoveLongFilter`, which counts UTF-8 bytes, a 40 character limit keeps
+/// a 14 character Chinese word.
+#[derive(Clone, Debug)]
+pub struct GraphemeLimitFilter {
+    limit: usize,
+}
+
+impl GraphemeLimitFilter {
+    pub fn limit(limit: usize) -> Self {
+        GraphemeLimitFilter { limit }
+    }
+}
+
+impl TokenFilter for GraphemeLimitFilter {
+    type Tokenizer<T: Tokenizer> = GraphemeLimitFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> GraphemeLimitFilterWrapper<T> {
+        GraphemeLimitFilterWrapper {
+            limit: self.limit,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GraphemeLimitFilterWrapper<T> {
+    limit: usize,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for GraphemeLimitFilterWrapper<T> {
+    type TokenStream<'a> = GraphemeLimitTokenStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        GraphemeLimitTokenStream {
+            limit: self.limit,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct GraphemeLimitTokenStream<T> {
+    limit: usize,
+    tail: T,
+}
+
+impl<T> GraphemeLimitTokenStream<T> {
+    fn is_within_limit(&self, token: &Token) -> bool {
+        // A grapheme takes at least one byte, skip counting for short tokens.
+        token.text.len() <= self.limit || token.text.graphemes(true).count() <= self.limit
+    }
+}
+
+impl<T: TokenStream> TokenStream for GraphemeLimitTokenStream<T> {
+    fn advance(&mut self) -> bool {
+        while self.tail.advance() {
+            if self.is_within_limit(self.tail.token()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::tokenizer::{RemoveLongFilter, TextAnalyzer, Token, WhitespaceTokenizer};
+    use crate::tokenizer::core::limit::GraphemeLimitFilter;
+
+    #[test]
+    fn test_grapheme_limit() {
+        let text = "中华人民共和国 abcdefgh e\u{301}e\u{301}e\u{301}";
+        let mut text_analyzer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(GraphemeLimitFilter::limit(7))
+            .build();
+        assert_eq!(token_texts(&mut text_analyzer, text), vec!["中华人民共和国", "e\u{301}e\u{301}e\u{301}"]);
+
+        // The same limit counted in bytes drops the chinese word.
+        let mut text_analyzer = TextAnalyzer::builder(WhitespaceTokenizer::default())
+            .filter(RemoveLongFilter::limit(10))
+            .build();
+        assert_eq!(token_texts(&mut text_analyzer, text), vec!["abcdefgh", "e\u{301}e\u{301}e\u{301}"]);
+    }
+
+    fn token_texts(text_analyzer: &mut TextAnalyzer, text: &str) -> Vec<String> {
+        let mut token_stream = text_analyzer.token_stream(text);
+        let mut tokens: Vec<String> = vec![];
+        token_stream.process(&mut |token: &Token| tokens.push(token.text.clone()));
+        tokens
+    }
+}
@@ -0,0 +1,91 @@
+use tantivy::tokenizer::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// `MaxTokensFilter` stops the token stream after a given number of tokens,
+/// so that pathological huge rows don't blow up indexing memory.
+/// It only bounds memory of lazy tokenizers, tokenizers which cut the whole text
+/// before emitting need the cap themselves, like `CjkTokenizer::max_tokens`.
+#[derive(Clone, Debug)]
+pub struct MaxTokensFilter {
+    limit: usize,
+}
+
+impl MaxTokensFilter {
+    pub fn limit(limit: usize) -> Self {
+        MaxTokensFilter { limit }
+    }
+}
+
+impl TokenFilter for MaxTokensFilter {
+    type Tokenizer<T: Tokenizer> = MaxTokensFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> MaxTokensFilterWrapper<T> {
+        MaxTokensFilterWrapper {
+            limit: self.limit,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MaxTokensFilterWrapper<T> {
+    limit: usize,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for MaxTokensFilterWrapper<T> {
+    type TokenStream<'a> = MaxTokensTokenStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        MaxTokensTokenStream {
+            remaining: self.limit,
+            tail: self.inner.token_stream(text),
+        }
+    }
+}
+
+pub struct MaxTokensTokenStream<T> {
+    remaining: usize,
+    tail: T,
+}
+
+impl<T: TokenStream> TokenStream for MaxTokensTokenStream<T> {
+    fn advance(&mut self) -> bool {
+        if self.remaining == 0 || !self.tail.advance() {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+
+    fn token(&self) -> &Token {
+        self.tail.token()
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        self.tail.token_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tantivy::tokenizer::{SimpleTokenizer, TextAnalyzer, Token};
+    use crate::tokenizer::core::limit::MaxTokensFilter;
+
+    #[test]
+    fn test_max_tokens() {
+        let mut text_analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
+            .filter(MaxTokensFilter::limit(3))
+            .build();
+        let mut tokens: Vec<String> = vec![];
+        text_analyzer
+            .token_stream("a b c d e f")
+            .process(&mut |token: &Token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, vec!["a", "b", "c"]);
+
+        let mut tokens: Vec<String> = vec![];
+        text_analyzer
+            .token_stream("a b")
+            .process(&mut |token: &Token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, vec!["a", "b"]);
+    }
+}
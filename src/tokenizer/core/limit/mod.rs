@@ -0,0 +1,4 @@
+mod grapheme;
+mod max_tokens;
+
+pub use {grapheme::GraphemeLimitFilter, max_tokens::MaxTokensFilter};
@@ -1,5 +1,9 @@
 pub mod cangjie;
+pub mod cjk;
+pub mod html_strip;
 pub mod icu;
+pub mod limit;
+pub mod pinyin;
 mod utils;
 
 pub use utils::TokenUtils;
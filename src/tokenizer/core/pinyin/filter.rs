@@ -0,0 +1,83 @@
+use super::PinyinTokenStream;
+use tantivy::tokenizer::{TokenFilter, Tokenizer};
+
+/// `PinyinFilter` keeps every token produced by the wrapped tokenizer.
+/// For a token made only of Han characters, it also emits the token's full pinyin
+/// and pinyin initials at the same position, e.g. `北京` -> `北京`, `beijing`, `bj`.
+#[derive(Clone, Debug, Default)]
+pub struct PinyinFilter;
+
+impl TokenFilter for PinyinFilter {
+    type Tokenizer<T: Tokenizer> = PinyinFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> PinyinFilterWrapper<T> {
+        PinyinFilterWrapper { inner: tokenizer }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PinyinFilterWrapper<T> {
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for PinyinFilterWrapper<T> {
+    type TokenStream<'a> = PinyinTokenStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        PinyinTokenStream::new(self.inner.token_stream(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use jieba_rs::Jieba;
+    use tantivy::tokenizer::{LowerCaser, TextAnalyzer, Token};
+    use crate::tokenizer::core::cangjie::{CangjieOption, CangjieTokenizer};
+    use crate::tokenizer::core::pinyin::PinyinFilter;
+    use crate::tokenizer::core::tests::assert_token;
+
+    #[test]
+    fn test_pinyin_tokens_share_position() {
+        let tokens = token_stream_helper("北京欢迎你");
+        assert_eq!(tokens.len(), 9);
+        assert_token(&tokens[0], 0, "北京", 0, 6);
+        assert_token(&tokens[1], 0, "beijing", 0, 6);
+        assert_token(&tokens[2], 0, "bj", 0, 6);
+        assert_token(&tokens[3], 1, "欢迎", 6, 12);
+        assert_token(&tokens[4], 1, "huanying", 6, 12);
+        assert_token(&tokens[5], 1, "hy", 6, 12);
+        assert_token(&tokens[6], 2, "你", 12, 15);
+        assert_token(&tokens[7], 2, "ni", 12, 15);
+        assert_token(&tokens[8], 2, "n", 12, 15);
+    }
+
+    #[test]
+    fn test_non_han_tokens_unchanged() {
+        let tokens = token_stream_helper("Hello 北京");
+        assert_eq!(tokens.len(), 4);
+        assert_token(&tokens[0], 0, "hello", 0, 5);
+        assert_token(&tokens[1], 1, "北京", 6, 12);
+        assert_token(&tokens[2], 1, "beijing", 6, 12);
+        assert_token(&tokens[3], 1, "bj", 6, 12);
+    }
+
+    fn token_stream_helper(text: &str) -> Vec<Token> {
+        let tokenizer = CangjieTokenizer {
+            worker: Arc::new(Jieba::default()),
+            option: CangjieOption::Default { hmm: false },
+            max_tokens: None,
+        };
+        let mut text_analyzer = TextAnalyzer::builder(tokenizer)
+            .filter(PinyinFilter)
+            .filter(LowerCaser)
+            .build();
+        let mut token_stream = text_analyzer.token_stream(text);
+        let mut tokens: Vec<Token> = vec![];
+        let mut add_token = |token: &Token| {
+            tokens.push(token.clone());
+        };
+        token_stream.process(&mut add_token);
+        tokens
+    }
+}
@@ -0,0 +1,4 @@
+mod filter;
+mod stream;
+
+pub use {filter::PinyinFilter, filter::PinyinFilterWrapper, stream::PinyinTokenStream};
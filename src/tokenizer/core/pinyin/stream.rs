@@ -0,0 +1,64 @@
+use ::pinyin::ToPinyin;
+use tantivy::tokenizer::{Token, TokenStream};
+
+pub struct PinyinTokenStream<T> {
+    tail: T,
+    // Pinyin variants of the current token, popped from the back.
+    pending: Vec<String>,
+    token: Token,
+}
+
+impl<T> PinyinTokenStream<T> {
+    pub fn new(tail: T) -> Self {
+        PinyinTokenStream {
+            tail,
+            pending: Vec::new(),
+            token: Token::default(),
+        }
+    }
+
+    /// Returns `(full_pinyin, initials)` if every char in `text` has a pinyin reading.
+    fn convert(text: &str) -> Option<(String, String)> {
+        let mut full = String::new();
+        let mut initials = String::new();
+        for c in text.chars() {
+            let pinyin = c.to_pinyin()?;
+            full.push_str(pinyin.plain());
+            initials.push_str(pinyin.first_letter());
+        }
+        if full.is_empty() {
+            None
+        } else {
+            Some((full, initials))
+        }
+    }
+}
+
+impl<T: TokenStream> TokenStream for PinyinTokenStream<T> {
+    fn advance(&mut self) -> bool {
+        // Pinyin tokens reuse offsets and position of the original token.
+        if let Some(text) = self.pending.pop() {
+            self.token.text = text;
+            return true;
+        }
+        if !self.tail.advance() {
+            return false;
+        }
+        self.token = self.tail.token().clone();
+        if let Some((full, initials)) = Self::convert(&self.token.text) {
+            if initials != full {
+                self.pending.push(initials);
+            }
+            self.pending.push(full);
+        }
+        true
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
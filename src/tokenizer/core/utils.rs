@@ -13,4 +13,38 @@ impl TokenUtils {
             .collect();
         return processed;
     }
+
+    /// Split `text` into chunks of about `chunk_size` bytes, each ending right after whitespace
+    /// or a sentence punctuation. Jieba never joins such a char with its neighbours, so chunks
+    /// are cut the same as the whole text. A chunk without such a boundary grows until the next one.
+    pub fn split_chunks(text: &str, chunk_size: usize) -> impl Iterator<Item = &str> {
+        let mut rest = text;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let mut end = rest.len();
+            if end > chunk_size {
+                let mut window_end = chunk_size;
+                while !rest.is_char_boundary(window_end) {
+                    window_end -= 1;
+                }
+                let is_boundary = |(_, c): &(usize, char)| Self::is_chunk_boundary(*c);
+                end = match rest[..window_end].char_indices().rev().find(is_boundary) {
+                    Some((idx, c)) => idx + c.len_utf8(),
+                    None => rest[window_end..]
+                        .char_indices()
+                        .find(is_boundary)
+                        .map_or(rest.len(), |(idx, c)| window_end + idx + c.len_utf8()),
+                };
+            }
+            let (chunk, tail) = rest.split_at(end);
+            rest = tail;
+            Some(chunk)
+        })
+    }
+
+    fn is_chunk_boundary(c: char) -> bool {
+        c.is_whitespace() || matches!(c, '。' | '！' | '？' | '；' | '，' | '、' | '!' | '?' | ';' | ',')
+    }
 }
\ No newline at end of file
@@ -1,15 +1,118 @@
 use super::*;
 use serde::{Deserialize, Serialize};
+use tantivy::schema::IndexRecordOption;
+
+/// Value type of a column, numeric, date and bool columns are stored as fast fields
+/// and can be used as range or term filters. Json column is queried by path, such as `attrs.color`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnType {
+    #[default]
+    #[serde(rename = "text")]
+    Text,
+    #[serde(rename = "u64")]
+    U64,
+    #[serde(rename = "i64")]
+    I64,
+    #[serde(rename = "f64")]
+    F64,
+    #[serde(rename = "date")]
+    Date,
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "json")]
+    Json,
+}
+
+/// What is recorded in the inverted index of a text or json column.
+/// `basic` only keeps doc ids, enough for term bitmap queries, `freqs` adds term frequencies
+/// for BM25 scoring, `positions` is required by phrase queries.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RecordOption {
+    #[serde(rename = "basic")]
+    Basic,
+    #[serde(rename = "freqs")]
+    Freqs,
+    #[default]
+    #[serde(rename = "positions")]
+    Positions,
+}
+
+impl RecordOption {
+    pub fn to_index_record_option(&self) -> IndexRecordOption {
+        match self {
+            RecordOption::Basic => IndexRecordOption::Basic,
+            RecordOption::Freqs => IndexRecordOption::WithFreqs,
+            RecordOption::Positions => IndexRecordOption::WithFreqsAndPositions,
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(deny_unknown_fields)]
+#[serde(try_from = "RawColumn")]
 pub struct Column {
-    tokenizer: Tokenizer,
+    #[serde(rename = "type")]
+    column_type: ColumnType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tokenizer: Option<Tokenizer>,
+    record: RecordOption,
+    fieldnorms: bool,
+}
+
+// Column before validation, text column needs a tokenizer, json column may have one
+// for its text values, other columns can't have one.
+// `record` and `fieldnorms` are only for text and json column.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawColumn {
+    #[serde(default, rename = "type")]
+    column_type: ColumnType,
+    #[serde(default)]
+    tokenizer: Option<Tokenizer>,
+    #[serde(default)]
+    record: Option<RecordOption>,
+    #[serde(default)]
+    fieldnorms: Option<bool>,
+}
+
+impl TryFrom<RawColumn> for Column {
+    type Error = String;
+
+    fn try_from(raw: RawColumn) -> Result<Self, Self::Error> {
+        let is_text = matches!(raw.column_type, ColumnType::Text | ColumnType::Json);
+        if !is_text && (raw.record.is_some() || raw.fieldnorms.is_some()) {
+            return Err(format!("Column with type {:?} can't have `record` or `fieldnorms`.", raw.column_type));
+        }
+        match (raw.column_type, &raw.tokenizer) {
+            (ColumnType::Text, None) => Err("Text column requires `tokenizer`.".to_string()),
+            (ColumnType::Text, Some(_)) | (ColumnType::Json, _) | (_, None) => Ok(Column {
+                column_type: raw.column_type,
+                tokenizer: raw.tokenizer,
+                record: raw.record.unwrap_or_default(),
+                fieldnorms: raw.fieldnorms.unwrap_or(true),
+            }),
+            (column_type, Some(_)) => Err(format!("Column with type {:?} can't have `tokenizer`.", column_type)),
+        }
+    }
 }
 
 impl Column {
-    pub fn get_tokenizer(&self) -> &Tokenizer {
-        &self.tokenizer
+    pub fn get_column_type(&self) -> ColumnType {
+        self.column_type
+    }
+
+    /// Only text and json column have tokenizer.
+    pub fn get_tokenizer(&self) -> Option<&Tokenizer> {
+        self.tokenizer.as_ref()
+    }
+
+    /// Default is `positions`.
+    pub fn get_record_option(&self) -> RecordOption {
+        self.record
+    }
+
+    /// Whether field norms are kept for BM25 length normalization, default is true.
+    pub fn has_fieldnorms(&self) -> bool {
+        self.fieldnorms
     }
 }
 
@@ -21,7 +124,8 @@ mod tests {
     fn test_deserialize() {
         let json_str = r#"{ "tokenizer": { "type": "default", "store_doc": true } }"#;
         let result: Column = serde_json::from_str(json_str).unwrap();
-        if let Tokenizer::Default { store_doc } = result.tokenizer {
+        assert_eq!(result.get_column_type(), ColumnType::Text);
+        if let Some(Tokenizer::Default { store_doc, .. }) = result.tokenizer {
             assert!(store_doc)
         } else {
             panic!("Error happened when parse `column` json parameter")
@@ -37,4 +141,68 @@ mod tests {
         let result_2: Result<Column, _> = serde_json::from_str(invalid_json_str_2);
         assert!(result_2.is_err());
     }
+
+    #[test]
+    fn test_deserialize_column_type() {
+        for (type_name, column_type) in [
+            ("u64", ColumnType::U64),
+            ("i64", ColumnType::I64),
+            ("f64", ColumnType::F64),
+            ("date", ColumnType::Date),
+            ("bool", ColumnType::Bool),
+        ] {
+            let json_str = format!(r#"{{ "type": "{}" }}"#, type_name);
+            let column: Column = serde_json::from_str(&json_str).unwrap();
+            assert_eq!(column.get_column_type(), column_type);
+            assert!(column.get_tokenizer().is_none());
+        }
+
+        let text: Column =
+            serde_json::from_str(r#"{ "type": "text", "tokenizer": { "type": "raw" } }"#).unwrap();
+        assert_eq!(text.get_column_type(), ColumnType::Text);
+        assert!(text.get_tokenizer().is_some());
+
+        // json column tokenizer is optional
+        let json: Column = serde_json::from_str(r#"{ "type": "json" }"#).unwrap();
+        assert_eq!(json.get_column_type(), ColumnType::Json);
+        assert!(json.get_tokenizer().is_none());
+        let json: Column =
+            serde_json::from_str(r#"{ "type": "json", "tokenizer": { "type": "simple" } }"#).unwrap();
+        assert!(json.get_tokenizer().is_some());
+
+        // text column without tokenizer
+        assert!(serde_json::from_str::<Column>(r#"{ "type": "text" }"#).is_err());
+        // typed column with tokenizer
+        assert!(serde_json::from_str::<Column>(r#"{ "type": "u64", "tokenizer": { "type": "raw" } }"#).is_err());
+        // unknown type
+        assert!(serde_json::from_str::<Column>(r#"{ "type": "decimal" }"#).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_record_option() {
+        let column: Column = serde_json::from_str(r#"{ "tokenizer": { "type": "default" } }"#).unwrap();
+        assert_eq!(column.get_record_option(), RecordOption::Positions);
+        assert!(column.has_fieldnorms());
+
+        for (record, record_option) in [
+            ("basic", RecordOption::Basic),
+            ("freqs", RecordOption::Freqs),
+            ("positions", RecordOption::Positions),
+        ] {
+            let json_str = format!(r#"{{ "tokenizer": {{ "type": "raw" }}, "record": "{}", "fieldnorms": false }}"#, record);
+            let column: Column = serde_json::from_str(&json_str).unwrap();
+            assert_eq!(column.get_record_option(), record_option);
+            assert!(!column.has_fieldnorms());
+        }
+        assert_eq!(RecordOption::Basic.to_index_record_option(), IndexRecordOption::Basic);
+
+        let json: Column = serde_json::from_str(r#"{ "type": "json", "record": "freqs" }"#).unwrap();
+        assert_eq!(json.get_record_option(), RecordOption::Freqs);
+
+        // unknown record option
+        assert!(serde_json::from_str::<Column>(r#"{ "tokenizer": { "type": "raw" }, "record": "offsets" }"#).is_err());
+        // typed column with record option or fieldnorms
+        assert!(serde_json::from_str::<Column>(r#"{ "type": "u64", "record": "basic" }"#).is_err());
+        assert!(serde_json::from_str::<Column>(r#"{ "type": "f64", "fieldnorms": false }"#).is_err());
+    }
 }
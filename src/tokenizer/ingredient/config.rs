@@ -3,6 +3,15 @@ use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
+    // Reserved key, it can't be used as a column name.
+    #[serde(default, rename = "__writer__")]
+    writer: WriterSettings,
+    // Reserved key, it can't be used as a column name.
+    #[serde(default, rename = "__docstore__")]
+    docstore: DocstoreSettings,
+    // Reserved key, it can't be used as a column name.
+    #[serde(default, rename = "__reader__")]
+    reader: ReaderSettings,
     #[serde(flatten)]
     columns: std::collections::HashMap<String, Column>,
 }
@@ -11,6 +20,18 @@ impl Config {
     pub fn get_columns(&self) -> &std::collections::HashMap<String, Column> {
         &self.columns
     }
+
+    pub fn get_writer_settings(&self) -> &WriterSettings {
+        &self.writer
+    }
+
+    pub fn get_docstore_settings(&self) -> &DocstoreSettings {
+        &self.docstore
+    }
+
+    pub fn get_reader_settings(&self) -> &ReaderSettings {
+        &self.reader
+    }
 }
 
 #[cfg(test)]
@@ -32,7 +53,7 @@ mod tests {
         assert!(!columns.contains_key("col3"));
 
         if let Some(column) = config.get_columns().get("col1") {
-            if let Tokenizer::Default { store_doc, .. } = column.get_tokenizer() {
+            if let Some(Tokenizer::Default { store_doc, .. }) = column.get_tokenizer() {
                 assert!(!store_doc);
             } else {
                 panic!("Error happened when parse `config` json parameter!")
@@ -42,7 +63,7 @@ mod tests {
         }
 
         if let Some(column) = config.get_columns().get("col2") {
-            if let Tokenizer::Simple { case_sensitive, .. } = column.get_tokenizer() {
+            if let Some(Tokenizer::Simple { case_sensitive, .. }) = column.get_tokenizer() {
                 assert!(case_sensitive);
             } else {
                 panic!("Error happened when parse `config` json parameter!")
@@ -51,4 +72,71 @@ mod tests {
             panic!("`col2` not found!")
         }
     }
+
+    #[test]
+    fn test_deserialize_writer_settings() {
+        let json_str = r#"
+            {
+                "__writer__": { "heap_size": 134217728, "merge_policy": { "type": "no_merge" } },
+                "col1": { "tokenizer": { "type": "default" } }
+            }
+        "#;
+        let config: Config = serde_json::from_str(json_str).unwrap();
+        assert_eq!(config.get_columns().len(), 1);
+        assert!(config.get_columns().contains_key("col1"));
+        assert!(!config.get_columns().contains_key("__writer__"));
+        let writer = config.get_writer_settings();
+        assert_eq!(writer.heap_size, 134217728);
+        assert_eq!(writer.num_threads, 2);
+        assert_eq!(writer.merge_policy, MergePolicy::NoMerge);
+
+        // Use default writer settings when `__writer__` not given.
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.get_writer_settings(), &WriterSettings::default());
+
+        // invalid writer settings
+        let invalid_json_str = r#"{ "__writer__": { "num_threads": 0 } }"#;
+        let result: Result<Config, _> = serde_json::from_str(invalid_json_str);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_deserialize_docstore_settings() {
+        let json_str = r#"
+            {
+                "__docstore__": { "compressor": { "type": "zstd", "level": 3 } },
+                "col1": { "tokenizer": { "type": "default", "store_doc": true } }
+            }
+        "#;
+        let config: Config = serde_json::from_str(json_str).unwrap();
+        assert_eq!(config.get_columns().len(), 1);
+        assert!(!config.get_columns().contains_key("__docstore__"));
+        assert_eq!(config.get_docstore_settings().compressor, DocstoreCompressor::Zstd { level: Some(3) });
+        assert_eq!(config.get_docstore_settings().block_size, 16_384);
+
+        // Use default docstore settings when `__docstore__` not given.
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.get_docstore_settings(), &DocstoreSettings::default());
+    }
+
+    #[test]
+    fn test_deserialize_reader_settings() {
+        let json_str = r#"
+            {
+                "__reader__": { "reload_policy": "manual" },
+                "col1": { "tokenizer": { "type": "default" } }
+            }
+        "#;
+        let config: Config = serde_json::from_str(json_str).unwrap();
+        assert_eq!(config.get_columns().len(), 1);
+        assert!(!config.get_columns().contains_key("__reader__"));
+        assert_eq!(config.get_reader_settings().reload_policy, ReloadPolicy::Manual);
+
+        // Use default reader settings when `__reader__` not given.
+        let config: Config = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.get_reader_settings(), &ReaderSettings::default());
+
+        // invalid reader settings
+        assert!(serde_json::from_str::<Config>(r#"{ "__reader__": { "reload_policy": "never" } }"#).is_err());
+    }
 }
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use tantivy::store::{Compressor, ZstdCompressor};
+use tantivy::IndexSettings;
+
+/// Settings of tantivy document store, used by columns with `store_doc: true`,
+/// stored under the reserved `__docstore__` key of index json parameter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DocstoreSettings {
+    #[serde(default)]
+    pub compressor: DocstoreCompressor,
+    #[serde(default = "default_block_size", deserialize_with = "block_size_validator")]
+    pub block_size: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+#[serde(tag = "type")]
+pub enum DocstoreCompressor {
+    #[serde(rename = "none")]
+    None,
+    #[default]
+    #[serde(rename = "lz4")]
+    Lz4,
+    #[serde(rename = "zstd")]
+    Zstd {
+        #[serde(default, skip_serializing_if = "Option::is_none", deserialize_with = "zstd_level_validator")]
+        level: Option<i32>,
+    },
+}
+
+fn default_block_size() -> usize {
+    16_384
+}
+
+fn block_size_validator<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let block_size = usize::deserialize(deserializer)?;
+    if block_size == 0 {
+        return Err(serde::de::Error::custom(
+            "Invalid value for block_size: 0. Expected at least 1.",
+        ));
+    }
+    Ok(block_size)
+}
+
+fn zstd_level_validator<'de, D>(deserializer: D) -> Result<Option<i32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let level = i32::deserialize(deserializer)?;
+    if !(1..=22).contains(&level) {
+        return Err(serde::de::Error::custom(format!(
+            "Invalid value for zstd level: {}. Expected 1 to 22.",
+            level
+        )));
+    }
+    Ok(Some(level))
+}
+
+impl Default for DocstoreSettings {
+    fn default() -> Self {
+        Self {
+            compressor: DocstoreCompressor::default(),
+            block_size: default_block_size(),
+        }
+    }
+}
+
+impl DocstoreSettings {
+    pub fn to_index_settings(&self) -> IndexSettings {
+        let docstore_compression = match &self.compressor {
+            DocstoreCompressor::None => Compressor::None,
+            DocstoreCompressor::Lz4 => Compressor::Lz4,
+            DocstoreCompressor::Zstd { level } => Compressor::Zstd(ZstdCompressor { compression_level: *level }),
+        };
+        IndexSettings {
+            docstore_compression,
+            docstore_blocksize: self.block_size,
+            ..IndexSettings::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_docstore_settings() {
+        let settings: DocstoreSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, DocstoreSettings::default());
+        assert_eq!(settings.to_index_settings().docstore_compression, Compressor::Lz4);
+
+        let settings: DocstoreSettings =
+            serde_json::from_str(r#"{ "compressor": { "type": "zstd", "level": 9 }, "block_size": 65536 }"#).unwrap();
+        assert_eq!(settings.compressor, DocstoreCompressor::Zstd { level: Some(9) });
+        let index_settings = settings.to_index_settings();
+        assert_eq!(index_settings.docstore_compression, Compressor::Zstd(ZstdCompressor { compression_level: Some(9) }));
+        assert_eq!(index_settings.docstore_blocksize, 65536);
+
+        let settings: DocstoreSettings = serde_json::from_str(r#"{ "compressor": { "type": "zstd" } }"#).unwrap();
+        assert_eq!(settings.compressor, DocstoreCompressor::Zstd { level: None });
+        let settings: DocstoreSettings = serde_json::from_str(r#"{ "compressor": { "type": "none" } }"#).unwrap();
+        assert_eq!(settings.to_index_settings().docstore_compression, Compressor::None);
+
+        // invalid values
+        assert!(serde_json::from_str::<DocstoreSettings>(r#"{ "compressor": { "type": "brotli" } }"#).is_err());
+        assert!(serde_json::from_str::<DocstoreSettings>(r#"{ "compressor": { "type": "zstd", "level": 30 } }"#).is_err());
+        assert!(serde_json::from_str::<DocstoreSettings>(r#"{ "block_size": 0 }"#).is_err());
+        assert!(serde_json::from_str::<DocstoreSettings>(r#"{ "blocksize": 1024 }"#).is_err());
+    }
+}
@@ -1,7 +1,13 @@
 mod column;
 mod config;
+mod docstore;
+mod reader;
 mod tokenizer;
+mod writer;
 
-pub use column::Column;
+pub use column::{Column, ColumnType, RecordOption};
 pub use config::Config;
+pub use docstore::{DocstoreCompressor, DocstoreSettings};
+pub use reader::{ReaderSettings, ReloadPolicy};
 pub use tokenizer::Tokenizer;
+pub use writer::{MergePolicy, WriterSettings};
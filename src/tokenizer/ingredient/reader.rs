@@ -0,0 +1,51 @@
+use serde::{Deserialize, Serialize};
+use tantivy::ReloadPolicy as TantivyReloadPolicy;
+
+/// Settings used to create tantivy `IndexReader`, stored under the
+/// reserved `__reader__` key of index json parameter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ReaderSettings {
+    #[serde(default)]
+    pub reload_policy: ReloadPolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum ReloadPolicy {
+    // Reload searcher when a commit is detected.
+    #[default]
+    #[serde(rename = "on_commit")]
+    OnCommit,
+    // Only reload searcher when index is committed through this library.
+    #[serde(rename = "manual")]
+    Manual,
+}
+
+impl ReaderSettings {
+    pub fn to_reload_policy(&self) -> TantivyReloadPolicy {
+        match self.reload_policy {
+            ReloadPolicy::OnCommit => TantivyReloadPolicy::OnCommit,
+            ReloadPolicy::Manual => TantivyReloadPolicy::Manual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_reader_settings() {
+        let settings: ReaderSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, ReaderSettings::default());
+        assert!(matches!(settings.to_reload_policy(), TantivyReloadPolicy::OnCommit));
+
+        let settings: ReaderSettings = serde_json::from_str(r#"{ "reload_policy": "manual" }"#).unwrap();
+        assert_eq!(settings.reload_policy, ReloadPolicy::Manual);
+        assert!(matches!(settings.to_reload_policy(), TantivyReloadPolicy::Manual));
+
+        // invalid values
+        assert!(serde_json::from_str::<ReaderSettings>(r#"{ "reload_policy": "on_demand" }"#).is_err());
+        assert!(serde_json::from_str::<ReaderSettings>(r#"{ "reload": "manual" }"#).is_err());
+    }
+}
@@ -11,11 +11,19 @@ pub enum Tokenizer {
     Default {
         #[serde(default)]
         store_doc: bool,
+        #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
     },
     #[serde(rename = "raw")]
     Raw {
         #[serde(default)]
         store_doc: bool,
+        #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
     },
     #[serde(rename = "simple")]
     Simple {
@@ -23,9 +31,15 @@ pub enum Tokenizer {
         stop_word_filters: Vec<String>,
         #[serde(default)]
         store_doc: bool,
+        #[serde(default)]
+        strip_html: bool,
         #[serde(default = "default_length_limit")]
         length_limit: usize,
         #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
         case_sensitive: bool,
     },
     #[serde(rename = "stem")]
@@ -36,9 +50,15 @@ pub enum Tokenizer {
         stem_languages: Vec<String>,
         #[serde(default)]
         store_doc: bool,
+        #[serde(default)]
+        strip_html: bool,
         #[serde(default = "default_length_limit")]
         length_limit: usize,
         #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
         case_sensitive: bool,
     },
     #[serde(rename = "whitespace")]
@@ -47,9 +67,15 @@ pub enum Tokenizer {
         stop_word_filters: Vec<String>,
         #[serde(default)]
         store_doc: bool,
+        #[serde(default)]
+        strip_html: bool,
         #[serde(default = "default_length_limit")]
         length_limit: usize,
         #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
         case_sensitive: bool,
     },
     #[serde(rename = "ngram")]
@@ -64,9 +90,15 @@ pub enum Tokenizer {
         stop_word_filters: Vec<String>,
         #[serde(default)]
         store_doc: bool,
+        #[serde(default)]
+        strip_html: bool,
         #[serde(default = "default_length_limit")]
         length_limit: usize,
         #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
         case_sensitive: bool,
     },
     #[serde(rename = "chinese")]
@@ -84,8 +116,35 @@ pub enum Tokenizer {
         #[serde(default)]
         hmm: bool,
         #[serde(default)]
+        pinyin: bool,
+        #[serde(default, deserialize_with = "stop_word_filters_validator")]
+        stop_word_filters: Vec<String>,
+        #[serde(default)]
         store_doc: bool,
         #[serde(default)]
+        strip_html: bool,
+        #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    #[serde(rename = "cjk")]
+    Cjk {
+        #[serde(default, deserialize_with = "stop_word_filters_validator")]
+        stop_word_filters: Vec<String>,
+        #[serde(default)]
+        store_doc: bool,
+        #[serde(default)]
+        strip_html: bool,
+        #[serde(default = "default_length_limit")]
+        length_limit: usize,
+        #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
         case_sensitive: bool,
     },
     #[serde(rename = "icu")]
@@ -102,6 +161,12 @@ pub enum Tokenizer {
         #[serde(default)]
         store_doc: bool,
         #[serde(default)]
+        strip_html: bool,
+        #[serde(default)]
+        char_length_limit: Option<usize>,
+        #[serde(default)]
+        max_tokens_per_doc: Option<usize>,
+        #[serde(default)]
         case_sensitive: bool,
     },
 }
@@ -217,8 +282,20 @@ mod tests {
         let json_str = r#"{"type": "default", "store_doc": true}"#;
         let tokenizer: Tokenizer = serde_json::from_str(json_str).unwrap();
         match tokenizer {
-            Tokenizer::Default { store_doc } => {
+            Tokenizer::Default { store_doc, char_length_limit, max_tokens_per_doc } => {
                 assert!(store_doc);
+                assert_eq!(char_length_limit, None);
+                assert_eq!(max_tokens_per_doc, None);
+            }
+            _ => panic!("Unexpected variant"),
+        }
+        let json_str = r#"{"type": "default", "char_length_limit": 20, "max_tokens_per_doc": 1000}"#;
+        let tokenizer: Tokenizer = serde_json::from_str(json_str).unwrap();
+        match tokenizer {
+            Tokenizer::Default { store_doc, char_length_limit, max_tokens_per_doc } => {
+                assert!(!store_doc);
+                assert_eq!(char_length_limit, Some(20));
+                assert_eq!(max_tokens_per_doc, Some(1000));
             }
             _ => panic!("Unexpected variant"),
         }
@@ -243,8 +320,9 @@ mod tests {
         let json_str = r#"{"type": "raw", "store_doc": false}"#;
         let tokenizer: Tokenizer = serde_json::from_str(json_str).unwrap();
         match tokenizer {
-            Tokenizer::Raw { store_doc } => {
+            Tokenizer::Raw { store_doc, char_length_limit, .. } => {
                 assert!(!store_doc);
+                assert_eq!(char_length_limit, None);
             }
             _ => panic!("Unexpected variant"),
         }
@@ -270,7 +348,10 @@ mod tests {
                 "type": "simple",
                 "stop_word_filters": ["english", "german"],
                 "store_doc": true,
+                "strip_html": true,
                 "length_limit": 50,
+                "char_length_limit": 20,
+                "max_tokens_per_doc": 1000,
                 "case_sensitive": false
             }
         "#;
@@ -279,11 +360,17 @@ mod tests {
             Tokenizer::Simple {
                 stop_word_filters,
                 store_doc,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
             } => {
+                assert_eq!(char_length_limit, Some(20));
+                assert_eq!(max_tokens_per_doc, Some(1000));
                 assert_eq!(stop_word_filters, vec!["english", "german"]);
                 assert!(store_doc);
+                assert!(strip_html);
                 assert_eq!(length_limit, 50);
                 assert!(!case_sensitive);
             }
@@ -307,7 +394,7 @@ mod tests {
         let invalid_json_2 = r#"
             {
                 "type": "simple",
-                "stop_word_filters": ["chinese", "german"],
+                "stop_word_filters": ["klingon", "german"],
                 "store_doc": true,
                 "length_limit": 50,
                 "case_sensitive": false
@@ -350,12 +437,18 @@ mod tests {
                 stop_word_filters,
                 stem_languages,
                 store_doc,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
             } => {
+                assert_eq!(char_length_limit, None);
+                assert_eq!(max_tokens_per_doc, None);
                 assert_eq!(stop_word_filters, vec! ["english", "german"]);
                 assert_eq!(stem_languages, vec!["english", "french"]);
                 assert!(store_doc);
+                assert!(!strip_html);
                 assert_eq!(length_limit, 60);
                 assert!(!case_sensitive);
             }
@@ -380,8 +473,8 @@ mod tests {
         let invalid_json_2 = r#"
             {
                 "type": "stem",
-                "stop_word_filters": ["english", "chinese"],
-                "stem_languages": ["chinese", "french"],
+                "stop_word_filters": ["english", "klingon"],
+                "stem_languages": ["latin", "french"],
                 "store_doc": true,
                 "length_limit": 60,
                 "case_sensitive": false
@@ -422,11 +515,17 @@ mod tests {
             Tokenizer::Whitespace {
                 stop_word_filters,
                 store_doc,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
             } => {
+                assert_eq!(char_length_limit, None);
+                assert_eq!(max_tokens_per_doc, None);
                 assert_eq!(stop_word_filters, vec!["english", "german"]);
                 assert!(store_doc);
+                assert!(!strip_html);
                 assert_eq!(length_limit, 70);
                 assert!(!case_sensitive);
             }
@@ -495,14 +594,20 @@ mod tests {
                 prefix_only,
                 stop_word_filters,
                 store_doc,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
             } => {
+                assert_eq!(char_length_limit, None);
+                assert_eq!(max_tokens_per_doc, None);
                 assert_eq!(min_gram, 2);
                 assert_eq!(max_gram, 4);
                 assert!(prefix_only);
                 assert_eq!(stop_word_filters, vec!["english", "german"]);
                 assert!(store_doc);
+                assert!(!strip_html);
                 assert_eq!(length_limit, 80);
                 assert!(case_sensitive);
             }
@@ -532,7 +637,7 @@ mod tests {
                 "min_gram": -1,
                 "max_gram": 4,
                 "prefix_only": true,
-                "stop_word_filters": ["chinese", "german"],
+                "stop_word_filters": ["klingon", "german"],
                 "store_doc": true,
                 "length_limit": 80,
                 "case_sensitive": true
@@ -566,6 +671,7 @@ mod tests {
                 "jieba": "default",
                 "mode": "search",
                 "hmm": true,
+                "pinyin": true,
                 "store_doc": false,
                 "case_sensitive": false
             }
@@ -576,13 +682,23 @@ mod tests {
                 jieba,
                 mode,
                 hmm,
+                pinyin,
+                stop_word_filters,
                 store_doc,
+                strip_html,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
             } => {
+                assert_eq!(char_length_limit, None);
+                assert_eq!(max_tokens_per_doc, None);
                 assert_eq!(jieba, "default");
                 assert_eq!(mode, "search");
                 assert!(hmm);
+                assert!(pinyin);
+                assert!(stop_word_filters.is_empty());
                 assert!(!store_doc);
+                assert!(!strip_html);
                 assert!(!case_sensitive);
             }
             _ => panic!("Unexpected variant"),
@@ -629,9 +745,60 @@ mod tests {
         "#;
         let invalid_tokenizer_3: Result<Tokenizer, _> = serde_json::from_str(invalid_json_3);
         assert!(invalid_tokenizer_3.is_err());
+
+        // pinyin is disabled by default
+        let json_str_4 = r#"{"type": "chinese"}"#;
+        match serde_json::from_str::<Tokenizer>(json_str_4).unwrap() {
+            Tokenizer::Chinese { pinyin, .. } => assert!(!pinyin),
+            _ => panic!("Unexpected variant"),
+        }
     }
 
 
+    #[test]
+    fn test_deserialize_cjk() {
+        let json_str = r#"
+            {
+                "type": "cjk",
+                "stop_word_filters": ["english"],
+                "store_doc": true,
+                "length_limit": 30,
+                "case_sensitive": false
+            }
+        "#;
+        let tokenizer: Tokenizer = serde_json::from_str(json_str).unwrap();
+        match tokenizer {
+            Tokenizer::Cjk {
+                stop_word_filters,
+                store_doc,
+                strip_html,
+                length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
+                case_sensitive,
+            } => {
+                assert_eq!(char_length_limit, None);
+                assert_eq!(max_tokens_per_doc, None);
+                assert_eq!(stop_word_filters, vec!["english"]);
+                assert!(store_doc);
+                assert!(!strip_html);
+                assert_eq!(length_limit, 30);
+                assert!(!case_sensitive);
+            }
+            _ => panic!("Unexpected variant"),
+        }
+
+        // invalid value type
+        let invalid_json_1 = r#"{"type": "cjk", "length_limit": "30"}"#;
+        let invalid_tokenizer_1: Result<Tokenizer, _> = serde_json::from_str(invalid_json_1);
+        assert!(invalid_tokenizer_1.is_err());
+
+        // invalid key
+        let invalid_json_2 = r#"{"type": "cjk", "mode": "search"}"#;
+        let invalid_tokenizer_2: Result<Tokenizer, _> = serde_json::from_str(invalid_json_2);
+        assert!(invalid_tokenizer_2.is_err());
+    }
+
     #[test]
     fn test_deserialize_icu() {
         let json_str = r#"
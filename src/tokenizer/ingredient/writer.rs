@@ -0,0 +1,215 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use tantivy::merge_policy::{LogMergePolicy, MergePolicy as TantivyMergePolicy, NoMergePolicy};
+
+/// Settings used to create tantivy `IndexWriter`, stored under the
+/// reserved `__writer__` key of index json parameter.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WriterSettings {
+    #[serde(default = "default_heap_size")]
+    pub heap_size: usize,
+    #[serde(default = "default_num_threads", deserialize_with = "num_threads_validator")]
+    pub num_threads: usize,
+    #[serde(default)]
+    pub merge_policy: MergePolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+#[serde(tag = "type")]
+pub enum MergePolicy {
+    #[serde(rename = "log")]
+    Log {
+        #[serde(default = "default_min_num_segments")]
+        min_num_segments: usize,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_docs_before_merge: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_layer_size: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        level_log_size: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        del_docs_ratio_before_merge: Option<f32>,
+    },
+    #[serde(rename = "no_merge", alias = "no-merge")]
+    NoMerge,
+}
+
+fn default_heap_size() -> usize {
+    1024 * 1024 * 64
+}
+
+fn default_num_threads() -> usize {
+    2
+}
+
+fn default_min_num_segments() -> usize {
+    5
+}
+
+fn num_threads_validator<'de, D>(deserializer: D) -> Result<usize, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let num_threads = usize::deserialize(deserializer)?;
+    if num_threads == 0 {
+        return Err(serde::de::Error::custom(
+            "Invalid value for num_threads: 0. Expected at least 1.",
+        ));
+    }
+    Ok(num_threads)
+}
+
+impl Default for WriterSettings {
+    fn default() -> Self {
+        Self {
+            heap_size: default_heap_size(),
+            num_threads: default_num_threads(),
+            merge_policy: MergePolicy::default(),
+        }
+    }
+}
+
+impl Default for MergePolicy {
+    fn default() -> Self {
+        MergePolicy::Log {
+            min_num_segments: default_min_num_segments(),
+            max_docs_before_merge: None,
+            min_layer_size: None,
+            level_log_size: None,
+            del_docs_ratio_before_merge: None,
+        }
+    }
+}
+
+impl WriterSettings {
+    /// Apply a writer json parameter on top of current settings.
+    /// Keys given in `override_json` replace the current ones, others are kept.
+    pub fn with_override(&self, override_json: &str) -> Result<WriterSettings, serde_json::Error> {
+        if override_json.trim().is_empty() {
+            return Ok(self.clone());
+        }
+        let overrides: serde_json::Value = serde_json::from_str(override_json)?;
+        let overrides = overrides.as_object().ok_or_else(|| {
+            <serde_json::Error as serde::de::Error>::custom("Writer json parameter should be an object.")
+        })?;
+        let mut current = serde_json::to_value(self)?;
+        if let Some(current) = current.as_object_mut() {
+            for (key, value) in overrides {
+                current.insert(key.clone(), value.clone());
+            }
+        }
+        serde_json::from_value(current)
+    }
+}
+
+impl MergePolicy {
+    pub fn to_merge_policy(&self) -> Box<dyn TantivyMergePolicy> {
+        match self {
+            MergePolicy::Log {
+                min_num_segments,
+                max_docs_before_merge,
+                min_layer_size,
+                level_log_size,
+                del_docs_ratio_before_merge,
+            } => {
+                let mut merge_policy = LogMergePolicy::default();
+                merge_policy.set_min_num_segments(*min_num_segments);
+                if let Some(max_docs_before_merge) = max_docs_before_merge {
+                    merge_policy.set_max_docs_before_merge(*max_docs_before_merge);
+                }
+                if let Some(min_layer_size) = min_layer_size {
+                    merge_policy.set_min_layer_size(*min_layer_size);
+                }
+                if let Some(level_log_size) = level_log_size {
+                    merge_policy.set_level_log_size(*level_log_size);
+                }
+                if let Some(del_docs_ratio_before_merge) = del_docs_ratio_before_merge {
+                    merge_policy.set_del_docs_ratio_before_merge(*del_docs_ratio_before_merge);
+                }
+                Box::new(merge_policy)
+            }
+            MergePolicy::NoMerge => Box::new(NoMergePolicy),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_default() {
+        let settings: WriterSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings, WriterSettings::default());
+        assert_eq!(settings.heap_size, 1024 * 1024 * 64);
+        assert_eq!(settings.num_threads, 2);
+        if let MergePolicy::Log { min_num_segments, max_docs_before_merge, .. } = settings.merge_policy {
+            assert_eq!(min_num_segments, 5);
+            assert!(max_docs_before_merge.is_none());
+        } else {
+            panic!("Default merge policy should be `log`")
+        }
+    }
+
+    #[test]
+    fn test_deserialize_settings() {
+        let json_str = r#"
+            {
+                "heap_size": 268435456,
+                "num_threads": 4,
+                "merge_policy": { "type": "log", "min_num_segments": 8, "level_log_size": 0.5 }
+            }
+        "#;
+        let settings: WriterSettings = serde_json::from_str(json_str).unwrap();
+        assert_eq!(settings.heap_size, 268435456);
+        assert_eq!(settings.num_threads, 4);
+        if let MergePolicy::Log { min_num_segments, level_log_size, .. } = settings.merge_policy {
+            assert_eq!(min_num_segments, 8);
+            assert_eq!(level_log_size, Some(0.5));
+        } else {
+            panic!("Error happened when parse `merge_policy` json parameter!")
+        }
+
+        let no_merge: WriterSettings =
+            serde_json::from_str(r#"{ "merge_policy": { "type": "no_merge" } }"#).unwrap();
+        assert_eq!(no_merge.merge_policy, MergePolicy::NoMerge);
+        let no_merge: WriterSettings =
+            serde_json::from_str(r#"{ "merge_policy": { "type": "no-merge" } }"#).unwrap();
+        assert_eq!(no_merge.merge_policy, MergePolicy::NoMerge);
+
+        // invalid num_threads
+        let result_1: Result<WriterSettings, _> = serde_json::from_str(r#"{ "num_threads": 0 }"#);
+        assert!(result_1.is_err());
+        // invalid merge policy type
+        let result_2: Result<WriterSettings, _> =
+            serde_json::from_str(r#"{ "merge_policy": { "type": "tiered" } }"#);
+        assert!(result_2.is_err());
+        // invalid key
+        let result_3: Result<WriterSettings, _> = serde_json::from_str(r#"{ "heap": 1 }"#);
+        assert!(result_3.is_err());
+    }
+
+    #[test]
+    fn test_with_override() {
+        let settings = WriterSettings {
+            heap_size: 1024 * 1024 * 128,
+            num_threads: 4,
+            merge_policy: MergePolicy::NoMerge,
+        };
+        assert_eq!(settings.with_override("").unwrap(), settings);
+
+        let overridden = settings.with_override(r#"{ "num_threads": 1 }"#).unwrap();
+        assert_eq!(overridden.heap_size, 1024 * 1024 * 128);
+        assert_eq!(overridden.num_threads, 1);
+        assert_eq!(overridden.merge_policy, MergePolicy::NoMerge);
+
+        let overridden = settings
+            .with_override(r#"{ "merge_policy": { "type": "log" } }"#)
+            .unwrap();
+        assert_eq!(overridden.merge_policy, MergePolicy::default());
+
+        assert!(settings.with_override(r#"{ "num_threads": 0 }"#).is_err());
+        assert!(settings.with_override("[1, 2]").is_err());
+    }
+}
@@ -1,23 +1,32 @@
 use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
-use tantivy::tokenizer::Language;
+use tantivy::tokenizer::{Language, StopWordFilter};
+use crate::tokenizer::stop_words;
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Copy, Clone)]
 pub enum SupportFilterLanguage {
+    Arabic,
     Danish,
     Dutch,
     English,
     Finnish,
     French,
     German,
+    Greek,
     Hungarian,
     Italian,
     Norwegian,
     Portuguese,
+    Romanian,
     Russian,
     Spanish,
     Swedish,
+    Tamil,
+    Turkish,
+    Chinese,
+    Japanese,
+    Korean,
 }
 
 impl FromStr for SupportFilterLanguage {
@@ -25,19 +34,27 @@ impl FromStr for SupportFilterLanguage {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
+            "arabic" => Ok(SupportFilterLanguage::Arabic),
             "danish" => Ok(SupportFilterLanguage::Danish),
             "dutch" => Ok(SupportFilterLanguage::Dutch),
             "english" => Ok(SupportFilterLanguage::English),
             "finnish" => Ok(SupportFilterLanguage::Finnish),
             "french" => Ok(SupportFilterLanguage::French),
             "german" => Ok(SupportFilterLanguage::German),
+            "greek" => Ok(SupportFilterLanguage::Greek),
             "hungarian" => Ok(SupportFilterLanguage::Hungarian),
             "italian" => Ok(SupportFilterLanguage::Italian),
             "norwegian" => Ok(SupportFilterLanguage::Norwegian),
             "portuguese" => Ok(SupportFilterLanguage::Portuguese),
+            "romanian" => Ok(SupportFilterLanguage::Romanian),
             "russian" => Ok(SupportFilterLanguage::Russian),
             "spanish" => Ok(SupportFilterLanguage::Spanish),
             "swedish" => Ok(SupportFilterLanguage::Swedish),
+            "tamil" => Ok(SupportFilterLanguage::Tamil),
+            "turkish" => Ok(SupportFilterLanguage::Turkish),
+            "chinese" => Ok(SupportFilterLanguage::Chinese),
+            "japanese" => Ok(SupportFilterLanguage::Japanese),
+            "korean" => Ok(SupportFilterLanguage::Korean),
             _ => Err(format!("Unknown filter language: {}", s)),
         }
     }
@@ -47,22 +64,49 @@ impl SupportFilterLanguage {
     #[allow(unreachable_patterns)]
     pub fn to_language(&self) -> Option<Language> {
         match self {
+            SupportFilterLanguage::Arabic => Some(Language::Arabic),
             SupportFilterLanguage::Danish => Some(Language::Danish),
             SupportFilterLanguage::Dutch => Some(Language::Dutch),
             SupportFilterLanguage::English => Some(Language::English),
             SupportFilterLanguage::Finnish => Some(Language::Finnish),
             SupportFilterLanguage::French => Some(Language::French),
             SupportFilterLanguage::German => Some(Language::German),
+            SupportFilterLanguage::Greek => Some(Language::Greek),
             SupportFilterLanguage::Hungarian => Some(Language::Hungarian),
             SupportFilterLanguage::Italian => Some(Language::Italian),
             SupportFilterLanguage::Norwegian => Some(Language::Norwegian),
             SupportFilterLanguage::Portuguese => Some(Language::Portuguese),
+            SupportFilterLanguage::Romanian => Some(Language::Romanian),
             SupportFilterLanguage::Russian => Some(Language::Russian),
             SupportFilterLanguage::Spanish => Some(Language::Spanish),
             SupportFilterLanguage::Swedish => Some(Language::Swedish),
+            SupportFilterLanguage::Tamil => Some(Language::Tamil),
+            SupportFilterLanguage::Turkish => Some(Language::Turkish),
+            _ => None,
+        }
+    }
+
+    /// Stop words shipped by this crate, for languages tantivy has no list for.
+    fn custom_stop_words(&self) -> Option<&'static [&'static str]> {
+        match self {
+            SupportFilterLanguage::Arabic => Some(stop_words::ARABIC),
+            SupportFilterLanguage::Greek => Some(stop_words::GREEK),
+            SupportFilterLanguage::Romanian => Some(stop_words::ROMANIAN),
+            SupportFilterLanguage::Tamil => Some(stop_words::TAMIL),
+            SupportFilterLanguage::Turkish => Some(stop_words::TURKISH),
+            SupportFilterLanguage::Chinese => Some(stop_words::CHINESE),
+            SupportFilterLanguage::Japanese => Some(stop_words::JAPANESE),
+            SupportFilterLanguage::Korean => Some(stop_words::KOREAN),
             _ => None,
         }
     }
+
+    pub fn to_stop_word_filter(&self) -> Option<StopWordFilter> {
+        match self.custom_stop_words() {
+            Some(words) => Some(StopWordFilter::remove(words.iter().map(|word| word.to_string()))),
+            None => self.to_language().and_then(StopWordFilter::new),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Copy, Clone)]
@@ -85,6 +129,11 @@ pub enum SupportLanguageAlgorithm {
     Swedish,
     Tamil,
     Turkish,
+    // Stemming is a no-op for CJK languages, they are accepted to keep
+    // `stem_languages` and `stop_word_filters` consistent.
+    Chinese,
+    Japanese,
+    Korean,
 }
 
 impl FromStr for SupportLanguageAlgorithm {
@@ -110,6 +159,9 @@ impl FromStr for SupportLanguageAlgorithm {
             "swedish" => Ok(SupportLanguageAlgorithm::Swedish),
             "tamil" => Ok(SupportLanguageAlgorithm::Tamil),
             "turkish" => Ok(SupportLanguageAlgorithm::Turkish),
+            "chinese" => Ok(SupportLanguageAlgorithm::Chinese),
+            "japanese" => Ok(SupportLanguageAlgorithm::Japanese),
+            "korean" => Ok(SupportLanguageAlgorithm::Korean),
             _ => Err(format!("Unsupported language algorithm: {}", s)),
         }
     }
@@ -141,3 +193,26 @@ impl SupportLanguageAlgorithm {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::{SupportFilterLanguage, SupportLanguageAlgorithm};
+
+    static ALL_LANGUAGES: &[&str] = &[
+        "arabic", "danish", "dutch", "english", "finnish", "french", "german", "greek", "hungarian",
+        "italian", "norwegian", "portuguese", "romanian", "russian", "spanish", "swedish", "tamil",
+        "turkish", "chinese", "japanese", "korean",
+    ];
+
+    #[test]
+    fn test_filter_and_stem_accept_same_languages() {
+        for language in ALL_LANGUAGES {
+            let filter_language = SupportFilterLanguage::from_str(language).unwrap();
+            assert!(filter_language.to_stop_word_filter().is_some(), "{}", language);
+            assert!(SupportLanguageAlgorithm::from_str(language).is_ok(), "{}", language);
+        }
+        assert!(SupportFilterLanguage::from_str("klingon").is_err());
+        assert!(SupportLanguageAlgorithm::from_str("klingon").is_err());
+    }
+}
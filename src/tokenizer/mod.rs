@@ -1,5 +1,6 @@
 pub mod ingredient;
 pub mod languages;
+mod stop_words;
 mod tests;
 pub mod parser;
 mod core;
@@ -2,10 +2,14 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::collections::HashMap;
 use jieba_rs::Jieba;
-use tantivy::tokenizer::{LowerCaser, NgramTokenizer, RawTokenizer, RemoveLongFilter, SimpleTokenizer, Stemmer, StopWordFilter, TextAnalyzer, TextAnalyzerBuilder, WhitespaceTokenizer};
+use tantivy::tokenizer::{LowerCaser, NgramTokenizer, RawTokenizer, RemoveLongFilter, SimpleTokenizer, Stemmer, TextAnalyzer, TextAnalyzerBuilder, Tokenizer as TantivyTokenizer, WhitespaceTokenizer};
 use crate::common::errors::TokenizerError;
 use crate::tokenizer::core::cangjie::{CangjieTokenizer, CangjieOption};
+use crate::tokenizer::core::cjk::CjkTokenizer;
+use crate::tokenizer::core::html_strip::HtmlStripTokenizer;
 use crate::tokenizer::core::icu::{IcuOption, IcuTokenizer};
+use crate::tokenizer::core::limit::{GraphemeLimitFilter, MaxTokensFilter};
+use crate::tokenizer::core::pinyin::PinyinFilter;
 use crate::tokenizer::ingredient::{Config, Tokenizer};
 use crate::tokenizer::languages::{SupportFilterLanguage, SupportLanguageAlgorithm};
 
@@ -28,16 +32,47 @@ pub struct TokenizerWrapper<'a> {
     tokenizer: &'a Tokenizer,
 }
 impl<'a> TokenizerWrapper<'a> {
+    /// Start a dynamic builder, stripping HTML markup before `tokenizer` when required.
+    fn builder_with_char_filter<T: TantivyTokenizer>(
+        tokenizer: T,
+        strip_html: bool,
+    ) -> TextAnalyzerBuilder {
+        if strip_html {
+            TextAnalyzer::builder(HtmlStripTokenizer::new(tokenizer)).dynamic()
+        } else {
+            TextAnalyzer::builder(tokenizer).dynamic()
+        }
+    }
+
+    /// `max_tokens_per_doc` caps the tokens produced by the tokenizer. `chinese`, `cjk` and `icu`
+    /// tokenizers cut the whole text before emitting, so they also get the cap and stop cutting early.
+    /// `char_length_limit` counts grapheme clusters and replaces the byte based `length_limit`.
+    fn apply_length_filters(
+        mut builder: TextAnalyzerBuilder,
+        length_limit: Option<usize>,
+        char_length_limit: Option<usize>,
+        max_tokens_per_doc: Option<usize>,
+    ) -> TextAnalyzerBuilder {
+        if let Some(max_tokens) = max_tokens_per_doc {
+            builder = builder.filter_dynamic(MaxTokensFilter::limit(max_tokens));
+        }
+        match (char_length_limit, length_limit) {
+            (Some(limit), _) => builder.filter_dynamic(GraphemeLimitFilter::limit(limit)),
+            (None, Some(limit)) => builder.filter_dynamic(RemoveLongFilter::limit(limit)),
+            (None, None) => builder,
+        }
+    }
+
     fn apply_stop_word_filters(
         mut builder: TextAnalyzerBuilder,
         stop_word_filters: &Vec<String>,
     ) -> TextAnalyzerBuilder {
         for stop_word_filter in stop_word_filters {
-            let language = SupportFilterLanguage::from_str(stop_word_filter.as_str())
+            let filter = SupportFilterLanguage::from_str(stop_word_filter.as_str())
                 .unwrap()
-                .to_language();
-            if language.is_some() {
-                builder = builder.filter_dynamic(StopWordFilter::new(language.unwrap()).unwrap());
+                .to_stop_word_filter();
+            if let Some(filter) = filter {
+                builder = builder.filter_dynamic(filter);
             }
         }
         builder
@@ -66,13 +101,14 @@ impl<'a> TokenizerWrapper<'a> {
 
     pub fn is_store_doc(&self) -> bool {
         match self.tokenizer {
-            Tokenizer::Default { store_doc }
-            | Tokenizer::Raw { store_doc }
+            Tokenizer::Default { store_doc, .. }
+            | Tokenizer::Raw { store_doc, .. }
             | Tokenizer::Simple { store_doc, .. }
             | Tokenizer::Stem { store_doc, .. }
             | Tokenizer::Whitespace { store_doc, .. }
             | Tokenizer::Ngram { store_doc, .. }
             | Tokenizer::Chinese { store_doc, .. }
+            | Tokenizer::Cjk { store_doc, .. }
             | Tokenizer::Icu { store_doc, .. } => *store_doc
         }
     }
@@ -86,6 +122,7 @@ impl<'a> TokenizerWrapper<'a> {
             | Tokenizer::Whitespace { case_sensitive, .. }
             | Tokenizer::Ngram { case_sensitive, .. }
             | Tokenizer::Icu { case_sensitive, .. }
+            | Tokenizer::Cjk { case_sensitive, .. }
             | Tokenizer::Chinese { case_sensitive, .. } => !*case_sensitive
         };
         return lower_case;
@@ -100,31 +137,38 @@ impl<'a> TokenizerWrapper<'a> {
             Tokenizer::Whitespace { .. } => "whitespace",
             Tokenizer::Ngram { .. } => "ngram",
             Tokenizer::Chinese { .. } => "chinese",
+            Tokenizer::Cjk { .. } => "cjk",
             Tokenizer::Icu { .. } => "icu"
         }
     }
 
     pub fn generate_text_analyzer(&self) -> Result<TextAnalyzer, TokenizerError> {
         match self.tokenizer {
-            Tokenizer::Default { .. } => {
-                Ok(
-                    TextAnalyzer::builder(SimpleTokenizer::default())
-                        .filter(RemoveLongFilter::limit(40))
-                        .filter(LowerCaser)
-                        .build()
-                )
+            Tokenizer::Default { char_length_limit, max_tokens_per_doc, .. } => {
+                let mut builder = TextAnalyzer::builder(SimpleTokenizer::default()).dynamic();
+                builder = Self::apply_length_filters(builder, Some(40), *char_length_limit, *max_tokens_per_doc);
+                Ok(builder.filter_dynamic(LowerCaser).build())
             }
-            Tokenizer::Raw { .. } => {
-                Ok(TextAnalyzer::builder(RawTokenizer::default()).build())
+            Tokenizer::Raw { char_length_limit, max_tokens_per_doc, .. } => {
+                let builder = TextAnalyzer::builder(RawTokenizer::default()).dynamic();
+                Ok(Self::apply_length_filters(builder, None, *char_length_limit, *max_tokens_per_doc).build())
             }
             Tokenizer::Simple {
                 stop_word_filters,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
                 ..
             } => {
-                let mut builder = TextAnalyzer::builder(SimpleTokenizer::default()).dynamic();
-                builder = builder.filter_dynamic(RemoveLongFilter::limit(*length_limit));
+                let mut builder = Self::builder_with_char_filter(SimpleTokenizer::default(), *strip_html);
+                builder = Self::apply_length_filters(
+                    builder,
+                    Some(*length_limit),
+                    *char_length_limit,
+                    *max_tokens_per_doc,
+                );
                 builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
                 if *case_sensitive == false {
                     builder = builder.filter_dynamic(LowerCaser);
@@ -134,12 +178,20 @@ impl<'a> TokenizerWrapper<'a> {
             Tokenizer::Stem {
                 stop_word_filters,
                 stem_languages,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
                 ..
             } => {
-                let mut builder = TextAnalyzer::builder(SimpleTokenizer::default()).dynamic();
-                builder = builder.filter_dynamic(RemoveLongFilter::limit(*length_limit));
+                let mut builder = Self::builder_with_char_filter(SimpleTokenizer::default(), *strip_html);
+                builder = Self::apply_length_filters(
+                    builder,
+                    Some(*length_limit),
+                    *char_length_limit,
+                    *max_tokens_per_doc,
+                );
                 builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
                 builder = Self::apply_stem_word_filters(builder, &stem_languages);
                 if *case_sensitive == false {
@@ -150,13 +202,21 @@ impl<'a> TokenizerWrapper<'a> {
             }
             Tokenizer::Whitespace {
                 stop_word_filters,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
                 ..
             } => {
                 let mut builder =
-                    TextAnalyzer::builder(WhitespaceTokenizer::default()).dynamic();
-                builder = builder.filter_dynamic(RemoveLongFilter::limit(*length_limit));
+                    Self::builder_with_char_filter(WhitespaceTokenizer::default(), *strip_html);
+                builder = Self::apply_length_filters(
+                    builder,
+                    Some(*length_limit),
+                    *char_length_limit,
+                    *max_tokens_per_doc,
+                );
                 builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
                 if *case_sensitive == false {
                     builder = builder.filter_dynamic(LowerCaser);
@@ -168,7 +228,10 @@ impl<'a> TokenizerWrapper<'a> {
                 max_gram,
                 prefix_only,
                 stop_word_filters,
+                strip_html,
                 length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
                 ..
             } => {
@@ -178,15 +241,21 @@ impl<'a> TokenizerWrapper<'a> {
                     ));
                 }
 
-                let mut builder = TextAnalyzer::builder(
+                let mut builder = Self::builder_with_char_filter(
                     NgramTokenizer::new(
                         *min_gram,
                         *max_gram,
                         *prefix_only,
-                    ).map_err(|e| { TokenizerError::TantivyError(e) })?
-                ).dynamic();
+                    ).map_err(|e| { TokenizerError::TantivyError(e) })?,
+                    *strip_html,
+                );
 
-                builder = builder.filter_dynamic(RemoveLongFilter::limit(*length_limit));
+                builder = Self::apply_length_filters(
+                    builder,
+                    Some(*length_limit),
+                    *char_length_limit,
+                    *max_tokens_per_doc,
+                );
                 builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
                 if *case_sensitive == false {
                     builder = builder.filter_dynamic(LowerCaser);
@@ -198,6 +267,11 @@ impl<'a> TokenizerWrapper<'a> {
                 jieba,
                 mode,
                 hmm,
+                pinyin,
+                stop_word_filters,
+                strip_html,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
                 ..
             } => {
@@ -215,21 +289,57 @@ impl<'a> TokenizerWrapper<'a> {
                     _ => CangjieOption::Unicode, // default option
                 };
 
-                let mut builder = TextAnalyzer::builder(CangjieTokenizer {
-                    worker: Arc::new(jieba_mode),
-                    option: tokenizer_option,
-                }).dynamic();
+                let mut builder = Self::builder_with_char_filter(
+                    CangjieTokenizer {
+                        worker: Arc::new(jieba_mode),
+                        option: tokenizer_option,
+                        max_tokens: *max_tokens_per_doc,
+                    },
+                    *strip_html,
+                );
 
+                builder = Self::apply_length_filters(builder, None, *char_length_limit, *max_tokens_per_doc);
+                builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
+                if *pinyin {
+                    builder = builder.filter_dynamic(PinyinFilter);
+                }
+                if *case_sensitive == false {
+                    builder = builder.filter_dynamic(LowerCaser);
+                }
+                Ok(builder.build())
+            }
+            Tokenizer::Cjk {
+                stop_word_filters,
+                strip_html,
+                length_limit,
+                char_length_limit,
+                max_tokens_per_doc,
+                case_sensitive,
+                ..
+            } => {
+                let mut builder = Self::builder_with_char_filter(
+                    CjkTokenizer { max_tokens: *max_tokens_per_doc },
+                    *strip_html,
+                );
+                builder = Self::apply_length_filters(
+                    builder,
+                    Some(*length_limit),
+                    *char_length_limit,
+                    *max_tokens_per_doc,
+                );
+                builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
                 if *case_sensitive == false {
                     builder = builder.filter_dynamic(LowerCaser);
                 }
                 Ok(builder.build())
             }
-
             Tokenizer::Icu {
                 mode,
                 stop_word_filters,
                 stem_languages,
+                strip_html,
+                char_length_limit,
+                max_tokens_per_doc,
                 case_sensitive,
                 ..
             } => {
@@ -241,9 +351,14 @@ impl<'a> TokenizerWrapper<'a> {
                     _ => IcuOption::Word, // default option
                 };
 
-                let mut builder = TextAnalyzer::builder(IcuTokenizer {
-                    option: tokenizer_option,
-                }).dynamic();
+                let mut builder = Self::builder_with_char_filter(
+                    IcuTokenizer {
+                        option: tokenizer_option,
+                        max_tokens: *max_tokens_per_doc,
+                    },
+                    *strip_html,
+                );
+                builder = Self::apply_length_filters(builder, None, *char_length_limit, *max_tokens_per_doc);
                 builder = Self::apply_stop_word_filters(builder, &stop_word_filters);
                 builder = Self::apply_stem_word_filters(builder, &stem_languages);
                 if *case_sensitive == false {
@@ -263,7 +378,11 @@ impl TokenizerUtils {
     ) -> Result<HashMap<String, TokenizerConfig>, TokenizerError> {
         let mut tokenizer_map: HashMap<String, TokenizerConfig> = HashMap::new();
         for (col_name, col) in tokenizer_config.get_columns() {
-            let tokenizer = col.get_tokenizer();
+            // Non-text columns don't need a tokenizer.
+            let tokenizer = match col.get_tokenizer() {
+                Some(tokenizer) => tokenizer,
+                None => continue,
+            };
             let inner_tokenizer = TokenizerWrapper::new(tokenizer);
             let tokenizer_config = TokenizerConfig::new(
                 inner_tokenizer.get_tokenizer_name().to_string(),
@@ -346,6 +465,139 @@ mod tests {
         assert_eq!(res_7.len(), 2);
     }
 
+    #[test]
+    fn test_cjk_json_parameter(){
+        let param = r#"
+        {
+            "text": {
+                "tokenizer":{
+                    "type": "cjk",
+                    "store_doc": true
+                }
+            }
+        }
+        "#;
+        // japanese bigram
+        let res_0 = index_parameter_search_helper(param, "潮汐", 10);
+        assert_eq!(res_0.len(), 2);
+        // korean bigram
+        let res_1 = index_parameter_search_helper(param, "조석", 10);
+        assert_eq!(res_1.len(), 1);
+        assert_eq!(res_1[0].row_id, 3);
+        // words for other scripts
+        let res_2 = index_parameter_search_helper(param, "moon", 10);
+        assert_eq!(res_2[0].row_id, 1);
+    }
+
+    #[test]
+    fn test_cjk_stop_word_filters(){
+        let param = r#"
+        {
+            "text": {
+                "tokenizer":{
+                    "type": "chinese",
+                    "stop_word_filters": ["chinese"]
+                }
+            }
+        }
+        "#;
+        let mut config = TokenizerUtils::parser_index_json_parameter(param).unwrap();
+        let text_analyzer = &mut config.get_mut("text").unwrap().text_analyzer;
+        let mut token_stream = text_analyzer.token_stream("我们的月球");
+        let mut tokens: Vec<String> = vec![];
+        token_stream.process(&mut |token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, vec!["月球"]);
+    }
+
+    #[test]
+    fn test_length_limit_json_parameter(){
+        let param = r#"
+        {
+            "text": {
+                "tokenizer":{
+                    "type": "whitespace",
+                    "char_length_limit": 7,
+                    "max_tokens_per_doc": 3
+                }
+            }
+        }
+        "#;
+        let mut config = TokenizerUtils::parser_index_json_parameter(param).unwrap();
+        let text_analyzer = &mut config.get_mut("text").unwrap().text_analyzer;
+        let mut token_stream = text_analyzer.token_stream("中华人民共和国 Tidal-Locking moon earth sun");
+        let mut tokens: Vec<String> = vec![];
+        token_stream.process(&mut |token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, vec!["中华人民共和国", "moon"]);
+    }
+
+    #[test]
+    fn test_length_limit_default_and_raw(){
+        let param = r#"
+        {
+            "col1": { "tokenizer": { "type": "default", "char_length_limit": 7, "max_tokens_per_doc": 3 } },
+            "col2": { "tokenizer": { "type": "raw", "char_length_limit": 7 } },
+            "col3": { "tokenizer": { "type": "default" } }
+        }
+        "#;
+        let mut config = TokenizerUtils::parser_index_json_parameter(param).unwrap();
+        let mut tokens_of = |column: &str, text: &str| {
+            let text_analyzer = &mut config.get_mut(column).unwrap().text_analyzer;
+            let mut token_stream = text_analyzer.token_stream(text);
+            let mut tokens: Vec<String> = vec![];
+            token_stream.process(&mut |token| tokens.push(token.text.clone()));
+            tokens
+        };
+        assert_eq!(tokens_of("col1", "中华人民共和国 TidalLocking Moon earth"), vec!["中华人民共和国", "moon"]);
+        assert_eq!(tokens_of("col2", "中华人民共和国"), vec!["中华人民共和国"]);
+        assert!(tokens_of("col2", "Tidal Locking").is_empty());
+        // Byte based limit of 40 is kept without `char_length_limit`.
+        assert_eq!(tokens_of("col3", &format!("{} moon", "a".repeat(41))), vec!["moon"]);
+    }
+
+    #[test]
+    fn test_strip_html_json_parameter(){
+        let param = r#"
+        {
+            "text": {
+                "tokenizer":{
+                    "type": "simple",
+                    "strip_html": true
+                }
+            }
+        }
+        "#;
+        let mut config = TokenizerUtils::parser_index_json_parameter(param).unwrap();
+        let text_analyzer = &mut config.get_mut("text").unwrap().text_analyzer;
+        let mut token_stream = text_analyzer.token_stream("<div>Hello&nbsp;<i>World</i></div><style>p{}</style>");
+        let mut tokens: Vec<String> = vec![];
+        token_stream.process(&mut |token| tokens.push(token.text.clone()));
+        assert_eq!(tokens, vec!["hello", "world"]);
+    }
+
+    #[test]
+    fn test_chinese_pinyin_json_parameter(){
+        let param = r#"
+        {
+            "text": {
+                "tokenizer":{
+                    "type": "chinese",
+                    "pinyin": true,
+                    "store_doc": true
+                }
+            }
+        }
+        "#;
+        // full pinyin
+        let res_0 = index_parameter_search_helper(param, "chaoxi", 10);
+        assert!(res_0.iter().any(|item| item.row_id == 0));
+        // pinyin initials
+        let res_1 = index_parameter_search_helper(param, "cx", 10);
+        assert!(res_1.iter().any(|item| item.row_id == 0));
+        // original chinese token
+        let res_2 = index_parameter_search_helper(param, "潮汐", 10);
+        assert!(res_2.iter().any(|item| item.row_id == 0));
+    }
+
 
     fn index_parameter_search_helper(
         index_json_parameter:&str,
@@ -0,0 +1,97 @@
+//! Stop word lists for languages that tantivy doesn't ship one for.
+//! Tokens are compared before lower casing, so every word is written in lower case.
+//!
+//! Chinese, Japanese and Korean lists hold whole words, they are meant for tokenizers which
+//! segment words: `chinese` (jieba) for Chinese, `icu` with `word` mode for Japanese and Korean.
+//! The `cjk` tokenizer emits overlapping bigrams, so only its two-char tokens and single-char
+//! runs can match, most particles are never removed by these lists.
+
+pub const ARABIC: &[&str] = &[
+    "من", "ومن", "منها", "منه", "في", "وفي", "فيها", "فيه", "و", "ف", "ثم", "او", "أو", "ب", "بها",
+    "به", "ا", "أ", "اى", "اي", "أي", "أى", "لا", "ولا", "الا", "ألا", "إلا", "لكن", "ما", "وما",
+    "كما", "فما", "عن", "مع", "اذا", "إذا", "ان", "أن", "إن", "انها", "أنها", "إنها", "انه", "أنه",
+    "إنه", "بان", "بأن", "فان", "فأن", "وان", "وأن", "وإن", "التى", "التي", "الذى", "الذي", "الذين",
+    "الى", "الي", "إلى", "إلي", "على", "عليها", "عليه", "اما", "أما", "إما", "ايضا", "أيضا", "كل",
+    "وكل", "لم", "ولم", "لن", "ولن", "هى", "هي", "هو", "وهى", "وهي", "وهو", "فهى", "فهي", "فهو",
+    "انت", "أنت", "لك", "لها", "له", "هذه", "هذا", "تلك", "ذلك", "هناك", "كانت", "كان", "يكون",
+    "تكون", "وكانت", "وكان", "غير", "بعض", "قد", "نحو", "بين", "بينما", "منذ", "ضمن", "حيث", "الان",
+    "الآن", "خلال", "بعد", "قبل", "حتى", "عند", "عندما", "لدى", "جميع",
+];
+
+pub const GREEK: &[&str] = &[
+    "ο", "η", "το", "οι", "τα", "του", "της", "των", "τον", "την", "τους", "τις", "και", "κι", "κ",
+    "είναι", "ειναι", "ήταν", "να", "θα", "με", "σε", "στο", "στη", "στην", "στον", "στα", "στους",
+    "στις", "από", "απο", "για", "προς", "που", "πως", "ως", "αλλά", "αλλα", "ή", "αν", "δεν", "μη",
+    "μην", "ένα", "ενα", "μια", "μία", "ενός", "μιας", "αυτό", "αυτή", "αυτός", "αυτά", "αυτοί",
+    "αυτές", "αυτών", "επί", "κατά", "μετά", "παρά", "αντί", "υπό", "έως", "ότι", "οτι", "όπως",
+    "όμως", "ενώ", "έχει", "έχουν", "μας", "σας", "μου", "σου", "τι", "τη",
+];
+
+pub const ROMANIAN: &[&str] = &[
+    "a", "acea", "aceasta", "această", "aceea", "acei", "aceia", "acel", "acela", "acele", "acelea",
+    "acest", "acesta", "aceste", "acestea", "acestei", "acestui", "aceşti", "aceştia", "acești",
+    "aceștia", "acolo", "acum", "ai", "aia", "aici", "al", "ale", "alt", "alta", "alte", "alti", "am",
+    "ar", "are", "aş", "aș", "aşa", "așa", "asta", "astfel", "au", "avea", "avem", "aveţi", "aveți",
+    "avut", "ca", "că", "cât", "câte", "câţi", "câți", "către", "care", "căreia", "căror", "căruia",
+    "ce", "cea", "ceea", "cei", "cel", "cele", "celor", "ceva", "chiar", "ci", "cine", "cineva", "cu",
+    "cum", "da", "dacă", "dar", "de", "deci", "deja", "deşi", "deși", "din", "dintre", "doar", "după",
+    "ea", "ei", "el", "ele", "era", "este", "eu", "fi", "fie", "fiecare", "fost", "iar", "în",
+    "înainte", "între", "îşi", "își", "la", "le", "li", "lor", "lui", "mai", "mea", "mei", "mele",
+    "meu", "mi", "mie", "mult", "multe", "mulţi", "mulți", "ne", "nici", "nimic", "nişte", "niște",
+    "noi", "nostru", "nu", "o", "oricare", "orice", "pe", "pentru", "peste", "poate", "prea", "prin",
+    "sa", "să", "săi", "sale", "sau", "se", "şi", "și", "sînt", "sunt", "suntem", "sunteţi",
+    "sunteți", "ta", "tale", "te", "ţi", "ți", "toată", "toate", "tot", "toţi", "toți", "totuşi",
+    "totuși", "tu", "un", "una", "unde", "unei", "unele", "unor", "unui", "unul", "va", "vă", "voi",
+    "vom", "vor",
+];
+
+pub const TAMIL: &[&str] = &[
+    "அது", "இது", "ஒரு", "என்று", "மற்றும்", "இந்த", "அந்த", "என", "என்ற", "உள்ள", "போது", "பற்றி",
+    "இருந்து", "வரை", "மேலும்", "ஆனால்", "அல்லது", "என்பது", "இல்லை", "அவர்", "அவர்கள்", "நான்",
+    "நாம்", "நீ", "நீங்கள்", "தான்", "கொண்டு", "அதன்", "இதன்", "அவன்", "அவள்", "எந்த", "எப்படி",
+    "ஏன்", "யார்", "எங்கே", "அங்கு", "இங்கு", "பின்", "முன்", "மிக", "போல", "உடன்", "ஆகிய", "ஆகும்",
+    "உள்ளது", "இருக்கும்", "இருந்த",
+];
+
+pub const TURKISH: &[&str] = &[
+    "acaba", "ama", "ancak", "artık", "aslında", "ayrıca", "az", "bazı", "belki", "ben", "bile",
+    "bir", "biri", "birkaç", "birşey", "biz", "bu", "buna", "bunu", "bunun", "çok", "çünkü", "da",
+    "daha", "de", "defa", "diğer", "diye", "dolayı", "eğer", "en", "fakat", "gibi", "göre", "hem",
+    "hep", "hepsi", "her", "hiç", "için", "ile", "ise", "kadar", "kez", "ki", "kim", "mı", "mi", "mu",
+    "mü", "nasıl", "ne", "neden", "nerde", "nerede", "nereye", "niçin", "niye", "o", "olan",
+    "olarak", "oldu", "olduğu", "olur", "onlar", "sanki", "sen", "siz", "sonra", "şey", "şöyle",
+    "şu", "tüm", "ve", "veya", "ya", "yani",
+];
+
+/// For the `chinese` tokenizer.
+pub const CHINESE: &[&str] = &[
+    "的", "了", "和", "是", "在", "就", "都", "而", "及", "与", "着", "或", "一个", "没有", "我们",
+    "你们", "他们", "她们", "它们", "这", "那", "这个", "那个", "这些", "那些", "之", "也", "又",
+    "但", "但是", "因为", "所以", "如果", "虽然", "并", "并且", "而且", "对", "对于", "把", "被",
+    "让", "给", "从", "向", "以", "为", "为了", "由", "于", "等", "啊", "吧", "呢", "吗", "哦", "嗯",
+    "呀", "么", "得", "地", "其", "其中", "此", "该", "各", "每", "某", "个", "我", "你", "他", "她",
+    "它",
+];
+
+/// For the `icu` tokenizer with `word` mode, single kana particles never match `cjk` bigrams.
+pub const JAPANESE: &[&str] = &[
+    "の", "に", "は", "を", "た", "が", "で", "て", "と", "し", "れ", "さ", "ある", "いる", "も",
+    "する", "から", "な", "こと", "として", "い", "や", "れる", "など", "なっ", "ない", "この",
+    "ため", "その", "あっ", "よう", "また", "もの", "という", "あり", "まで", "られ", "なる", "へ",
+    "か", "だ", "これ", "によって", "により", "おり", "より", "による", "ず", "なり", "られる",
+    "において", "ば", "なかっ", "なく", "しかし", "について", "せ", "だっ", "その後", "できる",
+    "それ", "う", "ので", "なお", "のみ", "でき", "き", "つ", "における", "および", "いう", "さらに",
+    "でも", "ら", "たり", "その他", "に関する", "たち", "ます", "ん", "なら", "に対して", "特に",
+    "せる", "及び", "これら", "とき", "では", "にて", "ほか", "ながら", "うち", "そして",
+    "とともに", "ただし", "かつて", "それぞれ", "または", "お", "ほど", "ものの", "に対する",
+    "ほとんど", "と共に", "といった", "です", "とも", "ところ", "ここ",
+];
+
+/// For the `icu` tokenizer with `word` mode.
+pub const KOREAN: &[&str] = &[
+    "이", "그", "저", "것", "수", "등", "들", "및", "에서", "으로", "로", "에게", "의", "가", "을",
+    "를", "은", "는", "에", "와", "과", "도", "으로서", "하다", "있다", "되다", "그리고", "그러나",
+    "하지만", "또는", "또한", "그런데", "그래서", "때문에", "위해", "대한", "대해", "통해", "같은",
+    "이런", "그런", "저런", "우리", "저희", "너희", "나", "너", "그녀", "그들", "이것", "그것",
+    "저것", "여기", "거기", "저기", "아", "어", "요",
+];
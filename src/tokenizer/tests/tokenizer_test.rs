@@ -127,15 +127,17 @@ mod tests {
             top_k: &10,
             query_with_filter: &false,
             u8_alive_bitmap: &vec![],
+            u64_alive_bitmap: &None,
             need_doc: &true,
             operation_or: &true,
+            filter_query: "",
         };
         let query_executor: QueryExecutor<'_, Vec<RowIdWithScore>> =
             QueryExecutor::new(&bm25_sentence_strategy);
 
         // Compute query results.
         let result: Vec<RowIdWithScore> = query_executor
-            .execute(&index_reader_bridge.reader.searcher())
+            .execute(&index_reader_bridge.reader.searcher(), &index_reader_bridge.dropped_columns)
             .unwrap();
         println!("searched res count:{:?}", result.len());
         for re in result {
@@ -153,15 +155,17 @@ mod tests {
             top_k: &10,
             query_with_filter: &false,
             u8_alive_bitmap: &vec![],
+            u64_alive_bitmap: &None,
             need_doc: &true,
             operation_or: &true,
+            filter_query: "",
         };
         let query_executor: QueryExecutor<'_, Vec<RowIdWithScore>> =
             QueryExecutor::new(&bm25_nlq_strategy);
 
         // Compute query results.
         let result: Vec<RowIdWithScore> = query_executor
-            .execute(&index_reader_bridge.reader.searcher())
+            .execute(&index_reader_bridge.reader.searcher(), &index_reader_bridge.dropped_columns)
             .unwrap();
         println!("searched res count:{:?}", result.len());
         for re in result {
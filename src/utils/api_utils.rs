@@ -1,6 +1,6 @@
 use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::{common::constants::LOG_CALLBACK, ERROR};
-use crate::ffi::{DocWithFreq, FFIBoolResult, FFIError, FFIFieldTokenNumsResult, FFIU64Result, FFIVecDocWithFreqResult, FFIVecRowIdWithScoreResult, FFIVecU8Result, FieldTokenNums, RowIdWithScore};
+use crate::ffi::{DocWithFreq, FFIBoolResult, FFIError, FFIFieldTokenNumsResult, FFIStringResult, FFIU64Result, FFIVecDocWithFreqResult, FFIVecRowIdWithScoreResult, FFIVecU8Result, FieldTokenNums, RowIdWithScore};
 
 pub trait FFIResult<T> {
     fn from_error(error_message: String) -> Self;
@@ -42,6 +42,18 @@ impl FFIResult<Vec<u8>> for FFIU64Result {
     }
 }
 
+impl FFIResult<String> for FFIStringResult {
+    fn from_error(error_message: String) -> Self {
+        FFIStringResult {
+            result: String::new(),
+            error: FFIError {
+                is_error: true,
+                message: error_message,
+            },
+        }
+    }
+}
+
 impl FFIResult<Vec<RowIdWithScore>> for FFIVecRowIdWithScoreResult {
     fn from_error(error_message: String) -> Self {
         FFIVecRowIdWithScoreResult {
@@ -5,30 +5,114 @@ use std::fs;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use tantivy::Index;
+use tantivy::schema::Schema;
 use crate::common::errors::{IndexUtilsError};
-use crate::tokenizer::ingredient::Config;
+use crate::tokenizer::ingredient::{Config, ReaderSettings, WriterSettings};
 use crate::logger::logger_bridge::TantivySearchLogger;
 use crate::{common::constants::LOG_CALLBACK, DEBUG, WARNING};
 use crate::common::constants::INDEX_INFO_FILE_NAME;
 use crate::tokenizer::parser::{TokenizerConfig, TokenizerUtils};
 
+/// Format version of `custom_index_setting.json` written by this library.
+/// - 0: only `tokenizers_json_parameter`, written before the file was versioned.
+/// - 1: adds library version, column names, writer settings, scoring params and creation time.
+/// - 2: adds dropped columns, which are hidden but still in the tantivy schema.
+///
+/// Readers migrate an older file in memory on every load, `upgrade_index_parameter`
+/// writes it back in current format when an index writer is opened.
+pub const INDEX_SETTING_FORMAT_VERSION: u32 = 2;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct IndexParameterInDisk {
+    // Missing in format 0, deserialized as 0.
+    #[serde(default)]
+    pub format_version: u32,
+    // Version of tantivy_search which wrote the file, empty if unknown.
+    #[serde(default)]
+    pub library_version: String,
     #[serde(default = "empty_index_json_parameter")]
     // Use `String` rather than `&'a str` will avoid lifetime issue while deserialize json string.
     pub tokenizers_json_parameter: String,
+    // Indexed columns without `row_id`.
+    #[serde(default)]
+    pub column_names: Vec<String>,
+    // Writer and merge settings, including the overrides of writer json parameter.
+    #[serde(default)]
+    pub writer_settings: WriterSettings,
+    #[serde(default)]
+    pub scoring: ScoringSettings,
+    // Columns dropped by `drop_column`, they stay in schema until documents are rebuilt.
+    // Their tokenizers are kept in `tokenizers_json_parameter` to index the fields of schema.
+    #[serde(default)]
+    pub dropped_columns: Vec<String>,
+    // Unix timestamp in seconds, 0 if unknown.
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// BM25 parameters the index is scored with. They are fixed by tantivy bm25 weight,
+/// so scores of indexes with different persisted params can't be compared.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ScoringSettings {
+    #[serde(default = "default_bm25_k1")]
+    pub bm25_k1: f32,
+    #[serde(default = "default_bm25_b")]
+    pub bm25_b: f32,
 }
 
 fn empty_index_json_parameter() -> String {
     "{}".to_string()
 }
 
+// Same as `K1` of tantivy `query::bm25`.
+fn default_bm25_k1() -> f32 {
+    1.2
+}
+
+// Same as `B` of tantivy `query::bm25`.
+fn default_bm25_b() -> f32 {
+    0.75
+}
+
+impl Default for ScoringSettings {
+    fn default() -> Self {
+        Self {
+            bm25_k1: default_bm25_k1(),
+            bm25_b: default_bm25_b(),
+        }
+    }
+}
+
 impl Default for IndexParameterInDisk {
     fn default() -> Self {
         Self {
+            format_version: INDEX_SETTING_FORMAT_VERSION,
+            library_version: env!("CARGO_PKG_VERSION").to_string(),
             tokenizers_json_parameter: "{}".to_string(),
+            column_names: Vec::new(),
+            writer_settings: WriterSettings::default(),
+            scoring: ScoringSettings::default(),
+            dropped_columns: Vec::new(),
+            created_at: 0,
+        }
+    }
+}
+
+impl IndexParameterInDisk {
+    pub fn new(tokenizers_json_parameter: String, column_names: Vec<String>, writer_settings: WriterSettings) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        Self {
+            tokenizers_json_parameter,
+            column_names,
+            writer_settings,
+            created_at,
+            ..Self::default()
         }
     }
 }
@@ -105,37 +189,142 @@ impl IndexUtils {
         return Ok(());
     }
 
+    // Upgrade index parameter written in an older format, newer format is refused
+    // because its settings may not be understood by this library.
+    fn migrate_index_parameter(mut parameter: IndexParameterInDisk) -> IndexJsonParameterResult {
+        if parameter.format_version > INDEX_SETTING_FORMAT_VERSION {
+            return Err(IndexUtilsError::UnsupportedFormatError(format!(
+                "format version {} written by tantivy_search {}, supported up to {}",
+                parameter.format_version, parameter.library_version, INDEX_SETTING_FORMAT_VERSION
+            )));
+        }
+        if parameter.format_version == 0 {
+            // Writer settings were only kept under `__writer__` key of index json parameter.
+            let config: Config = serde_json::from_str(parameter.tokenizers_json_parameter.as_str()).map_err(|e| {
+                IndexUtilsError::JsonDeserializeError(Self::format_error(&parameter, e))
+            })?;
+            parameter.writer_settings = config.get_writer_settings().clone();
+        }
+        // Format 1 has no dropped columns, they are deserialized as empty.
+        parameter.format_version = INDEX_SETTING_FORMAT_VERSION;
+        Ok(parameter)
+    }
+
+    // Load index parameter saved in the index directory, migrated to current format.
+    // Default parameter is used if the index directory has no settings file.
+    pub fn load_index_parameter(index_files_directory: &Path) -> IndexJsonParameterResult {
+        let path = index_files_directory.join(INDEX_INFO_FILE_NAME);
+        if !path.exists() {
+            return Ok(IndexParameterInDisk::default());
+        }
+        Self::read_file(&path)
+            .and_then(Self::parse_index_parameter)
+            .and_then(Self::migrate_index_parameter)
+    }
+
     pub fn load_tokenizer_config(index_files_directory: &Path) -> ConfigResult {
+        Self::load_index_parameter(index_files_directory).and_then(Self::deserialize_config)
+    }
+
+    // Load the raw index json parameter saved in the index directory.
+    pub fn load_index_json_parameter(index_files_directory: &Path) -> StringResult {
+        Ok(Self::load_index_parameter(index_files_directory)?.tokenizers_json_parameter)
+    }
+
+    // Load writer settings persisted with index parameter.
+    pub fn load_writer_settings(index_files_directory: &Path) -> Result<WriterSettings, IndexUtilsError> {
+        Ok(Self::load_index_parameter(index_files_directory)?.writer_settings)
+    }
+
+    // Load reader settings from `__reader__` key of index json parameter.
+    pub fn load_reader_settings(index_files_directory: &Path) -> Result<ReaderSettings, IndexUtilsError> {
+        Ok(Self::load_tokenizer_config(index_files_directory)?.get_reader_settings().clone())
+    }
+
+    // Columns of schema except `row_id` and dropped columns.
+    fn get_column_names(schema: &Schema, dropped_columns: &[String]) -> Vec<String> {
+        schema
+            .fields()
+            .map(|(_, field_entry)| field_entry.name().to_string())
+            .filter(|name| name != "row_id" && !dropped_columns.contains(name))
+            .collect()
+    }
+
+    // Refuse index parameter whose column names don't match the tantivy schema,
+    // such as a settings file copied from another index. Unknown column names are skipped.
+    pub fn check_column_names(parameter: &IndexParameterInDisk, schema: &Schema) -> EmptyResult {
+        if parameter.column_names.is_empty() {
+            return Ok(());
+        }
+        let mut expected = Self::get_column_names(schema, &parameter.dropped_columns);
+        let mut column_names = parameter.column_names.clone();
+        expected.sort();
+        column_names.sort();
+        if expected != column_names {
+            return Err(IndexUtilsError::ColumnNamesMismatchError(format!(
+                "settings file has {:?}, schema has {:?}", column_names, expected
+            )));
+        }
+        Ok(())
+    }
+
+    // Write index parameter of an older format back in current format, column names
+    // missing in format 0 are filled from the schema. Return whether the file is rewritten.
+    pub fn upgrade_index_parameter(index_files_directory: &Path, schema: &Schema) -> Result<bool, IndexUtilsError> {
         let path = index_files_directory.join(INDEX_INFO_FILE_NAME);
-        let config = if path.exists() {
-            Self::read_file(&path).and_then(Self::parse_index_parameter)
-        } else {
-            let default_parameter = IndexParameterInDisk::default();
-            Self::parse_index_parameter(default_parameter.tokenizers_json_parameter)
-        };
-        config.and_then(Self::deserialize_config)
+        if !path.exists() {
+            return Ok(false);
+        }
+        let parameter = Self::read_file(&path).and_then(Self::parse_index_parameter)?;
+        if parameter.format_version >= INDEX_SETTING_FORMAT_VERSION {
+            return Ok(false);
+        }
+        let mut parameter = Self::migrate_index_parameter(parameter)?;
+        if parameter.column_names.is_empty() {
+            parameter.column_names = Self::get_column_names(schema, &parameter.dropped_columns);
+        }
+        DEBUG!(function:"upgrade_index_parameter", "index parameter: {:?}", parameter);
+        Self::write_index_parameter(index_files_directory, &parameter)?;
+        Ok(true)
     }
 
     // Save custom index json parameter (json content) to the index directory.
     pub fn save_index_parameter_to_disk(
         path: &Path,
         tokenizer_json_parameter: String,
-        // FixMe: more custom settings.
+        column_names: Vec<String>,
+        writer_settings: WriterSettings,
         enable_recreate_directory: bool,
     ) -> EmptyResult {
         Self::initialize_index_directory(path, enable_recreate_directory)?;
 
-        let parameter = IndexParameterInDisk {
-            tokenizers_json_parameter: tokenizer_json_parameter.to_string(),
-        };
-        let file_path = path.join(INDEX_INFO_FILE_NAME);
+        let parameter = IndexParameterInDisk::new(tokenizer_json_parameter, column_names, writer_settings);
         DEBUG!(function:"save_index_parameter_to_disk", "index parameter: {:?}", parameter);
-        let setting_json = serde_json::to_string(&parameter).map_err(|e| {
+        Self::write_index_parameter(path, &parameter)
+    }
+
+    // Update index parameter saved in the index directory, other index files are kept.
+    pub fn update_index_parameter<F>(index_files_directory: &Path, update: F) -> EmptyResult
+    where
+        F: FnOnce(&mut IndexParameterInDisk),
+    {
+        let mut parameter = Self::load_index_parameter(index_files_directory)?;
+        update(&mut parameter);
+        DEBUG!(function:"update_index_parameter", "index parameter: {:?}", parameter);
+        Self::write_index_parameter(index_files_directory, &parameter)
+    }
+
+    // Write to a temporary file then rename it, so a failed write won't leave a broken settings file.
+    fn write_index_parameter(path: &Path, parameter: &IndexParameterInDisk) -> EmptyResult {
+        let file_path = path.join(INDEX_INFO_FILE_NAME);
+        let temp_file_path = path.join(format!("{}.tmp", INDEX_INFO_FILE_NAME));
+        let setting_json = serde_json::to_string(parameter).map_err(|e| {
             IndexUtilsError::JsonSerializeError(Self::format_path_error(file_path.as_path(), e))
         })?;
 
-        File::create(&file_path)
+        File::create(&temp_file_path)
             .and_then(|mut file: File| file.write_all(setting_json.as_bytes()))
+            .and_then(|_| fs::rename(&temp_file_path, &file_path))
             .map_err(|e| {
                 IndexUtilsError::WriteFileError(Self::format_path_error(file_path.as_path(), e))
             })
@@ -171,25 +360,38 @@ impl IndexUtils {
 
 #[cfg(test)]
 mod test {
-    use crate::utils::index_utils::IndexParameterInDisk;
+    use tantivy::schema::{Schema, FAST, INDEXED, TEXT};
+    use tempfile::TempDir;
+    use crate::common::constants::INDEX_INFO_FILE_NAME;
+    use crate::common::errors::IndexUtilsError;
+    use crate::tokenizer::ingredient::{MergePolicy, WriterSettings};
+    use crate::utils::index_utils::{IndexParameterInDisk, IndexUtils, ScoringSettings, INDEX_SETTING_FORMAT_VERSION};
 
     #[test]
     fn test_default() {
         let parameter = IndexParameterInDisk::default();
         assert_eq!(parameter.tokenizers_json_parameter, "{}");
+        assert_eq!(parameter.format_version, INDEX_SETTING_FORMAT_VERSION);
     }
 
     #[test]
     fn serialize_json_parameter() {
-        let parameter: IndexParameterInDisk = IndexParameterInDisk {
-            tokenizers_json_parameter: "{\"text\":{\"tokenizer\":{\"type\":\"default\"}}}"
-                .to_string(),
-        };
-        let json = serde_json::to_string(&parameter).unwrap();
+        let parameter: IndexParameterInDisk = IndexParameterInDisk::new(
+            "{\"text\":{\"tokenizer\":{\"type\":\"default\"}}}".to_string(),
+            vec!["text".to_string()],
+            WriterSettings::default(),
+        );
+        let json = serde_json::to_value(&parameter).unwrap();
+        assert_eq!(json["format_version"], INDEX_SETTING_FORMAT_VERSION);
+        assert_eq!(json["library_version"], env!("CARGO_PKG_VERSION"));
         assert_eq!(
-            json,
-            r#"{"tokenizers_json_parameter":"{\"text\":{\"tokenizer\":{\"type\":\"default\"}}}"}"#
+            json["tokenizers_json_parameter"],
+            "{\"text\":{\"tokenizer\":{\"type\":\"default\"}}}"
         );
+        assert_eq!(json["column_names"], serde_json::json!(["text"]));
+        assert_eq!(json["writer_settings"]["num_threads"], 2);
+        assert_eq!(json["scoring"]["bm25_b"], 0.75);
+        assert!(json["created_at"].as_u64().unwrap() > 0);
     }
 
     #[test]
@@ -201,5 +403,116 @@ mod test {
             parameter.tokenizers_json_parameter,
             "{\"text\":{\"tokenizer\":{\"type\":\"default\"}}}"
         );
+        assert_eq!(parameter.format_version, 0);
+        assert_eq!(parameter.scoring, ScoringSettings::default());
+        assert_eq!(parameter.scoring.bm25_k1, 1.2);
+    }
+
+    #[test]
+    fn migrate_json_parameter() {
+        let tmp_dir = TempDir::new().unwrap();
+
+        // Format 0, writer settings only exist in `__writer__` key.
+        let json = r#"{"tokenizers_json_parameter":"{\"__writer__\":{\"num_threads\":4,\"merge_policy\":{\"type\":\"no_merge\"}}}"}"#;
+        std::fs::write(tmp_dir.path().join(INDEX_INFO_FILE_NAME), json).unwrap();
+        let parameter = IndexUtils::load_index_parameter(tmp_dir.path()).unwrap();
+        assert_eq!(parameter.format_version, INDEX_SETTING_FORMAT_VERSION);
+        assert_eq!(parameter.writer_settings.num_threads, 4);
+        assert_eq!(parameter.writer_settings.merge_policy, MergePolicy::NoMerge);
+        assert_eq!(IndexUtils::load_writer_settings(tmp_dir.path()).unwrap().num_threads, 4);
+
+        // Saved in current format.
+        let writer_settings = WriterSettings { num_threads: 3, ..WriterSettings::default() };
+        IndexUtils::save_index_parameter_to_disk(
+            tmp_dir.path(), "{}".to_string(), vec!["col1".to_string()], writer_settings.clone(), true
+        ).unwrap();
+        let parameter = IndexUtils::load_index_parameter(tmp_dir.path()).unwrap();
+        assert_eq!(parameter.column_names, vec!["col1".to_string()]);
+        assert_eq!(parameter.writer_settings, writer_settings);
+        assert_eq!(parameter.library_version, env!("CARGO_PKG_VERSION"));
+
+        // Newer format, unknown keys are ignored but the version is refused.
+        let json = format!(
+            r#"{{"format_version":{},"library_version":"9.9.9","tokenizers_json_parameter":"{{}}","new_key":1}}"#,
+            INDEX_SETTING_FORMAT_VERSION + 1
+        );
+        std::fs::write(tmp_dir.path().join(INDEX_INFO_FILE_NAME), json).unwrap();
+        assert!(matches!(
+            IndexUtils::load_index_parameter(tmp_dir.path()),
+            Err(IndexUtilsError::UnsupportedFormatError(_))
+        ));
+        assert!(IndexUtils::load_tokenizer_config(tmp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn update_json_parameter() {
+        let tmp_dir = TempDir::new().unwrap();
+        IndexUtils::save_index_parameter_to_disk(
+            tmp_dir.path(), "{}".to_string(), vec!["col1".to_string(), "col2".to_string()], WriterSettings::default(), true
+        ).unwrap();
+        std::fs::write(tmp_dir.path().join("meta.json"), "{}").unwrap();
+
+        IndexUtils::update_index_parameter(tmp_dir.path(), |parameter| {
+            parameter.column_names.retain(|column_name| column_name != "col2");
+            parameter.dropped_columns.push("col2".to_string());
+        }).unwrap();
+        let parameter = IndexUtils::load_index_parameter(tmp_dir.path()).unwrap();
+        assert_eq!(parameter.column_names, vec!["col1".to_string()]);
+        assert_eq!(parameter.dropped_columns, vec!["col2".to_string()]);
+        // Other files of index directory are kept.
+        assert!(tmp_dir.path().join("meta.json").exists());
+    }
+
+    fn get_schema() -> Schema {
+        let mut schema_builder = Schema::builder();
+        schema_builder.add_u64_field("row_id", FAST | INDEXED);
+        schema_builder.add_text_field("col1", TEXT);
+        schema_builder.add_text_field("col2", TEXT);
+        schema_builder.build()
+    }
+
+    #[test]
+    fn check_column_names() {
+        let schema = get_schema();
+        let mut parameter = IndexParameterInDisk::new(
+            "{}".to_string(), vec!["col2".to_string(), "col1".to_string()], WriterSettings::default()
+        );
+        assert!(IndexUtils::check_column_names(&parameter, &schema).is_ok());
+
+        // Dropped columns are still in schema.
+        parameter.column_names = vec!["col1".to_string()];
+        assert!(IndexUtils::check_column_names(&parameter, &schema).is_err());
+        parameter.dropped_columns = vec!["col2".to_string()];
+        assert!(IndexUtils::check_column_names(&parameter, &schema).is_ok());
+
+        parameter.column_names = vec!["col1".to_string(), "col3".to_string()];
+        assert!(matches!(
+            IndexUtils::check_column_names(&parameter, &schema),
+            Err(IndexUtilsError::ColumnNamesMismatchError(_))
+        ));
+
+        // Column names are unknown in format 0.
+        parameter.column_names = Vec::new();
+        assert!(IndexUtils::check_column_names(&parameter, &schema).is_ok());
+    }
+
+    #[test]
+    fn upgrade_json_parameter() {
+        let tmp_dir = TempDir::new().unwrap();
+        let schema = get_schema();
+        assert!(!IndexUtils::upgrade_index_parameter(tmp_dir.path(), &schema).unwrap());
+
+        let json = r#"{"tokenizers_json_parameter":"{\"__writer__\":{\"num_threads\":4}}"}"#;
+        std::fs::write(tmp_dir.path().join(INDEX_INFO_FILE_NAME), json).unwrap();
+        assert!(IndexUtils::upgrade_index_parameter(tmp_dir.path(), &schema).unwrap());
+
+        let content = std::fs::read_to_string(tmp_dir.path().join(INDEX_INFO_FILE_NAME)).unwrap();
+        let parameter: IndexParameterInDisk = serde_json::from_str(&content).unwrap();
+        assert_eq!(parameter.format_version, INDEX_SETTING_FORMAT_VERSION);
+        assert_eq!(parameter.column_names, vec!["col1".to_string(), "col2".to_string()]);
+        assert_eq!(parameter.writer_settings.num_threads, 4);
+
+        // Current format is kept as it is.
+        assert!(!IndexUtils::upgrade_index_parameter(tmp_dir.path(), &schema).unwrap());
     }
 }